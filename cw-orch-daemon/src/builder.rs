@@ -4,7 +4,7 @@ use crate::{
     log::print_if_log_disabled,
     network_config,
     senders::{builder::SenderBuilder, CosmosOptions, CosmosWalletKey},
-    DaemonAsyncBase, DaemonBuilder, DaemonStateFile, TxSender, Wallet,
+    DaemonAsyncBase, DaemonBuilder, TxSender, Wallet,
 };
 
 use super::{error::DaemonError, state::DaemonState};
@@ -62,6 +62,7 @@ impl DaemonAsyncBuilder {
 
     /// Reuse already existent [`DaemonState`]
     /// Useful for multi-chain scenarios
+    /// Also allows using another storage for the state, see [`DaemonState::from_backend`]
     pub fn state(&mut self, state: DaemonState) -> &mut Self {
         self.state = Some(state);
         self
@@ -189,14 +190,8 @@ impl DaemonAsyncBuilder {
                 if let Some(write_on_change) = self.write_on_change {
                     state.write_on_change = write_on_change;
                 }
-                // It's most likely a new chain, need to "prepare" json state for writes
-                if let DaemonStateFile::FullAccess { json_file_state } = &state.json_state {
-                    let mut json_file_lock = json_file_state.lock().unwrap();
-                    json_file_lock.prepare(&state.chain_data.chain_id, &state.deployment_id);
-                    if state.write_on_change {
-                        json_file_lock.force_write();
-                    }
-                }
+                // It's most likely a new chain, need to "prepare" state for writes
+                state.prepare()?;
                 state
             }
             None => {
@@ -13,6 +13,16 @@ pub struct GrpcChannel {}
 impl GrpcChannel {
     /// Connect to any of the provided gRPC endpoints
     pub async fn connect(grpc: &[String], chain_id: &str) -> Result<Channel, DaemonError> {
+        GrpcChannel::connect_endpoint(grpc, chain_id)
+            .await
+            .map(|(channel, _)| channel)
+    }
+
+    /// Connect to any of the provided gRPC endpoints, also returning the url of the endpoint the channel is connected to
+    pub(crate) async fn connect_endpoint(
+        grpc: &[String],
+        chain_id: &str,
+    ) -> Result<(Channel, String), DaemonError> {
         if grpc.is_empty() {
             return Err(DaemonError::GRPCListIsEmpty);
         }
@@ -63,7 +73,7 @@ impl GrpcChannel {
             }
 
             // add endpoint to succesful connections
-            successful_connections.push(channel);
+            successful_connections.push((channel, address.clone()));
         }
 
         // we could not get any succesful connections
@@ -78,6 +88,13 @@ impl GrpcChannel {
     pub async fn from_chain_info(chain_info: &ChainInfoOwned) -> Result<Channel, DaemonError> {
         GrpcChannel::connect(&chain_info.grpc_urls, &chain_info.chain_id).await
     }
+
+    /// Create a gRPC channel from the chain info, also returning the url of the endpoint the channel is connected to
+    pub(crate) async fn endpoint_from_chain_info(
+        chain_info: &ChainInfoOwned,
+    ) -> Result<(Channel, String), DaemonError> {
+        GrpcChannel::connect_endpoint(&chain_info.grpc_urls, &chain_info.chain_id).await
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +127,13 @@ pub enum DaemonError {
     OpenFile(String, String),
     #[error("State file {0} already locked, use another state file, clone daemon which holds the lock, or use `state` method of Builder")]
     StateAlreadyLocked(String),
+    #[error("Unexpected daemon state format: {0}")]
+    InvalidStateFormat(String),
+    #[error("Deployment id {0} is reserved by the daemon state, use another deployment id")]
+    ReservedDeploymentId(String),
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] ::rusqlite::Error),
 }
 
 impl DaemonError {
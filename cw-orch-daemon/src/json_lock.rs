@@ -1,7 +1,10 @@
-use crate::DaemonError;
+use crate::{DaemonError, DaemonStateBackend};
 use file_lock::{FileLock, FileOptions};
 use serde_json::{from_reader, json, Value};
-use std::{fs::File, io::Seek};
+use std::{
+    fs::File,
+    io::{ErrorKind, Seek},
+};
 
 /// State file reader and writer
 /// Mainly used by [`crate::Daemon`] and [`crate::DaemonAsync`], but could also be used for tests or custom edits of the state
@@ -15,7 +18,8 @@ pub struct JsonLockedState {
 impl JsonLockedState {
     /// Lock a state files
     /// Other process won't be able to lock it
-    pub fn new(path: &str) -> Self {
+    /// Errors if the file is already locked or doesn't contain a valid state
+    pub fn new(path: &str) -> Result<Self, DaemonError> {
         // open file pointer set read/write permissions to true
         // create it if it does not exists
         // don't truncate it
@@ -27,25 +31,28 @@ impl JsonLockedState {
             .truncate(false);
 
         // Lock file, non blocking so it errors in case someone else already holding lock of it
-        let lock: FileLock = FileLock::lock(path, false, options)
-            .unwrap_or_else(|_| panic!("Was not able to receive {path} state lock"));
+        let lock: FileLock =
+            FileLock::lock(path, false, options).map_err(|err| match err.kind() {
+                ErrorKind::WouldBlock => DaemonError::StateAlreadyLocked(path.to_owned()),
+                _ => DaemonError::OpenFile(path.to_owned(), err.to_string()),
+            })?;
 
         // return empty json object if file is empty
         // return file content if not
-        let json: Value = if lock.file.metadata().unwrap().len().eq(&0) {
+        let json: Value = if lock.file.metadata()?.len().eq(&0) {
             json!({})
         } else {
-            let json: Value = from_reader(&lock.file).unwrap();
-            patch_state_if_old(json)
+            let json: Value = from_reader(&lock.file)?;
+            patch_state_if_old(json)?
         };
 
         let filename = path.to_owned();
 
-        JsonLockedState {
+        Ok(JsonLockedState {
             lock,
             json,
             path: filename,
-        }
+        })
     }
 
     /// Prepare json for further writes
@@ -70,16 +77,17 @@ impl JsonLockedState {
         &self.json[chain_id]
     }
 
-    /// Give a value to write
-    pub fn get_mut(&mut self, chain_id: &str) -> &mut Value {
-        self.json.get_mut(chain_id).unwrap()
+    /// Give a value to write, `None` if the chain has no state
+    pub fn get_mut(&mut self, chain_id: &str) -> Option<&mut Value> {
+        self.json.get_mut(chain_id)
     }
 
     /// Force write to a file
-    pub fn force_write(&mut self) {
-        self.lock.file.set_len(0).unwrap();
-        self.lock.file.rewind().unwrap();
-        serde_json::to_writer_pretty(&self.lock.file, &self.json).unwrap();
+    pub fn force_write(&mut self) -> Result<(), DaemonError> {
+        self.lock.file.set_len(0)?;
+        self.lock.file.rewind()?;
+        serde_json::to_writer_pretty(&self.lock.file, &self.json)?;
+        Ok(())
     }
 
     pub fn path(&self) -> &str {
@@ -87,10 +95,57 @@ impl JsonLockedState {
     }
 }
 
+impl DaemonStateBackend for JsonLockedState {
+    fn prepare(&mut self, chain_id: &str, deployment_id: &str) -> Result<(), DaemonError> {
+        JsonLockedState::prepare(self, chain_id, deployment_id);
+        Ok(())
+    }
+
+    fn chain_state(&self, chain_id: &str) -> Result<Value, DaemonError> {
+        Ok(self.get(chain_id).clone())
+    }
+
+    fn set_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+        value: Value,
+    ) -> Result<(), DaemonError> {
+        self.json[chain_id][key][contract_id] = value;
+        Ok(())
+    }
+
+    fn remove_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+    ) -> Result<(), DaemonError> {
+        self.json[chain_id][key][contract_id] = Value::Null;
+        Ok(())
+    }
+
+    fn clear_chain(&mut self, chain_id: &str) -> Result<(), DaemonError> {
+        self.json[chain_id] = json!({});
+        Ok(())
+    }
+
+    fn persist(&mut self) -> Result<(), DaemonError> {
+        self.force_write()
+    }
+
+    fn full_state(&self) -> Result<Value, DaemonError> {
+        Ok(self.state())
+    }
+}
+
 // Write json when dropping
 impl Drop for JsonLockedState {
     fn drop(&mut self) {
-        self.force_write()
+        if let Err(err) = self.force_write() {
+            log::error!("Failed to write the daemon state to {}: {err}", self.path);
+        }
     }
 }
 
@@ -101,38 +156,37 @@ pub fn read(filename: &String) -> Result<Value, DaemonError> {
     Ok(json)
 }
 
-pub(crate) fn patch_state_if_old(maybe_old: Value) -> Value {
-    let expect_object = |v: Value| -> serde_json::Map<String, Value> {
+/// Patches states using the old format (indexed by chain name, then chain id) into the current format (indexed by chain id)
+pub(crate) fn patch_state_if_old(maybe_old: Value) -> Result<Value, DaemonError> {
+    let expect_object = |v: Value| -> Result<serde_json::Map<String, Value>, DaemonError> {
         let Value::Object(map) = v else {
-            panic!("Unexpected daemon state format");
+            return Err(DaemonError::InvalidStateFormat(v.to_string()));
         };
-        map
+        Ok(map)
     };
 
-    let maybe_old_map = expect_object(maybe_old);
+    let maybe_old_map = expect_object(maybe_old)?;
     let mut maybe_old_iter = maybe_old_map.iter();
     let Some((_maybe_chain_name, maybe_chain_id_object)) = maybe_old_iter.next() else {
         // Empty map
-        return Value::Object(maybe_old_map);
+        return Ok(Value::Object(maybe_old_map));
     };
     let Value::Object(maybe_chain_map) = maybe_chain_id_object else {
-        panic!("Unexpected daemon state format");
+        return Err(DaemonError::InvalidStateFormat(
+            maybe_chain_id_object.to_string(),
+        ));
     };
     if maybe_chain_map.iter().any(|(key, _val)| key == "code_ids") {
         // It's new format we good
-        return Value::Object(maybe_old_map);
+        return Ok(Value::Object(maybe_old_map));
     }
     // Assuming it's an old daemon state from now on as we didn't found code id object under first key
     // We just need to join all chain maps
-    let new_state = maybe_old_map.into_iter().fold(
-        serde_json::Map::new(),
-        |mut acc, (_chain_name, chain_value)| {
-            let chain_map = expect_object(chain_value);
-            acc.extend(chain_map);
-            acc
-        },
-    );
-    Value::Object(new_state)
+    let mut new_state = serde_json::Map::new();
+    for (_chain_name, chain_value) in maybe_old_map {
+        new_state.extend(expect_object(chain_value)?);
+    }
+    Ok(Value::Object(new_state))
 }
 
 #[cfg(test)]
@@ -153,7 +207,7 @@ mod test_old_patch {
                 }
             }
         });
-        let patched = patch_state_if_old(old_map);
+        let patched = patch_state_if_old(old_map).unwrap();
         let expected = json!({
             "chain-id": {
                     "abracadabra": {
@@ -166,10 +220,19 @@ mod test_old_patch {
         });
         assert_eq!(patched, expected);
         // Already new map, nothing to patch
-        let not_patched = patch_state_if_old(patched);
+        let not_patched = patch_state_if_old(patched).unwrap();
         assert_eq!(not_patched, expected);
     }
 
+    #[test]
+    fn malformed_state() {
+        assert!(patch_state_if_old(json!([])).is_err());
+        assert!(patch_state_if_old(json!({ "chain-id": 1 })).is_err());
+        assert!(
+            patch_state_if_old(json!({ "chain-name": { "chain-id": {} }, "other": 1 })).is_err()
+        );
+    }
+
     #[test]
     fn big_test() {
         let old_starship_state = json!({
@@ -321,10 +384,10 @@ mod test_old_patch {
               }
             }
         });
-        let patched = patch_state_if_old(old_starship_state);
+        let patched = patch_state_if_old(old_starship_state).unwrap();
         assert_eq!(patched, expected);
         // Already new map, nothing to patch
-        let not_patched = patch_state_if_old(patched);
+        let not_patched = patch_state_if_old(patched).unwrap();
         assert_eq!(not_patched, expected);
     }
 }
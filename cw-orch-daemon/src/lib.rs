@@ -12,6 +12,8 @@ pub mod keys;
 pub mod live_mock;
 pub mod queriers;
 pub mod senders;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_state;
 pub mod tx_broadcaster;
 pub mod tx_builder;
 
@@ -38,10 +40,15 @@ pub(crate) mod cosmos_modules {
             authz::v1beta1 as authz,
             bank::v1beta1 as bank,
             base::{abci::v1beta1 as abci, tendermint::v1beta1 as tendermint},
+            distribution::v1beta1 as distribution,
             feegrant::v1beta1 as feegrant,
+            gov::v1 as gov_v1,
             gov::v1beta1 as gov,
+            mint::v1beta1 as mint,
+            slashing::v1beta1 as slashing,
             staking::v1beta1 as staking,
             tx::v1beta1 as tx,
+            upgrade::v1beta1 as upgrade,
             vesting::v1beta1 as vesting,
         },
         cosmwasm::wasm::v1 as cosmwasm,
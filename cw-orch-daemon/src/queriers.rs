@@ -46,20 +46,45 @@ macro_rules! cosmos_query {
 mod authz;
 mod bank;
 mod cosmwasm;
+mod distribution;
 mod env;
 mod feegrant;
 mod gov;
 mod ibc;
+mod mint;
 mod node;
+mod slashing;
 mod staking;
+mod upgrade;
 
 pub use authz::Authz;
 pub use bank::{cosmrs_to_cosmwasm_coins, Bank};
 pub use cosmwasm::{CosmWasm, CosmWasmBase};
+pub use distribution::{
+    cosmrs_to_cosmwasm_dec_coins_floor, cosmrs_to_cosmwasm_decimal, cosmrs_to_cosmwasm_decimal256,
+    Distribution, DistributionParams,
+};
 pub use feegrant::FeeGrant;
 pub use ibc::Ibc;
+pub use mint::{Mint, MintParams};
 pub use node::Node;
+pub use slashing::{Slashing, SlashingParams, ValidatorSigningInfo};
+pub use upgrade::{Upgrade, UpgradePlan};
+
+use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 
 // this two containt structs that are helpers for the queries
 pub use gov::*;
 pub use staking::*;
+
+/// Request for the page starting at `key`, the first page when `key` is empty.
+/// Used to iterate over all the pages of a query until the returned `next_key` is empty.
+pub(crate) fn page_request(key: Vec<u8>) -> PageRequest {
+    PageRequest {
+        key,
+        offset: 0,
+        limit: 0,
+        count_total: false,
+        reverse: false,
+    }
+}
@@ -2,7 +2,7 @@ use std::{marker::PhantomData, str::FromStr};
 
 use crate::senders::query::QuerySender;
 use crate::senders::QueryOnlySender;
-use crate::{cosmos_modules, error::DaemonError, DaemonBase};
+use crate::{cosmos_modules, error::DaemonError, queriers::page_request, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmrs::AccountId;
 use cosmwasm_std::{
@@ -152,6 +152,33 @@ impl<Sender: QuerySender> CosmWasmBase<Sender> {
         Ok(client.all_contract_state(request).await?.into_inner())
     }
 
+    /// Query all the storage entries of a contract, going through all the pages of [`Self::_all_contract_state`]
+    pub async fn _all_contract_storage(
+        &self,
+        address: &Addr,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, DaemonError> {
+        let mut entries = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response = self
+                ._all_contract_state(address, Some(page_request(next_key)))
+                .await?;
+            entries.extend(
+                response
+                    .models
+                    .into_iter()
+                    .map(|model| (model.key, model.value)),
+            );
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        Ok(entries)
+    }
+
     /// Query code
     pub async fn _code(&self, code_id: u64) -> Result<CodeInfoResponse, DaemonError> {
         use cosmos_modules::cosmwasm::{query_client::*, QueryCodeRequest};
@@ -309,6 +336,10 @@ impl<Sender: QuerySender> WasmQuerier for CosmWasmBase<Sender> {
     ) -> Result<Checksum, cw_orch_core::CwEnvError> {
         <T as Uploadable>::wasm(contract.environment().daemon.chain_info()).checksum()
     }
+
+    fn is_not_found(&self, error: &DaemonError) -> bool {
+        matches!(error, DaemonError::Status(status) if status.code() == tonic::Code::NotFound)
+    }
 }
 
 pub fn cosmrs_to_cosmwasm_code_info(
@@ -0,0 +1,266 @@
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
+use cosmrs::proto::cosmos::base::v1beta1::DecCoin;
+use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, StdError, Uint128, Uint256};
+use cw_orch_core::environment::{DistributionQuerier, Querier, QuerierGetter};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+/// Querier for the Cosmos Distribution module
+/// All the async function are prefixed with `_`
+pub struct Distribution {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+}
+
+impl Distribution {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+        }
+    }
+}
+
+impl Querier for Distribution {
+    type Error = DaemonError;
+}
+
+impl<Sender: QuerySender> QuerierGetter<Distribution> for DaemonBase<Sender> {
+    fn querier(&self) -> Distribution {
+        Distribution::new(self)
+    }
+}
+
+/// Parameters of the distribution module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistributionParams {
+    /// Share of the rewards sent to the community pool
+    pub community_tax: Decimal,
+    /// Deprecated by the Cosmos SDK, zero on recent chains
+    pub base_proposer_reward: Decimal,
+    /// Deprecated by the Cosmos SDK, zero on recent chains
+    pub bonus_proposer_reward: Decimal,
+    /// Whether delegators can set a different address to withdraw their rewards to
+    pub withdraw_addr_enabled: bool,
+}
+
+impl Distribution {
+    /// Query the rewards accrued by a delegation, truncated to whole tokens
+    pub async fn _delegation_rewards(
+        &self,
+        delegator_addr: &Addr,
+        validator_addr: &Addr,
+    ) -> Result<Vec<Coin>, DaemonError> {
+        let rewards: cosmos_modules::distribution::QueryDelegationRewardsResponse = cosmos_query!(
+            self,
+            distribution,
+            delegation_rewards,
+            QueryDelegationRewardsRequest {
+                delegator_address: delegator_addr.into(),
+                validator_address: validator_addr.into(),
+            }
+        );
+        Ok(cosmrs_to_cosmwasm_dec_coins_floor(rewards.rewards)?)
+    }
+
+    /// Query the rewards accrued by all the delegations of a delegator, truncated to whole tokens
+    pub async fn _delegation_total_rewards(
+        &self,
+        delegator_addr: &Addr,
+    ) -> Result<Vec<Coin>, DaemonError> {
+        let rewards: cosmos_modules::distribution::QueryDelegationTotalRewardsResponse = cosmos_query!(
+            self,
+            distribution,
+            delegation_total_rewards,
+            QueryDelegationTotalRewardsRequest {
+                delegator_address: delegator_addr.into(),
+            }
+        );
+        Ok(cosmrs_to_cosmwasm_dec_coins_floor(rewards.total)?)
+    }
+
+    /// Query the commission accumulated by a validator, truncated to whole tokens
+    pub async fn _validator_commission(
+        &self,
+        validator_addr: &Addr,
+    ) -> Result<Vec<Coin>, DaemonError> {
+        let commission: cosmos_modules::distribution::QueryValidatorCommissionResponse = cosmos_query!(
+            self,
+            distribution,
+            validator_commission,
+            QueryValidatorCommissionRequest {
+                validator_address: validator_addr.into(),
+            }
+        );
+        Ok(cosmrs_to_cosmwasm_dec_coins_floor(
+            commission.commission.unwrap_or_default().commission,
+        )?)
+    }
+
+    /// Query the rewards of a validator that were not withdrawn yet (commission and delegation rewards), truncated to whole tokens
+    pub async fn _validator_outstanding_rewards(
+        &self,
+        validator_addr: &Addr,
+    ) -> Result<Vec<Coin>, DaemonError> {
+        let rewards: cosmos_modules::distribution::QueryValidatorOutstandingRewardsResponse = cosmos_query!(
+            self,
+            distribution,
+            validator_outstanding_rewards,
+            QueryValidatorOutstandingRewardsRequest {
+                validator_address: validator_addr.into(),
+            }
+        );
+        Ok(cosmrs_to_cosmwasm_dec_coins_floor(
+            rewards.rewards.unwrap_or_default().rewards,
+        )?)
+    }
+
+    /// Query the funds of the community pool, truncated to whole tokens
+    pub async fn _community_pool(&self) -> Result<Vec<Coin>, DaemonError> {
+        let pool: cosmos_modules::distribution::QueryCommunityPoolResponse = cosmos_query!(
+            self,
+            distribution,
+            community_pool,
+            QueryCommunityPoolRequest {}
+        );
+        Ok(cosmrs_to_cosmwasm_dec_coins_floor(pool.pool)?)
+    }
+
+    /// Query the address the rewards of a delegator are withdrawn to
+    pub async fn _delegator_withdraw_address(
+        &self,
+        delegator_addr: &Addr,
+    ) -> Result<Addr, DaemonError> {
+        let withdraw_address: cosmos_modules::distribution::QueryDelegatorWithdrawAddressResponse = cosmos_query!(
+            self,
+            distribution,
+            delegator_withdraw_address,
+            QueryDelegatorWithdrawAddressRequest {
+                delegator_address: delegator_addr.into(),
+            }
+        );
+        Ok(Addr::unchecked(withdraw_address.withdraw_address))
+    }
+
+    /// Query distribution parameters
+    pub async fn _params(&self) -> Result<DistributionParams, DaemonError> {
+        let params: cosmos_modules::distribution::QueryParamsResponse =
+            cosmos_query!(self, distribution, params, QueryParamsRequest {});
+        let params = params
+            .params
+            .ok_or_else(|| DaemonError::StdErr("distribution params not found".to_string()))?;
+        Ok(DistributionParams {
+            community_tax: cosmrs_to_cosmwasm_decimal(&params.community_tax)?,
+            base_proposer_reward: cosmrs_to_cosmwasm_decimal(&params.base_proposer_reward)?,
+            bonus_proposer_reward: cosmrs_to_cosmwasm_decimal(&params.bonus_proposer_reward)?,
+            withdraw_addr_enabled: params.withdraw_addr_enabled,
+        })
+    }
+}
+
+impl Distribution {
+    /// Query the commission accumulated by a validator, truncated to whole tokens
+    pub fn validator_commission(&self, validator_addr: &Addr) -> Result<Vec<Coin>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._validator_commission(validator_addr))
+    }
+
+    /// Query the rewards of a validator that were not withdrawn yet, truncated to whole tokens
+    pub fn validator_outstanding_rewards(
+        &self,
+        validator_addr: &Addr,
+    ) -> Result<Vec<Coin>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._validator_outstanding_rewards(validator_addr))
+    }
+
+    /// Query the funds of the community pool, truncated to whole tokens
+    pub fn community_pool(&self) -> Result<Vec<Coin>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._community_pool())
+    }
+
+    /// Query the address the rewards of a delegator are withdrawn to
+    pub fn delegator_withdraw_address(&self, delegator_addr: &Addr) -> Result<Addr, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._delegator_withdraw_address(delegator_addr))
+    }
+
+    /// Query distribution parameters
+    pub fn params(&self) -> Result<DistributionParams, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._params())
+    }
+}
+
+impl DistributionQuerier for Distribution {
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._delegation_rewards(delegator, &Addr::unchecked(validator)))
+    }
+
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._delegation_total_rewards(delegator))
+    }
+}
+
+/// Converts decimal coins to coins, truncating the fractional amounts.
+/// Coins with an amount lower than one are dropped.
+pub fn cosmrs_to_cosmwasm_dec_coins_floor(coins: Vec<DecCoin>) -> Result<Vec<Coin>, StdError> {
+    // Decimal amounts are encoded with 18 fractional digits
+    let precision = Uint256::from(10u128.pow(18));
+    let mut truncated = vec![];
+    for coin in coins {
+        let amount = Uint128::try_from(coin.amount.parse::<Uint256>()? / precision)?;
+        if !amount.is_zero() {
+            truncated.push(Coin {
+                denom: coin.denom,
+                amount,
+            });
+        }
+    }
+    Ok(truncated)
+}
+
+/// Converts a decimal encoded with 18 fractional digits, as returned by the gRPC queries, to a [`Decimal`]
+pub fn cosmrs_to_cosmwasm_decimal(dec: &str) -> Result<Decimal, DaemonError> {
+    Decimal::from_atomics(dec.parse::<Uint128>()?, 18)
+        .map_err(|e| DaemonError::StdErr(e.to_string()))
+}
+
+/// Converts a decimal encoded with 18 fractional digits, as returned by the gRPC queries, to a [`Decimal256`]
+pub fn cosmrs_to_cosmwasm_decimal256(dec: &str) -> Result<Decimal256, DaemonError> {
+    Decimal256::from_atomics(dec.parse::<Uint256>()?, 18)
+        .map_err(|e| DaemonError::StdErr(e.to_string()))
+}
+
+/// Some modules encode their decimals as the bytes of their string representation
+pub(crate) fn dec_bytes_to_str(bytes: &[u8]) -> Result<&str, DaemonError> {
+    std::str::from_utf8(bytes).map_err(|e| DaemonError::StdErr(e.to_string()))
+}
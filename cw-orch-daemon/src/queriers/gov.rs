@@ -1,8 +1,13 @@
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
-use cosmwasm_std::Addr;
-use cw_orch_core::environment::{Querier, QuerierGetter};
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128, VoteOption};
+use cw_orch_core::environment::{
+    GovDeposit, GovProposal, GovQuerier, GovVote, GovVoteOption, ProposalMessage, ProposalStatus,
+    Querier, QuerierGetter, TallyResult,
+};
 use tokio::runtime::Handle;
+
+use super::{bank::cosmrs_to_cosmwasm_coins, page_request};
 use tonic::transport::Channel;
 
 /// Querier for the Cosmos Gov module
@@ -13,7 +18,7 @@ pub struct Gov {
 }
 
 impl Gov {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -32,7 +37,7 @@ impl Querier for Gov {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Gov> for Daemon {
+impl<Sender: QuerySender> QuerierGetter<Gov> for DaemonBase<Sender> {
     fn querier(&self) -> Gov {
         Gov::new(self)
     }
@@ -173,7 +178,7 @@ impl Gov {
 
     /// TallyResult queries the tally of a proposal vote.
     pub async fn _tally_result(
-        &mut self,
+        &self,
         proposal_id: u64,
     ) -> Result<cosmos_modules::gov::TallyResult, DaemonError> {
         let tally_result: cosmos_modules::gov::QueryTallyResultResponse = cosmos_query!(
@@ -188,6 +193,255 @@ impl Gov {
     }
 }
 
+impl GovQuerier for Gov {
+    fn proposal(&self, proposal_id: u64) -> Result<GovProposal, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                // The v1 query returns the messages and the proposer of the proposal
+                let proposal: cosmos_modules::gov_v1::QueryProposalResponse = cosmos_query!(
+                    self,
+                    gov_v1,
+                    proposal,
+                    QueryProposalRequest {
+                        proposal_id: proposal_id,
+                    }
+                );
+                let proposal = proposal.proposal.ok_or_else(|| {
+                    DaemonError::StdErr(format!("proposal {proposal_id} not found"))
+                })?;
+                cosmrs_to_gov_proposal(proposal)
+            })
+    }
+
+    fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<GovProposal>, Self::Error> {
+        let status = match status {
+            None => GovProposalStatus::Unspecified,
+            Some(ProposalStatus::DepositPeriod) => GovProposalStatus::DepositPeriod,
+            Some(ProposalStatus::VotingPeriod) => GovProposalStatus::VotingPeriod,
+            Some(ProposalStatus::Passed) => GovProposalStatus::Passed,
+            Some(ProposalStatus::Rejected) => GovProposalStatus::Rejected,
+            Some(ProposalStatus::Failed) => GovProposalStatus::Failed,
+        } as i32;
+        let proposals = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                let mut proposals = vec![];
+                let mut next_key = vec![];
+                loop {
+                    let response: cosmos_modules::gov_v1::QueryProposalsResponse = cosmos_query!(
+                        self,
+                        gov_v1,
+                        proposals,
+                        QueryProposalsRequest {
+                            proposal_status: status,
+                            voter: String::new(),
+                            depositor: String::new(),
+                            pagination: Some(page_request(next_key))
+                        }
+                    );
+                    proposals.extend(response.proposals);
+                    match response.pagination {
+                        Some(pagination) if !pagination.next_key.is_empty() => {
+                            next_key = pagination.next_key
+                        }
+                        _ => break,
+                    }
+                }
+                Ok::<_, DaemonError>(proposals)
+            })?;
+        proposals.into_iter().map(cosmrs_to_gov_proposal).collect()
+    }
+
+    fn vote(&self, proposal_id: u64, voter: &Addr) -> Result<GovVote, Self::Error> {
+        let vote = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._vote(proposal_id, voter))?;
+        cosmrs_to_gov_vote(vote)
+    }
+
+    fn votes(&self, proposal_id: u64) -> Result<Vec<GovVote>, Self::Error> {
+        let votes = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                let mut votes = vec![];
+                let mut next_key = vec![];
+                loop {
+                    let response = self
+                        ._votes(proposal_id, Some(page_request(next_key)))
+                        .await?;
+                    votes.extend(response.votes);
+                    match response.pagination {
+                        Some(pagination) if !pagination.next_key.is_empty() => {
+                            next_key = pagination.next_key
+                        }
+                        _ => break,
+                    }
+                }
+                Ok::<_, DaemonError>(votes)
+            })?;
+        votes.into_iter().map(cosmrs_to_gov_vote).collect()
+    }
+
+    fn deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error> {
+        let deposits = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                let mut deposits = vec![];
+                let mut next_key = vec![];
+                loop {
+                    let response = self
+                        ._deposits(proposal_id, Some(page_request(next_key)))
+                        .await?;
+                    deposits.extend(response.deposits);
+                    match response.pagination {
+                        Some(pagination) if !pagination.next_key.is_empty() => {
+                            next_key = pagination.next_key
+                        }
+                        _ => break,
+                    }
+                }
+                Ok::<_, DaemonError>(deposits)
+            })?;
+        deposits
+            .into_iter()
+            .map(|deposit| {
+                Ok(GovDeposit {
+                    proposal_id: deposit.proposal_id,
+                    depositor: deposit.depositor,
+                    amount: cosmrs_to_cosmwasm_coins(deposit.amount)?,
+                })
+            })
+            .collect()
+    }
+
+    fn tally(&self, proposal_id: u64) -> Result<TallyResult, Self::Error> {
+        let tally = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._tally_result(proposal_id))?;
+        cosmrs_to_tally_result(tally)
+    }
+}
+
+/// Converts a gov module proposal to a [`GovProposal`]
+pub fn cosmrs_to_gov_proposal(
+    proposal: cosmos_modules::gov_v1::Proposal,
+) -> Result<GovProposal, DaemonError> {
+    let status = match proposal.status {
+        1 => ProposalStatus::DepositPeriod,
+        2 => ProposalStatus::VotingPeriod,
+        3 => ProposalStatus::Passed,
+        4 => ProposalStatus::Rejected,
+        5 => ProposalStatus::Failed,
+        status => {
+            return Err(DaemonError::StdErr(format!(
+                "unknown proposal status {status}"
+            )))
+        }
+    };
+    let final_tally_result = match status {
+        ProposalStatus::DepositPeriod | ProposalStatus::VotingPeriod => None,
+        _ => proposal
+            .final_tally_result
+            .map(|tally| {
+                Ok::<_, DaemonError>(TallyResult {
+                    yes: tally.yes_count.parse()?,
+                    no: tally.no_count.parse()?,
+                    abstain: tally.abstain_count.parse()?,
+                    no_with_veto: tally.no_with_veto_count.parse()?,
+                })
+            })
+            .transpose()?,
+    };
+
+    Ok(GovProposal {
+        id: proposal.id,
+        title: proposal.title,
+        summary: proposal.summary,
+        proposer: proposal.proposer,
+        messages: proposal
+            .messages
+            .into_iter()
+            .map(|msg| ProposalMessage {
+                type_url: msg.type_url,
+                value: msg.value.into(),
+            })
+            .collect(),
+        status,
+        total_deposit: cosmrs_to_cosmwasm_coins(proposal.total_deposit)?,
+        submit_time: proposal
+            .submit_time
+            .map(|t| to_timestamp(t.seconds, t.nanos))
+            .unwrap_or_default(),
+        deposit_end_time: proposal
+            .deposit_end_time
+            .map(|t| to_timestamp(t.seconds, t.nanos))
+            .unwrap_or_default(),
+        voting_start_time: proposal
+            .voting_start_time
+            .map(|t| to_timestamp(t.seconds, t.nanos)),
+        voting_end_time: proposal
+            .voting_end_time
+            .map(|t| to_timestamp(t.seconds, t.nanos)),
+        final_tally_result,
+    })
+}
+
+/// Converts a gov module vote to a [`GovVote`]
+pub fn cosmrs_to_gov_vote(vote: cosmos_modules::gov::Vote) -> Result<GovVote, DaemonError> {
+    let options = vote
+        .options
+        .into_iter()
+        .map(|option| {
+            let vote_option = match option.option {
+                1 => VoteOption::Yes,
+                2 => VoteOption::Abstain,
+                3 => VoteOption::No,
+                4 => VoteOption::NoWithVeto,
+                option => return Err(DaemonError::StdErr(format!("unknown vote option {option}"))),
+            };
+            Ok(GovVoteOption {
+                option: vote_option,
+                // Weights are encoded as decimals with 18 fractional digits
+                weight: Decimal::from_atomics(option.weight.parse::<Uint128>()?, 18)
+                    .map_err(|e| DaemonError::StdErr(e.to_string()))?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(GovVote {
+        proposal_id: vote.proposal_id,
+        voter: vote.voter,
+        options,
+    })
+}
+
+fn cosmrs_to_tally_result(
+    tally: cosmos_modules::gov::TallyResult,
+) -> Result<TallyResult, DaemonError> {
+    Ok(TallyResult {
+        yes: tally.yes.parse()?,
+        no: tally.no.parse()?,
+        abstain: tally.abstain.parse()?,
+        no_with_veto: tally.no_with_veto.parse()?,
+    })
+}
+
+pub(crate) fn to_timestamp(seconds: i64, nanos: i32) -> Timestamp {
+    Timestamp::from_seconds(seconds as u64).plus_nanos(nanos as u64)
+}
+
 /// Proposal status
 #[allow(missing_docs)]
 pub enum GovProposalStatus {
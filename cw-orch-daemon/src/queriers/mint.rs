@@ -0,0 +1,116 @@
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
+use cosmwasm_std::{Decimal, Decimal256};
+use cw_orch_core::environment::{Querier, QuerierGetter};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+use super::distribution::{
+    cosmrs_to_cosmwasm_decimal, cosmrs_to_cosmwasm_decimal256, dec_bytes_to_str,
+};
+
+/// Querier for the Cosmos Mint module
+/// All the async function are prefixed with `_`
+pub struct Mint {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+}
+
+impl Mint {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+        }
+    }
+}
+
+impl Querier for Mint {
+    type Error = DaemonError;
+}
+
+impl<Sender: QuerySender> QuerierGetter<Mint> for DaemonBase<Sender> {
+    fn querier(&self) -> Mint {
+        Mint::new(self)
+    }
+}
+
+/// Parameters of the mint module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MintParams {
+    pub mint_denom: String,
+    /// Maximum annual change in inflation rate
+    pub inflation_rate_change: Decimal,
+    pub inflation_max: Decimal,
+    pub inflation_min: Decimal,
+    /// Targeted ratio of bonded tokens
+    pub goal_bonded: Decimal,
+    pub blocks_per_year: u64,
+}
+
+impl Mint {
+    /// Query the current minting inflation rate
+    pub async fn _inflation(&self) -> Result<Decimal, DaemonError> {
+        let inflation: cosmos_modules::mint::QueryInflationResponse =
+            cosmos_query!(self, mint, inflation, QueryInflationRequest {});
+        cosmrs_to_cosmwasm_decimal(dec_bytes_to_str(&inflation.inflation)?)
+    }
+
+    /// Query the current amount of tokens minted per year
+    pub async fn _annual_provisions(&self) -> Result<Decimal256, DaemonError> {
+        let provisions: cosmos_modules::mint::QueryAnnualProvisionsResponse = cosmos_query!(
+            self,
+            mint,
+            annual_provisions,
+            QueryAnnualProvisionsRequest {}
+        );
+        cosmrs_to_cosmwasm_decimal256(dec_bytes_to_str(&provisions.annual_provisions)?)
+    }
+
+    /// Query mint parameters
+    pub async fn _params(&self) -> Result<MintParams, DaemonError> {
+        let params: cosmos_modules::mint::QueryParamsResponse =
+            cosmos_query!(self, mint, params, QueryParamsRequest {});
+        let params = params.params.unwrap_or_default();
+        Ok(MintParams {
+            mint_denom: params.mint_denom,
+            inflation_rate_change: cosmrs_to_cosmwasm_decimal(&params.inflation_rate_change)?,
+            inflation_max: cosmrs_to_cosmwasm_decimal(&params.inflation_max)?,
+            inflation_min: cosmrs_to_cosmwasm_decimal(&params.inflation_min)?,
+            goal_bonded: cosmrs_to_cosmwasm_decimal(&params.goal_bonded)?,
+            blocks_per_year: params.blocks_per_year,
+        })
+    }
+}
+
+impl Mint {
+    /// Query the current minting inflation rate
+    pub fn inflation(&self) -> Result<Decimal, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._inflation())
+    }
+
+    /// Query the current amount of tokens minted per year
+    pub fn annual_provisions(&self) -> Result<Decimal256, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._annual_provisions())
+    }
+
+    /// Query mint parameters
+    pub fn params(&self) -> Result<MintParams, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._params())
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
+use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
+use cosmwasm_std::{Decimal, Timestamp};
+use cw_orch_core::environment::{Querier, QuerierGetter};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+use super::{
+    distribution::{cosmrs_to_cosmwasm_decimal, dec_bytes_to_str},
+    gov::to_timestamp,
+};
+
+/// Querier for the Cosmos Slashing module
+/// All the async function are prefixed with `_`
+pub struct Slashing {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+}
+
+impl Slashing {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+        }
+    }
+}
+
+impl Querier for Slashing {
+    type Error = DaemonError;
+}
+
+impl<Sender: QuerySender> QuerierGetter<Slashing> for DaemonBase<Sender> {
+    fn querier(&self) -> Slashing {
+        Slashing::new(self)
+    }
+}
+
+/// Liveness information of a validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSigningInfo {
+    /// Consensus address of the validator
+    pub address: String,
+    /// Height at which the validator started signing blocks
+    pub start_height: u64,
+    /// Index of the current block in the signed blocks window
+    pub index_offset: u64,
+    /// Time until which the validator is jailed
+    pub jailed_until: Timestamp,
+    /// Whether the validator was permanently removed for double signing
+    pub tombstoned: bool,
+    /// Number of blocks missed in the signed blocks window
+    pub missed_blocks_counter: u64,
+}
+
+/// Parameters of the slashing module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlashingParams {
+    pub signed_blocks_window: u64,
+    pub min_signed_per_window: Decimal,
+    /// Jail duration in seconds
+    pub downtime_jail_duration: u64,
+    pub slash_fraction_double_sign: Decimal,
+    pub slash_fraction_downtime: Decimal,
+}
+
+impl Slashing {
+    /// Query the signing info of a validator by consensus address
+    pub async fn _signing_info(
+        &self,
+        cons_address: impl Into<String>,
+    ) -> Result<ValidatorSigningInfo, DaemonError> {
+        let signing_info: cosmos_modules::slashing::QuerySigningInfoResponse = cosmos_query!(
+            self,
+            slashing,
+            signing_info,
+            QuerySigningInfoRequest {
+                cons_address: cons_address.into(),
+            }
+        );
+        Ok(cosmrs_to_cosmwasm_signing_info(
+            signing_info.val_signing_info.unwrap(),
+        ))
+    }
+
+    /// Query the signing info of all the validators
+    ///
+    /// see [PageRequest] for pagination
+    pub async fn _signing_infos(
+        &self,
+        pagination: Option<PageRequest>,
+    ) -> Result<Vec<ValidatorSigningInfo>, DaemonError> {
+        let signing_infos: cosmos_modules::slashing::QuerySigningInfosResponse = cosmos_query!(
+            self,
+            slashing,
+            signing_infos,
+            QuerySigningInfosRequest {
+                pagination: pagination
+            }
+        );
+        Ok(signing_infos
+            .info
+            .into_iter()
+            .map(cosmrs_to_cosmwasm_signing_info)
+            .collect())
+    }
+
+    /// Query slashing parameters
+    pub async fn _params(&self) -> Result<SlashingParams, DaemonError> {
+        let params: cosmos_modules::slashing::QueryParamsResponse =
+            cosmos_query!(self, slashing, params, QueryParamsRequest {});
+        let params = params.params.unwrap_or_default();
+        Ok(SlashingParams {
+            signed_blocks_window: params.signed_blocks_window as u64,
+            min_signed_per_window: cosmrs_to_cosmwasm_decimal(dec_bytes_to_str(
+                &params.min_signed_per_window,
+            )?)?,
+            downtime_jail_duration: params
+                .downtime_jail_duration
+                .map(|d| d.seconds as u64)
+                .unwrap_or_default(),
+            slash_fraction_double_sign: cosmrs_to_cosmwasm_decimal(dec_bytes_to_str(
+                &params.slash_fraction_double_sign,
+            )?)?,
+            slash_fraction_downtime: cosmrs_to_cosmwasm_decimal(dec_bytes_to_str(
+                &params.slash_fraction_downtime,
+            )?)?,
+        })
+    }
+}
+
+impl Slashing {
+    /// Query the signing info of a validator by consensus address
+    pub fn signing_info(
+        &self,
+        cons_address: impl Into<String>,
+    ) -> Result<ValidatorSigningInfo, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._signing_info(cons_address))
+    }
+
+    /// Query the signing info of all the validators
+    pub fn signing_infos(
+        &self,
+        pagination: Option<PageRequest>,
+    ) -> Result<Vec<ValidatorSigningInfo>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._signing_infos(pagination))
+    }
+
+    /// Query slashing parameters
+    pub fn params(&self) -> Result<SlashingParams, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._params())
+    }
+}
+
+pub fn cosmrs_to_cosmwasm_signing_info(
+    info: cosmos_modules::slashing::ValidatorSigningInfo,
+) -> ValidatorSigningInfo {
+    ValidatorSigningInfo {
+        address: info.address,
+        start_height: info.start_height as u64,
+        index_offset: info.index_offset as u64,
+        jailed_until: info
+            .jailed_until
+            .map(|t| to_timestamp(t.seconds, t.nanos))
+            .unwrap_or_default(),
+        tombstoned: info.tombstoned,
+        missed_blocks_counter: info.missed_blocks_counter as u64,
+    }
+}
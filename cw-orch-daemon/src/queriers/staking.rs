@@ -1,13 +1,13 @@
 use std::fmt::Display;
 
-use crate::{cosmos_modules, error::DaemonError, Daemon};
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
 use cosmrs::proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmwasm_std::{Addr, StdError};
-use cw_orch_core::environment::{Querier, QuerierGetter};
+use cw_orch_core::environment::{Querier, QuerierGetter, StakingQuerier};
 use tokio::runtime::Handle;
-use tonic::transport::Channel;
+use tonic::{transport::Channel, Code};
 
-use super::bank::cosmrs_to_cosmwasm_coin;
+use super::{bank::cosmrs_to_cosmwasm_coin, page_request};
 
 /// Querier for the Cosmos Staking module
 /// All the async function are prefixed with `_`
@@ -17,7 +17,7 @@ pub struct Staking {
 }
 
 impl Staking {
-    pub fn new(daemon: &Daemon) -> Self {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
         Self {
             channel: daemon.channel(),
             rt_handle: Some(daemon.rt_handle.clone()),
@@ -36,7 +36,7 @@ impl Querier for Staking {
     type Error = DaemonError;
 }
 
-impl QuerierGetter<Staking> for Daemon {
+impl<Sender: QuerySender> QuerierGetter<Staking> for DaemonBase<Sender> {
     fn querier(&self) -> Staking {
         Staking::new(self)
     }
@@ -299,6 +299,97 @@ impl Staking {
     }
 }
 
+impl StakingQuerier for Staking {
+    fn bonded_denom(&self) -> Result<String, Self::Error> {
+        let params = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._params())?;
+        params
+            .params
+            .map(|params| params.bond_denom)
+            .ok_or_else(|| DaemonError::StdErr("staking params not found".to_string()))
+    }
+
+    fn validator(&self, validator: &str) -> Result<cosmwasm_std::Validator, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._validator(&Addr::unchecked(validator)))
+    }
+
+    fn validators(&self) -> Result<Vec<cosmwasm_std::Validator>, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._validators(StakingBondStatus::Bonded))
+    }
+
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<cosmwasm_std::Delegation>, Self::Error> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                use cosmos_modules::staking::{query_client::QueryClient, QueryDelegationRequest};
+                let mut client = QueryClient::new(self.channel.clone());
+                let response = client
+                    .delegation(QueryDelegationRequest {
+                        delegator_addr: delegator.to_string(),
+                        validator_addr: validator.to_string(),
+                    })
+                    .await;
+                match response {
+                    Ok(response) => Ok::<_, DaemonError>(
+                        response
+                            .into_inner()
+                            .delegation_response
+                            .map(cosmrs_to_cosmwasm_delegation)
+                            .transpose()?,
+                    ),
+                    // The node answers with a `NotFound` status when there is no delegation
+                    Err(status) if status.code() == Code::NotFound => Ok(None),
+                    Err(status) => Err(status.into()),
+                }
+            })
+    }
+
+    fn delegator_delegations(
+        &self,
+        delegator: &Addr,
+    ) -> Result<Vec<cosmwasm_std::Delegation>, Self::Error> {
+        let delegations = self
+            .rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(async {
+                let mut delegations = vec![];
+                let mut next_key = vec![];
+                loop {
+                    let response = self
+                        ._delegator_delegations(delegator, Some(page_request(next_key)))
+                        .await?;
+                    delegations.extend(response.delegation_responses);
+                    match response.pagination {
+                        Some(pagination) if !pagination.next_key.is_empty() => {
+                            next_key = pagination.next_key
+                        }
+                        _ => break,
+                    }
+                }
+                Ok::<_, DaemonError>(delegations)
+            })?;
+        Ok(delegations
+            .into_iter()
+            .map(cosmrs_to_cosmwasm_delegation)
+            .collect::<Result<_, _>>()?)
+    }
+}
+
 /// Staking bond statuses
 pub enum StakingBondStatus {
     /// UNSPECIFIED defines an invalid validator status.
@@ -0,0 +1,122 @@
+use crate::{cosmos_modules, error::DaemonError, senders::query::QuerySender, DaemonBase};
+use cw_orch_core::environment::{Querier, QuerierGetter};
+use tokio::runtime::Handle;
+use tonic::transport::Channel;
+
+/// Querier for the Cosmos Upgrade module
+/// All the async function are prefixed with `_`
+pub struct Upgrade {
+    pub channel: Channel,
+    pub rt_handle: Option<Handle>,
+}
+
+impl Upgrade {
+    pub fn new<Sender: QuerySender>(daemon: &DaemonBase<Sender>) -> Self {
+        Self {
+            channel: daemon.channel(),
+            rt_handle: Some(daemon.rt_handle.clone()),
+        }
+    }
+
+    pub fn new_async(channel: Channel) -> Self {
+        Self {
+            channel,
+            rt_handle: None,
+        }
+    }
+}
+
+impl Querier for Upgrade {
+    type Error = DaemonError;
+}
+
+impl<Sender: QuerySender> QuerierGetter<Upgrade> for DaemonBase<Sender> {
+    fn querier(&self) -> Upgrade {
+        Upgrade::new(self)
+    }
+}
+
+/// Scheduled software upgrade of the chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpgradePlan {
+    pub name: String,
+    /// Height at which the upgrade is performed
+    pub height: u64,
+    /// Metadata about the upgrade, usually the binaries to use
+    pub info: String,
+}
+
+impl Upgrade {
+    /// Query the currently scheduled upgrade, if any
+    pub async fn _current_plan(&self) -> Result<Option<UpgradePlan>, DaemonError> {
+        let plan: cosmos_modules::upgrade::QueryCurrentPlanResponse =
+            cosmos_query!(self, upgrade, current_plan, QueryCurrentPlanRequest {});
+        Ok(plan.plan.map(|plan| UpgradePlan {
+            name: plan.name,
+            height: plan.height as u64,
+            info: plan.info,
+        }))
+    }
+
+    /// Query the height at which an upgrade was applied.
+    /// Returns `None` if the upgrade wasn't applied.
+    pub async fn _applied_plan(&self, name: impl Into<String>) -> Result<Option<u64>, DaemonError> {
+        let applied: cosmos_modules::upgrade::QueryAppliedPlanResponse = cosmos_query!(
+            self,
+            upgrade,
+            applied_plan,
+            QueryAppliedPlanRequest { name: name.into() }
+        );
+        Ok((applied.height != 0).then_some(applied.height as u64))
+    }
+
+    /// Query the consensus versions of the modules, optionally for a single module
+    pub async fn _module_versions(
+        &self,
+        module_name: impl Into<String>,
+    ) -> Result<Vec<(String, u64)>, DaemonError> {
+        let versions: cosmos_modules::upgrade::QueryModuleVersionsResponse = cosmos_query!(
+            self,
+            upgrade,
+            module_versions,
+            QueryModuleVersionsRequest {
+                module_name: module_name.into()
+            }
+        );
+        Ok(versions
+            .module_versions
+            .into_iter()
+            .map(|m| (m.name, m.version))
+            .collect())
+    }
+}
+
+impl Upgrade {
+    /// Query the currently scheduled upgrade, if any
+    pub fn current_plan(&self) -> Result<Option<UpgradePlan>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._current_plan())
+    }
+
+    /// Query the height at which an upgrade was applied.
+    /// Returns `None` if the upgrade wasn't applied.
+    pub fn applied_plan(&self, name: impl Into<String>) -> Result<Option<u64>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._applied_plan(name))
+    }
+
+    /// Query the consensus versions of the modules, optionally for a single module
+    pub fn module_versions(
+        &self,
+        module_name: impl Into<String>,
+    ) -> Result<Vec<(String, u64)>, DaemonError> {
+        self.rt_handle
+            .as_ref()
+            .ok_or(DaemonError::QuerierNeedRuntime)?
+            .block_on(self._module_versions(module_name))
+    }
+}
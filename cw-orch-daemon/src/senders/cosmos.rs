@@ -11,6 +11,7 @@ use crate::{
     keys::private::PrivateKey,
     proto::injective::{InjectiveEthAccount, ETHEREUM_COIN_TYPE},
     queriers::{Bank, Node},
+    tx_broadcaster::RetryStrategy,
     tx_builder::TxBuilder,
     tx_resp::CosmTxResponse,
     upload_wasm, CosmosOptions, GrpcChannel,
@@ -50,6 +51,8 @@ pub struct CosmosSender<C: Signing + Clone> {
     pub private_key: PrivateKey,
     /// gRPC channel
     pub grpc_channel: Channel,
+    /// Url of the gRPC endpoint the channel is connected to
+    pub(crate) grpc_url: Option<String>,
     /// Information about the chain
     pub chain_info: Arc<ChainInfoOwned>,
     pub(crate) options: CosmosOptions,
@@ -94,9 +97,13 @@ impl Wallet {
             &pk.public_key(&secp).raw_address.unwrap(),
         )?;
 
+        let (grpc_channel, grpc_url) =
+            GrpcChannel::endpoint_from_chain_info(chain_info.as_ref()).await?;
+
         Ok(Self {
             chain_info: chain_info.clone(),
-            grpc_channel: GrpcChannel::from_chain_info(chain_info.as_ref()).await?,
+            grpc_channel,
+            grpc_url: Some(grpc_url),
             private_key: pk,
             secp,
             options,
@@ -472,4 +479,25 @@ impl Signer for Wallet {
     fn authz_granter(&self) -> Option<&Addr> {
         self.options.authz_granter.as_ref()
     }
+
+    fn retry_strategies(&self) -> Vec<RetryStrategy> {
+        self.options.retry_strategies.clone()
+    }
+
+    fn fallback_grpc_urls(&self) -> Vec<String> {
+        self.chain_info
+            .grpc_urls
+            .iter()
+            .filter(|url| self.grpc_url.as_ref() != Some(*url))
+            .cloned()
+            .collect()
+    }
+
+    fn with_grpc_endpoint(&self, channel: Channel, grpc_url: &str) -> Option<Self> {
+        Some(Self {
+            grpc_channel: channel,
+            grpc_url: Some(grpc_url.to_string()),
+            ..self.clone()
+        })
+    }
 }
@@ -4,7 +4,7 @@ use cosmrs::AccountId;
 use cosmwasm_std::Addr;
 use cw_orch_core::environment::ChainInfoOwned;
 
-use crate::{DaemonError, Wallet};
+use crate::{tx_broadcaster::RetryStrategy, DaemonError, Wallet};
 
 use super::{builder::SenderBuilder, CosmosSender};
 
@@ -15,6 +15,8 @@ pub struct CosmosOptions {
     pub authz_granter: Option<Addr>,
     pub fee_granter: Option<Addr>,
     pub hd_index: Option<u32>,
+    /// Additional strategies used when retrying to broadcast transactions
+    pub retry_strategies: Vec<RetryStrategy>,
     /// Used to derive the private key
     pub(crate) key: CosmosWalletKey,
 }
@@ -60,6 +62,12 @@ impl CosmosOptions {
         self
     }
 
+    /// Registers a retry strategy, tested before the default ones when broadcasting transactions
+    pub fn retry_strategy(mut self, strategy: RetryStrategy) -> Self {
+        self.retry_strategies.push(strategy);
+        self
+    }
+
     pub fn set_authz_granter(&mut self, granter: &Addr) {
         self.authz_granter = Some(granter.clone());
     }
@@ -75,6 +83,10 @@ impl CosmosOptions {
     pub fn set_mnemonic(&mut self, mnemonic: impl Into<String>) {
         self.key = CosmosWalletKey::Mnemonic(mnemonic.into());
     }
+
+    pub fn add_retry_strategy(&mut self, strategy: RetryStrategy) {
+        self.retry_strategies.push(strategy);
+    }
 }
 
 impl SenderBuilder for CosmosOptions {
@@ -5,7 +5,7 @@ use crate::{
     queriers::Node,
     tx_broadcaster::{
         account_sequence_strategy, assert_broadcast_code_cosm_response, insufficient_fee_strategy,
-        TxBroadcaster,
+        transport_error_strategy, RetryStrategy, TxBroadcaster,
     },
     CosmTxResponse, DaemonError, QuerySender, TxBuilder, TxSender,
 };
@@ -18,6 +18,7 @@ use cosmrs::{
 };
 use cosmwasm_std::Addr;
 use prost::Message;
+use tonic::transport::Channel;
 
 pub struct SigningAccount {
     pub account_number: u64,
@@ -52,6 +53,29 @@ pub trait Signer: QuerySender<Error = DaemonError> + Sync {
 
     fn gas_price(&self) -> Result<f64, DaemonError>;
 
+    // --- Related to transaction broadcasting --- //
+    /// Additional retry strategies applied when broadcasting a transaction.
+    /// Those are tested before the default strategies.
+    fn retry_strategies(&self) -> Vec<RetryStrategy> {
+        vec![]
+    }
+
+    /// gRPC endpoints to fall back to when a transaction is retried because of a transport error.
+    /// The endpoint the signer is connected to should not be part of this list
+    fn fallback_grpc_urls(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Returns a copy of this signer that queries, simulates and broadcasts through the provided channel.
+    /// Used to rebuild and resubmit a transaction on a fallback endpoint.
+    /// Signers that return `None` keep using their own channel
+    fn with_grpc_endpoint(&self, _channel: Channel, _grpc_url: &str) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
     /// Computes the gas needed for submitting a transaction
     fn calculate_gas(
         &self,
@@ -113,11 +137,16 @@ impl<T: Signer + Sync> TxSender for T {
         // We retry broadcasting the tx, with the following strategies
         // 1. In case there is an `incorrect account sequence` error, we can retry as much as possible (doesn't cost anything to the user)
         // 2. In case there is an insufficient_fee error, we retry once (costs fee to the user everytime we submit this kind of tx)
-        // 3. In case there is an other error, we fail
+        // 3. In case the node can't be reached, we retry a few times, switching to the fallback endpoints
+        // 4. In case there is an other error, we fail
+        // Strategies registered on the signer are tested first
 
         let tx_response = TxBroadcaster::default()
+            .add_strategies(self.retry_strategies())
             .add_strategy(insufficient_fee_strategy())
             .add_strategy(account_sequence_strategy())
+            .add_strategy(transport_error_strategy())
+            .fallback_endpoints(self.fallback_grpc_urls())
             .broadcast(tx_builder, self)
             .await?;
 
@@ -62,8 +62,9 @@ pub trait TxSender: QuerySender + Sync {
                 })
                 .await?;
 
-            let commit = commit.into_inner().tx_response.unwrap();
-            Ok(commit)
+            commit.into_inner().tx_response.ok_or_else(|| {
+                DaemonError::StdErr("tx broadcast returned no tx response".to_string())
+            })
         }
     }
 
@@ -0,0 +1,232 @@
+//! SQLite storage for the [`DaemonState`](crate::DaemonState).
+//!
+//! ## Example
+//! ```no_run
+//! use std::sync::Arc;
+//! use cw_orch_core::environment::ChainInfoOwned;
+//! use cw_orch_daemon::{networks, sqlite_state::SqliteState, DaemonBuilder, DaemonState};
+//!
+//! let chain_info = Arc::new(ChainInfoOwned::from(networks::PION_1));
+//! let backend = SqliteState::new("state.sqlite").unwrap();
+//! let state = DaemonState::from_backend(backend, &chain_info, "default".to_string(), true).unwrap();
+//!
+//! let daemon = DaemonBuilder::new(networks::PION_1)
+//!     .state(state)
+//!     .build()
+//!     .unwrap();
+//! ```
+use std::{path::Path, time::Duration};
+
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+
+use crate::{json_lock::patch_state_if_old, DaemonError, DaemonStateBackend};
+
+/// Time a writer waits for a concurrent transaction to finish before erroring
+const BUSY_TIMEOUT: Duration = Duration::from_secs(30);
+
+const UPSERT_VALUE: &str =
+    "INSERT INTO daemon_state (chain_id, key, contract_id, value) VALUES (?1, ?2, ?3, ?4)
+    ON CONFLICT (chain_id, key, contract_id) DO UPDATE SET value = excluded.value";
+
+/// State backend storing the deployment information in an SQLite database.
+///
+/// Every change is committed in its own transaction, so multiple processes (e.g. concurrent CI jobs)
+/// can safely use the same database at the same time.
+#[derive(Debug)]
+pub struct SqliteState {
+    connection: Connection,
+}
+
+impl SqliteState {
+    /// Opens the database at the given path, creating it if it doesn't exist
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, DaemonError> {
+        let connection = Connection::open(path)?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS daemon_state (
+                chain_id TEXT NOT NULL,
+                key TEXT NOT NULL,
+                contract_id TEXT NOT NULL,
+                value TEXT NOT NULL,
+                PRIMARY KEY (chain_id, key, contract_id)
+            )",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Opens the database at the given path and imports the content of the JSON state file at `json_file_path`.
+    /// Values already present in the database are overwritten.
+    pub fn from_json_file(
+        path: impl AsRef<Path>,
+        json_file_path: &str,
+    ) -> Result<Self, DaemonError> {
+        let mut state = Self::new(path)?;
+        crate::migrate_json_state(json_file_path, &mut state)?;
+        Ok(state)
+    }
+}
+
+impl DaemonStateBackend for SqliteState {
+    fn prepare(&mut self, _chain_id: &str, _deployment_id: &str) -> Result<(), DaemonError> {
+        // Rows are created on write
+        Ok(())
+    }
+
+    fn chain_state(&self, chain_id: &str) -> Result<Value, DaemonError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT key, contract_id, value FROM daemon_state WHERE chain_id = ?1")?;
+        let rows = statement.query_map(params![chain_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut state = json!({});
+        for row in rows {
+            let (key, contract_id, value) = row?;
+            state[key][contract_id] = serde_json::from_str(&value)?;
+        }
+        Ok(state)
+    }
+
+    fn set_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+        value: Value,
+    ) -> Result<(), DaemonError> {
+        self.connection.execute(
+            UPSERT_VALUE,
+            params![chain_id, key, contract_id, value.to_string()],
+        )?;
+        Ok(())
+    }
+
+    fn remove_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+    ) -> Result<(), DaemonError> {
+        self.connection.execute(
+            "DELETE FROM daemon_state WHERE chain_id = ?1 AND key = ?2 AND contract_id = ?3",
+            params![chain_id, key, contract_id],
+        )?;
+        Ok(())
+    }
+
+    fn clear_chain(&mut self, chain_id: &str) -> Result<(), DaemonError> {
+        self.connection.execute(
+            "DELETE FROM daemon_state WHERE chain_id = ?1",
+            params![chain_id],
+        )?;
+        Ok(())
+    }
+
+    fn persist(&mut self) -> Result<(), DaemonError> {
+        // Changes are committed on write
+        Ok(())
+    }
+
+    fn full_state(&self) -> Result<Value, DaemonError> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT chain_id, key, contract_id, value FROM daemon_state")?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut state = json!({});
+        for row in rows {
+            let (chain_id, key, contract_id, value) = row?;
+            state[chain_id][key][contract_id] = serde_json::from_str(&value)?;
+        }
+        Ok(state)
+    }
+
+    /// Imports the whole state in a single transaction
+    fn import(&mut self, state: Value) -> Result<(), DaemonError> {
+        let state = patch_state_if_old(state)?;
+        let transaction = self.connection.transaction()?;
+        for (chain_id, chain_state) in state.as_object().cloned().unwrap_or_default() {
+            for (key, values) in chain_state.as_object().cloned().unwrap_or_default() {
+                for (contract_id, value) in values.as_object().cloned().unwrap_or_default() {
+                    if !value.is_null() {
+                        transaction.execute(
+                            UPSERT_VALUE,
+                            params![chain_id, key, contract_id, value.to_string()],
+                        )?;
+                    }
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::gen_temp_file_path;
+
+    #[test]
+    fn set_and_remove() -> anyhow::Result<()> {
+        let mut state = SqliteState::new(gen_temp_file_path())?;
+
+        state.set_value("juno-1", "code_ids", "counter", json!(1))?;
+        state.set_value("juno-1", "default", "counter", json!("juno1counter"))?;
+        state.set_value("juno-1", "code_ids", "counter", json!(2))?;
+        assert_eq!(
+            state.chain_state("juno-1")?,
+            json!({
+                "code_ids": { "counter": 2 },
+                "default": { "counter": "juno1counter" }
+            })
+        );
+
+        state.remove_value("juno-1", "default", "counter")?;
+        assert_eq!(
+            state.chain_state("juno-1")?,
+            json!({ "code_ids": { "counter": 2 } })
+        );
+
+        state.clear_chain("juno-1")?;
+        assert_eq!(state.chain_state("juno-1")?, json!({}));
+        Ok(())
+    }
+
+    #[test]
+    fn import_old_json_state() -> anyhow::Result<()> {
+        let mut state = SqliteState::new(gen_temp_file_path())?;
+        state.import(json!({
+            "juno": {
+                "juno-1": {
+                    "code_ids": { "counter": 1 },
+                    "default": { "counter": "juno1counter", "removed": null }
+                }
+            }
+        }))?;
+
+        assert_eq!(
+            state.full_state()?,
+            json!({
+                "juno-1": {
+                    "code_ids": { "counter": 1 },
+                    "default": { "counter": "juno1counter" }
+                }
+            })
+        );
+        Ok(())
+    }
+}
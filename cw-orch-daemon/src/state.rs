@@ -7,7 +7,7 @@ use crate::{
 
 use cosmwasm_std::Addr;
 use cw_orch_core::{
-    environment::{ChainInfoOwned, CwEnv, Environment, StateInterface},
+    environment::{ChainInfoOwned, CwEnv, DeploymentRecord, Environment, StateInterface},
     log::local_target,
     CwEnvError,
 };
@@ -16,17 +16,96 @@ use serde::Serialize;
 use serde_json::{json, Value};
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Debug,
     path::Path,
     sync::{Arc, Mutex},
 };
 
+/// Key under which the deployment history of contracts is stored in the state of a chain.
+/// It can't be used as a deployment id.
+pub const HISTORY_KEY: &str = "__history";
+
+/// Keys of the state of a chain that can't be used as deployment ids
+const RESERVED_KEYS: [&str; 2] = ["code_ids", HISTORY_KEY];
+
 /// Global state to track which files are already open by other daemons from other threads
 /// This is necessary because File lock will allow same process to lock file how many times as process wants
 pub(crate) static LOCKED_FILES: Lazy<Mutex<HashSet<String>>> =
     Lazy::new(|| Mutex::new(HashSet::new()));
 
+/// Storage of the deployment information (code ids and addresses) used by [`DaemonState`].
+///
+/// The state of a chain is represented as a JSON object with the following format:
+/// ```json
+/// {
+///     "code_ids": { "contract_id": 1 },
+///     "deployment_id": { "contract_id": "address" }
+/// }
+/// ```
+/// [`JsonLockedState`] is the default implementation, storing all chains in a single locked JSON file.
+pub trait DaemonStateBackend: Debug + Send {
+    /// Prepares the backend for writes on the given chain and deployment
+    fn prepare(&mut self, chain_id: &str, deployment_id: &str) -> Result<(), DaemonError>;
+
+    /// Returns the state of the given chain
+    fn chain_state(&self, chain_id: &str) -> Result<Value, DaemonError>;
+
+    /// Sets `value` under `key` (a deployment id or `code_ids`) for `contract_id` on the given chain
+    fn set_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+        value: Value,
+    ) -> Result<(), DaemonError>;
+
+    /// Removes the value stored under `key` for `contract_id` on the given chain
+    fn remove_value(
+        &mut self,
+        chain_id: &str,
+        key: &str,
+        contract_id: &str,
+    ) -> Result<(), DaemonError>;
+
+    /// Removes all the state of the given chain
+    fn clear_chain(&mut self, chain_id: &str) -> Result<(), DaemonError>;
+
+    /// Persists pending changes
+    fn persist(&mut self) -> Result<(), DaemonError>;
+
+    /// Returns the state of all chains, indexed by chain id
+    fn full_state(&self) -> Result<Value, DaemonError>;
+
+    /// Imports a full state (indexed by chain id), overwriting existing values.
+    /// States using the old format (indexed by chain name) are patched before being imported.
+    fn import(&mut self, state: Value) -> Result<(), DaemonError> {
+        let state = patch_state_if_old(state)?;
+        for (chain_id, chain_state) in state.as_object().cloned().unwrap_or_default() {
+            for (key, values) in chain_state.as_object().cloned().unwrap_or_default() {
+                for (contract_id, value) in values.as_object().cloned().unwrap_or_default() {
+                    if !value.is_null() {
+                        self.set_value(&chain_id, &key, &contract_id, value)?;
+                    }
+                }
+            }
+        }
+        self.persist()
+    }
+}
+
+/// Imports the content of a JSON state file into the provided backend.
+/// Can be used to migrate an existing state file to another [`DaemonStateBackend`]
+pub fn migrate_json_state(
+    json_file_path: &str,
+    backend: &mut dyn DaemonStateBackend,
+) -> Result<(), DaemonError> {
+    let json = crate::json_lock::read(&json_file_path.to_string())?;
+    backend.import(json)
+}
+
 /// Stores the chain information and deployment state.
-/// Uses a simple JSON file to store the deployment information locally.
+/// Uses a simple JSON file to store the deployment information locally by default.
+/// Other storages can be used with [`DaemonState::from_backend`].
 #[derive(Debug, Clone)]
 pub struct DaemonState {
     pub json_state: DaemonStateFile,
@@ -56,6 +135,10 @@ pub enum DaemonStateFile {
     FullAccess {
         json_file_state: Arc<Mutex<JsonLockedState>>,
     },
+    /// State stored in a custom [`DaemonStateBackend`]
+    Backend {
+        backend: Arc<Mutex<dyn DaemonStateBackend>>,
+    },
 }
 
 impl DaemonState {
@@ -67,6 +150,7 @@ impl DaemonState {
         read_only: bool,
         write_on_change: bool,
     ) -> Result<DaemonState, DaemonError> {
+        check_deployment_id(&deployment_id)?;
         let chain_id = &chain_data.chain_id;
 
         log::debug!(target: &local_target(), "Using state file : {}", json_file_path);
@@ -101,14 +185,14 @@ impl DaemonState {
             if lock.contains(&json_file_path) {
                 return Err(DaemonError::StateAlreadyLocked(json_file_path));
             }
-            let mut json_file_state = JsonLockedState::new(&json_file_path);
+            let mut json_file_state = JsonLockedState::new(&json_file_path)?;
             // Insert file to a locked files list and drop global mutex lock asap
             lock.insert(json_file_path);
             drop(lock);
 
             json_file_state.prepare(chain_id, &deployment_id);
             if write_on_change {
-                json_file_state.force_write();
+                json_file_state.force_write()?;
             }
             DaemonStateFile::FullAccess {
                 json_file_state: Arc::new(Mutex::new(json_file_state)),
@@ -123,6 +207,60 @@ impl DaemonState {
         })
     }
 
+    /// Creates a new state stored in the provided backend.
+    /// Pass it to [`crate::DaemonBuilder::state`] to use it with a daemon.
+    pub fn from_backend(
+        backend: impl DaemonStateBackend + 'static,
+        chain_data: &Arc<ChainInfoOwned>,
+        deployment_id: String,
+        write_on_change: bool,
+    ) -> Result<DaemonState, DaemonError> {
+        log::debug!(target: &local_target(), "Using state backend : {:?}", backend);
+
+        let mut state = DaemonState {
+            json_state: DaemonStateFile::Backend {
+                backend: Arc::new(Mutex::new(backend)),
+            },
+            deployment_id,
+            chain_data: chain_data.clone(),
+            write_on_change,
+        };
+        state.prepare()?;
+        Ok(state)
+    }
+
+    /// Prepares the state for writes on the current chain and deployment id
+    pub(crate) fn prepare(&mut self) -> Result<(), DaemonError> {
+        check_deployment_id(&self.deployment_id)?;
+        if let DaemonStateFile::ReadOnly { .. } = self.json_state {
+            return Ok(());
+        }
+        let chain_id = self.chain_data.chain_id.clone();
+        let deployment_id = self.deployment_id.clone();
+        let write_on_change = self.write_on_change;
+        self.with_backend(|backend| {
+            backend.prepare(&chain_id, &deployment_id)?;
+            if write_on_change {
+                backend.persist()?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Runs `f` on the writable backend of this state
+    fn with_backend<R>(
+        &self,
+        f: impl FnOnce(&mut dyn DaemonStateBackend) -> Result<R, DaemonError>,
+    ) -> Result<R, DaemonError> {
+        match &self.json_state {
+            DaemonStateFile::ReadOnly { path } => Err(DaemonError::StateReadOnly(path.clone())),
+            DaemonStateFile::FullAccess { json_file_state } => {
+                f(&mut *json_file_state.lock().unwrap())
+            }
+            DaemonStateFile::Backend { backend } => f(&mut *backend.lock().unwrap()),
+        }
+    }
+
     /// Returns the path of the file where the state of `cw-orchestrator` is stored.
     pub fn state_file_path() -> Result<String, DaemonError> {
         // check if STATE_FILE en var is configured, default to state.json
@@ -163,7 +301,7 @@ impl DaemonState {
         let json = match &self.json_state {
             DaemonStateFile::ReadOnly { path } => {
                 let j = crate::json_lock::read(path)?;
-                let j = patch_state_if_old(j);
+                let j = patch_state_if_old(j)?;
 
                 j[&self.chain_data.chain_id].clone()
             }
@@ -172,6 +310,10 @@ impl DaemonState {
                 .unwrap()
                 .get(&self.chain_data.chain_id)
                 .clone(),
+            DaemonStateFile::Backend { backend } => backend
+                .lock()
+                .unwrap()
+                .chain_state(&self.chain_data.chain_id)?,
         };
         Ok(json[key].clone())
     }
@@ -183,54 +325,35 @@ impl DaemonState {
         contract_id: &str,
         value: T,
     ) -> Result<(), DaemonError> {
-        let json_file_state = match &mut self.json_state {
-            DaemonStateFile::ReadOnly { path } => {
-                return Err(DaemonError::StateReadOnly(path.clone()))
-            }
-            DaemonStateFile::FullAccess { json_file_state } => json_file_state,
-        };
-
-        let mut json_file_lock = json_file_state.lock().unwrap();
-        let val = json_file_lock.get_mut(&self.chain_data.chain_id);
-        val[key][contract_id] = json!(value);
-
-        if self.write_on_change {
-            json_file_lock.force_write();
-        }
+        let chain_id = &self.chain_data.chain_id;
+        let write_on_change = self.write_on_change;
+        self.with_backend(|backend| {
+            backend.set_value(chain_id, key, contract_id, json!(value))?;
 
-        Ok(())
+            if write_on_change {
+                backend.persist()?;
+            }
+            Ok(())
+        })
     }
 
     /// Remove a stateful value using the chainId and networkId
     pub fn remove(&mut self, key: &str, contract_id: &str) -> Result<(), DaemonError> {
-        let json_file_state = match &mut self.json_state {
-            DaemonStateFile::ReadOnly { path } => {
-                return Err(DaemonError::StateReadOnly(path.clone()))
-            }
-            DaemonStateFile::FullAccess { json_file_state } => json_file_state,
-        };
+        let chain_id = &self.chain_data.chain_id;
+        let write_on_change = self.write_on_change;
+        self.with_backend(|backend| {
+            backend.remove_value(chain_id, key, contract_id)?;
 
-        let mut json_file_lock = json_file_state.lock().unwrap();
-        let val = json_file_lock.get_mut(&self.chain_data.chain_id);
-        val[key][contract_id] = Value::Null;
-
-        if self.write_on_change {
-            json_file_lock.force_write();
-        }
-
-        Ok(())
+            if write_on_change {
+                backend.persist()?;
+            }
+            Ok(())
+        })
     }
 
     /// Forcefully write current json to a file
     pub fn force_write(&mut self) -> Result<(), DaemonError> {
-        let json_file_state = match &mut self.json_state {
-            DaemonStateFile::ReadOnly { path } => {
-                return Err(DaemonError::StateReadOnly(path.clone()))
-            }
-            DaemonStateFile::FullAccess { json_file_state } => json_file_state,
-        };
-        json_file_state.lock().unwrap().force_write();
-        Ok(())
+        self.with_backend(|backend| backend.persist())
     }
 
     /// Flushes all the state related to the current chain
@@ -239,22 +362,16 @@ impl DaemonState {
         if self.chain_data.kind != ChainKind::Local {
             panic!("Can only flush local chain state");
         }
-        let json_file_state = match &mut self.json_state {
-            DaemonStateFile::ReadOnly { path } => {
-                return Err(DaemonError::StateReadOnly(path.clone()))
-            }
-            DaemonStateFile::FullAccess { json_file_state } => json_file_state,
-        };
+        let chain_id = &self.chain_data.chain_id;
+        let write_on_change = self.write_on_change;
+        self.with_backend(|backend| {
+            backend.clear_chain(chain_id)?;
 
-        let mut json_file_lock = json_file_state.lock().unwrap();
-        let json = json_file_lock.get_mut(&self.chain_data.chain_id);
-
-        *json = json!({});
-
-        if self.write_on_change {
-            json_file_lock.force_write();
-        }
-        Ok(())
+            if write_on_change {
+                backend.persist()?;
+            }
+            Ok(())
+        })
     }
 }
 
@@ -321,6 +438,51 @@ impl StateInterface for DaemonState {
         }
         Ok(store)
     }
+
+    /// Appends the record to the deployment history of the contract in the state file
+    fn record_deployment(
+        &mut self,
+        contract_id: &str,
+        record: DeploymentRecord,
+    ) -> Result<(), CwEnvError> {
+        let mut history = self.get_deployment_history(contract_id)?;
+        history.push(record);
+        self.set(HISTORY_KEY, contract_id, history)?;
+        Ok(())
+    }
+
+    fn get_deployment_history(
+        &self,
+        contract_id: &str,
+    ) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        let history = self.get(HISTORY_KEY)?;
+        match history.get(contract_id) {
+            Some(records) if !records.is_null() => Ok(serde_json::from_value(records.clone())?),
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn get_all_deployment_history(
+        &self,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        let history = self.get(HISTORY_KEY)?;
+        let value = history.as_object().cloned().unwrap_or_default();
+        let mut store = HashMap::new();
+        for (id, records) in value {
+            if !records.is_null() {
+                store.insert(id, serde_json::from_value(records)?);
+            }
+        }
+        Ok(store)
+    }
+}
+
+/// Errors if the deployment id collides with a key reserved by the state
+fn check_deployment_id(deployment_id: &str) -> Result<(), DaemonError> {
+    if RESERVED_KEYS.contains(&deployment_id) {
+        return Err(DaemonError::ReservedDeploymentId(deployment_id.to_owned()));
+    }
+    Ok(())
 }
 
 pub trait DeployedChains<Chain: CwEnv>: cw_orch_core::contract::Deploy<Chain> {
@@ -341,8 +503,9 @@ pub trait DeployedChains<Chain: CwEnv>: cw_orch_core::contract::Deploy<Chain> {
     fn get_all_deployed_chains() -> Vec<String> {
         let deployed_state_file = Self::deployed_state_file_path();
         if let Some(state_file) = deployed_state_file {
-            if let Ok(module_state_json) = crate::json_lock::read(&state_file) {
-                let module_state_json = patch_state_if_old(module_state_json);
+            if let Ok(module_state_json) =
+                crate::json_lock::read(&state_file).and_then(patch_state_if_old)
+            {
                 return module_state_json
                     .as_object()
                     .unwrap()
@@ -364,7 +527,7 @@ pub trait DeployedChains<Chain: CwEnv>: cw_orch_core::contract::Deploy<Chain> {
         }) else {
             return;
         };
-        let state = patch_state_if_old(maybe_old_state);
+        let state = patch_state_if_old(maybe_old_state).unwrap_or_else(|e| panic!("{e}"));
 
         let all_contracts = self.get_contracts_mut();
 
@@ -437,9 +600,18 @@ mod tempstate {
 
 #[cfg(test)]
 pub mod test {
-    use std::env;
+    use std::{env, sync::Arc};
 
-    use crate::{env::STATE_FILE_ENV_NAME, DaemonState};
+    use cosmwasm_std::{Addr, Timestamp};
+    use cw_orch_core::environment::{
+        DeploymentAction, DeploymentHistory, DeploymentRecord, StateInterface,
+    };
+
+    use crate::{
+        env::STATE_FILE_ENV_NAME, gen_temp_file_path, networks::JUNO_1, DaemonError, DaemonState,
+    };
+
+    use super::HISTORY_KEY;
 
     #[test]
     #[serial_test::serial]
@@ -492,4 +664,51 @@ pub mod test {
         std::env::remove_var(STATE_FILE_ENV_NAME);
         Ok(())
     }
+
+    #[test]
+    fn deployment_history() -> anyhow::Result<()> {
+        let mut state = DaemonState::new(
+            gen_temp_file_path().into_os_string().into_string().unwrap(),
+            &Arc::new(JUNO_1.into()),
+            "default".to_string(),
+            false,
+            true,
+        )?;
+        let record = |action, code_id, seconds| DeploymentRecord {
+            code_id: Some(code_id),
+            address: Some(Addr::unchecked("juno1counter")),
+            timestamp: Some(Timestamp::from_seconds(seconds)),
+            ..DeploymentRecord::new(action)
+        };
+
+        state.record_deployment("counter", record(DeploymentAction::Instantiate, 1, 10))?;
+        state.record_deployment("counter", record(DeploymentAction::Migrate, 2, 20))?;
+        state.record_deployment("counter", record(DeploymentAction::Migrate, 3, 30))?;
+
+        assert_eq!(state.get_deployment_history("counter")?.len(), 3);
+        assert_eq!(state.get_deployment_history("unknown")?, vec![]);
+        assert_eq!(
+            state.deployment_at("counter", Timestamp::from_seconds(25))?,
+            Some(record(DeploymentAction::Migrate, 2, 20))
+        );
+        assert_eq!(
+            state.deployments_between(Timestamp::from_seconds(15), Timestamp::from_seconds(25))?
+                ["counter"],
+            vec![record(DeploymentAction::Migrate, 2, 20)]
+        );
+        assert_eq!(state.rollback_code_id("counter")?, Some(2));
+        Ok(())
+    }
+
+    #[test]
+    fn reserved_deployment_id() {
+        let state = DaemonState::new(
+            gen_temp_file_path().into_os_string().into_string().unwrap(),
+            &Arc::new(JUNO_1.into()),
+            HISTORY_KEY.to_string(),
+            false,
+            true,
+        );
+        assert!(matches!(state, Err(DaemonError::ReservedDeploymentId(_))));
+    }
 }
@@ -103,6 +103,7 @@ impl DaemonBuilder {
 
     /// Reuse already existent [`DaemonState`]
     /// Useful for multi-chain scenarios
+    /// Also allows using another storage for the state, see [`DaemonState::from_backend`]
     pub fn state(&mut self, state: DaemonState) -> &mut Self {
         self.state = Some(state);
         self
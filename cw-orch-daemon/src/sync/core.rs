@@ -2,7 +2,7 @@ use std::{fmt::Debug, ops::DerefMut};
 
 use super::super::senders::Wallet;
 use crate::{
-    queriers::{Bank, CosmWasmBase, Node},
+    queriers::{Bank, CosmWasmBase, Distribution, Gov, Node, Staking},
     senders::{builder::SenderBuilder, query::QuerySender},
     CosmTxResponse, DaemonAsyncBase, DaemonBuilder, DaemonError, DaemonState,
 };
@@ -297,4 +297,7 @@ impl<Sender: QuerySender> DefaultQueriers for DaemonBase<Sender> {
     type Bank = Bank;
     type Wasm = CosmWasmBase<Sender>;
     type Node = Node;
+    type Staking = Staking;
+    type Distribution = Distribution;
+    type Gov = Gov;
 }
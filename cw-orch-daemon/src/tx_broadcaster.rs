@@ -1,54 +1,163 @@
-use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
-use cw_orch_core::log::transaction_target;
-
-use crate::senders::tx::TxSender;
-use crate::{queriers::Node, senders::sign::Signer, CosmTxResponse, DaemonError, TxBuilder};
-
-pub type StrategyAction =
-    fn(&mut TxBuilder, &Result<TxResponse, DaemonError>) -> Result<(), DaemonError>;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
+use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
+use cw_orch_core::log::{connectivity_target, transaction_target};
+use rand_core::{OsRng, RngCore};
+use tonic::transport::Channel;
+
+use crate::{
+    queriers::Node, senders::sign::Signer, CosmTxResponse, DaemonError, GrpcChannel, TxBuilder,
+    TxSender,
+};
+
+/// Condition tested on the response of a transaction that was submitted to a node.
+pub type BroadcastCondition = Arc<dyn Fn(&TxResponse) -> bool + Send + Sync>;
+/// Condition tested on the error returned while simulating/building/broadcasting a transaction.
+pub type SimulationCondition = Arc<dyn Fn(&DaemonError) -> bool + Send + Sync>;
+/// Action taken before re-trying the transaction submission.
+pub type StrategyAction = Arc<
+    dyn Fn(&mut TxBuilder, &Result<TxResponse, DaemonError>) -> Result<(), DaemonError>
+        + Send
+        + Sync,
+>;
+
+#[derive(Clone)]
 pub struct RetryStrategy {
     /// This function is called right after a transaction has been submitted to a node.
     /// It is used to check if the transaction has been submitted successfully or if an error occurred
     /// This function returns true if an error corresponding to the current strategy is detected
-    pub broadcast_condition: fn(&TxResponse) -> bool,
+    pub broadcast_condition: BroadcastCondition,
     /// This function is called when a simulation error occurs
     /// This function return true if an error corresponding to the current strategy is detected
-    pub simulation_condition: fn(&DaemonError) -> bool,
+    pub simulation_condition: SimulationCondition,
     /// Once the algorithm detects an error when broadcasting/simulating a transaction, it triggers this action before re-trying tx submission
     /// This action takes the transaction builder and the transaction response result as arguments
     /// It should make changes to the tx builder object (which is passed as a mutable reference)
     /// The tx_builder object will be used after that to re-try submitting the transaction
     pub action: Option<StrategyAction>,
     pub max_retries: BroadcastRetry,
+    /// Delay to wait for before re-trying tx submission
+    pub backoff: Backoff,
+    /// Maximum time spent re-trying with this strategy, measured from the first broadcast.
+    /// No retry is attempted once this duration has elapsed
+    pub max_duration: Option<Duration>,
     pub(crate) current_retries: u64,
     pub reason: String,
 }
 
 impl RetryStrategy {
     pub fn new(
-        broadcast_condition: fn(&TxResponse) -> bool,
-        simulation_condition: fn(&DaemonError) -> bool,
+        broadcast_condition: impl Fn(&TxResponse) -> bool + Send + Sync + 'static,
+        simulation_condition: impl Fn(&DaemonError) -> bool + Send + Sync + 'static,
         action: Option<StrategyAction>,
         max_retries: BroadcastRetry,
         reason: String,
     ) -> Self {
         RetryStrategy {
-            broadcast_condition,
-            simulation_condition,
+            broadcast_condition: Arc::new(broadcast_condition),
+            simulation_condition: Arc::new(simulation_condition),
             action,
             max_retries,
+            backoff: Backoff::default(),
+            max_duration: None,
             current_retries: 0,
             reason,
         }
     }
+
+    /// Sets the action triggered before re-trying tx submission
+    pub fn with_action(
+        mut self,
+        action: impl Fn(&mut TxBuilder, &Result<TxResponse, DaemonError>) -> Result<(), DaemonError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.action = Some(Arc::new(action));
+        self
+    }
+
+    /// Sets the delay to wait for before re-trying tx submission
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets the maximum time spent re-trying with this strategy
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+}
+
+/// Delay applied before re-trying tx submission
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Backoff {
+    /// Waits for the average block time of the chain
+    #[default]
+    AverageBlock,
+    /// Waits for a fixed duration
+    Fixed(Duration),
+    /// Waits for `initial * multiplier^retry`, capped at `max`.
+    /// If `jitter` is set, the delay is randomized between half and the full computed delay
+    Exponential {
+        initial: Duration,
+        multiplier: f64,
+        max: Duration,
+        jitter: bool,
+    },
+}
+
+impl Backoff {
+    /// Exponential backoff doubling at each retry, with jitter
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Backoff::Exponential {
+            initial,
+            multiplier: 2.0,
+            max,
+            jitter: true,
+        }
+    }
+
+    /// Computes the delay to wait for before the `retry`-th retry (starting at 1)
+    pub async fn delay(&self, retry: u64, channel: Channel) -> Result<Duration, DaemonError> {
+        let delay = match *self {
+            Backoff::AverageBlock => Node::new_async(channel)._average_block_speed(None).await?,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential {
+                initial,
+                multiplier,
+                max,
+                jitter,
+            } => {
+                let exponent = retry.saturating_sub(1).min(i32::MAX as u64) as i32;
+                let delay = Duration::from_secs_f64(
+                    (initial.as_secs_f64() * multiplier.powi(exponent)).min(max.as_secs_f64()),
+                );
+                if jitter {
+                    let half = delay / 2;
+                    let jitter_millis = OsRng.next_u64() % (half.as_millis() as u64 + 1);
+                    half + Duration::from_millis(jitter_millis)
+                } else {
+                    delay
+                }
+            }
+        };
+        Ok(delay)
+    }
 }
 
 #[derive(Default)]
 pub struct TxBroadcaster {
     strategies: Vec<RetryStrategy>,
+    /// gRPC endpoints to fall back to when a transport error occurs
+    fallback_endpoints: Vec<String>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BroadcastRetry {
     Infinite,
     Finite(u64),
@@ -63,12 +172,29 @@ impl TxBroadcaster {
         self
     }
 
+    /// Adds multiple retry strategies to the broadcaster, see [`Self::add_strategy`]
+    pub fn add_strategies(mut self, s: impl IntoIterator<Item = RetryStrategy>) -> Self {
+        self.strategies.extend(s);
+        self
+    }
+
+    /// Sets the gRPC endpoints the broadcaster switches to when a transaction is retried because of a transport error.
+    /// Endpoints are used in order, the tx is broadcasted through the signer channel until the first transport error.
+    pub fn fallback_endpoints(mut self, grpc_urls: Vec<String>) -> Self {
+        self.fallback_endpoints = grpc_urls;
+        self
+    }
+
     /// Broadcasts a transaction with the given signer
-    pub async fn broadcast(
+    pub async fn broadcast<S: Signer>(
         mut self,
         mut tx_builder: TxBuilder,
-        signer: &impl Signer,
+        signer: &S,
     ) -> Result<TxResponse, DaemonError> {
+        let start = Instant::now();
+        // Copy of the signer connected to a fallback endpoint, set after a transport error
+        let mut fallback_signer: Option<S> = None;
+        let mut next_endpoint = 0;
         let mut tx_retry = true;
 
         // We try and broadcast once
@@ -82,27 +208,40 @@ impl TxBroadcaster {
 
             // On error, we verify it matches the retry strategies. If it does, we update the retry counts and start over
             for s in self.strategies.iter_mut() {
-                if strategy_condition_met(s, &tx_response) && can_retry(s) {
+                if strategy_condition_met(s, &tx_response) && can_retry(s, start.elapsed()) {
                     // We update the tx and resubmit
-                    if let Some(action) = s.action {
+                    if let Some(action) = &s.action {
                         action(&mut tx_builder, &tx_response)?;
                     }
                     tx_retry = true;
 
-                    // We still await for the next block, to avoid spamming retry when an error occurs
-                    let block_speed = Node::new_async(signer.channel())
-                        ._average_block_speed(None)
+                    // On transport errors, we switch to the next available endpoint
+                    if matches!(&tx_response, Err(e) if is_transport_error(e)) {
+                        if let Some(switched_signer) =
+                            switch_endpoint(&self.fallback_endpoints, &mut next_endpoint, signer)
+                                .await
+                        {
+                            fallback_signer = Some(switched_signer);
+                        }
+                    }
+                    // The tx is rebuilt (account query and simulation) and broadcasted on the active endpoint
+                    let active_signer = fallback_signer.as_ref().unwrap_or(signer);
+
+                    // We still wait before retrying, to avoid spamming retry when an error occurs
+                    let delay = s
+                        .backoff
+                        .delay(s.current_retries, active_signer.channel())
                         .await?;
                     log::warn!(
                         target: &transaction_target(),
                         "Retrying broadcasting TX in {:?} milliseconds because of {}",
-                        block_speed.as_millis(),
+                        delay.as_millis(),
                         s.reason
                     );
-                    tokio::time::sleep(block_speed).await;
+                    tokio::time::sleep(delay).await;
 
-                    tx_response = broadcast_helper(&mut tx_builder, signer).await;
-                    continue;
+                    tx_response = broadcast_helper(&mut tx_builder, active_signer).await;
+                    break;
                 }
             }
         }
@@ -110,6 +249,44 @@ impl TxBroadcaster {
     }
 }
 
+/// Connects the signer to the next fallback endpoint that is reachable, if any
+async fn switch_endpoint<S: Signer>(
+    endpoints: &[String],
+    next_endpoint: &mut usize,
+    signer: &S,
+) -> Option<S> {
+    while let Some(endpoint) = endpoints.get(*next_endpoint) {
+        *next_endpoint += 1;
+        match GrpcChannel::connect(&[endpoint.clone()], &signer.chain_id()).await {
+            Ok(channel) => {
+                let Some(switched_signer) = signer.with_grpc_endpoint(channel, endpoint) else {
+                    log::warn!(
+                        target: &connectivity_target(),
+                        "The signer can't switch to gRPC endpoint {}",
+                        endpoint
+                    );
+                    return None;
+                };
+                log::warn!(
+                    target: &connectivity_target(),
+                    "Switching to gRPC endpoint {} after a transport error",
+                    endpoint
+                );
+                return Some(switched_signer);
+            }
+            Err(e) => {
+                log::warn!(
+                    target: &connectivity_target(),
+                    "Cannot switch to gRPC endpoint {}: {}",
+                    endpoint,
+                    e
+                );
+            }
+        }
+    }
+    None
+}
+
 fn strategy_condition_met(
     s: &RetryStrategy,
     tx_response: &Result<TxResponse, DaemonError>,
@@ -163,13 +340,16 @@ pub fn assert_broadcast_code_cosm_response(
     }
 }
 
-fn can_retry(s: &mut RetryStrategy) -> bool {
+fn can_retry(s: &mut RetryStrategy, elapsed: Duration) -> bool {
+    if s.max_duration
+        .is_some_and(|max_duration| elapsed >= max_duration)
+    {
+        return false;
+    }
+    s.current_retries += 1;
     match s.max_retries {
         BroadcastRetry::Infinite => true,
-        BroadcastRetry::Finite(max_retries) => {
-            s.current_retries += 1;
-            s.current_retries <= max_retries
-        }
+        BroadcastRetry::Finite(max_retries) => s.current_retries <= max_retries,
     }
 }
 
@@ -219,25 +399,26 @@ pub fn insufficient_fee_strategy() -> RetryStrategy {
     RetryStrategy::new(
         |tx_response| has_insufficient_fee(&tx_response.raw_log),
         |_| false, // Simulation doesn't have gas issues
-        Some(|tx_builder, tx_response| {
-            // get the suggested fee from the error message
-            // If we enter this function for this specific strategy, it's because something was detected in the tx response object
-            let suggested_fee = parse_suggested_fee(&tx_response.as_ref().unwrap().raw_log);
-
-            let Some(new_fee) = suggested_fee else {
-                return Err(DaemonError::InsufficientFee(
-                    tx_response.as_ref().unwrap().raw_log.clone(),
-                ));
-            };
-
-            // update the fee and try again
-            tx_builder.fee_amount(new_fee);
-
-            Ok(())
-        }),
+        None,
         BroadcastRetry::Finite(1),
         "an insufficient fee error".to_string(),
     )
+    .with_action(|tx_builder, tx_response| {
+        // get the suggested fee from the error message
+        // If we enter this function for this specific strategy, it's because something was detected in the tx response object
+        let suggested_fee = parse_suggested_fee(&tx_response.as_ref().unwrap().raw_log);
+
+        let Some(new_fee) = suggested_fee else {
+            return Err(DaemonError::InsufficientFee(
+                tx_response.as_ref().unwrap().raw_log.clone(),
+            ));
+        };
+
+        // update the fee and try again
+        tx_builder.fee_amount(new_fee);
+
+        Ok(())
+    })
 }
 
 fn has_account_sequence_error(raw_log: &str) -> bool {
@@ -254,6 +435,35 @@ pub fn account_sequence_strategy() -> RetryStrategy {
     )
 }
 
+/// Returns true if the error originates from the gRPC transport layer (unreachable or unresponsive node)
+pub fn is_transport_error(error: &DaemonError) -> bool {
+    match error {
+        DaemonError::TransportError(_) | DaemonError::CannotConnectGRPC => true,
+        DaemonError::Status(status) => matches!(
+            status.code(),
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+        ),
+        _ => false,
+    }
+}
+
+/// Retries transactions that failed because of a transport error.
+/// When used with fallback endpoints, the broadcaster switches endpoint before each retry.
+/// Registered by default on all senders, after the insufficient fee and account sequence strategies.
+pub fn transport_error_strategy() -> RetryStrategy {
+    RetryStrategy::new(
+        |_| false,
+        is_transport_error,
+        None,
+        BroadcastRetry::Finite(3),
+        "a transport error".to_string(),
+    )
+    .with_backoff(Backoff::exponential(
+        Duration::from_millis(500),
+        Duration::from_secs(10),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -264,4 +474,75 @@ mod tests {
         let fee = parse_suggested_fee(log).unwrap();
         assert_eq!(fee, 444255);
     }
+
+    #[test]
+    fn test_retry_limits() {
+        let mut strategy = account_sequence_strategy();
+        assert!(can_retry(&mut strategy, Duration::ZERO));
+        assert_eq!(strategy.current_retries, 1);
+
+        let mut strategy = insufficient_fee_strategy();
+        assert!(can_retry(&mut strategy, Duration::ZERO));
+        assert!(!can_retry(&mut strategy, Duration::ZERO));
+
+        let mut strategy = account_sequence_strategy().with_max_duration(Duration::from_secs(10));
+        assert!(can_retry(&mut strategy, Duration::from_secs(9)));
+        assert!(!can_retry(&mut strategy, Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_closure_strategy() {
+        let mempool_log = "tx already exists in cache";
+        let strategy = RetryStrategy::new(
+            move |tx_response| tx_response.raw_log.contains(mempool_log),
+            |_| false,
+            None,
+            BroadcastRetry::Finite(2),
+            "a tx already in mempool".to_string(),
+        );
+        let tx_response = TxResponse {
+            raw_log: mempool_log.to_string(),
+            ..Default::default()
+        };
+        assert!(strategy_condition_met(&strategy, &Ok(tx_response)));
+        assert!(!strategy_condition_met(
+            &strategy,
+            &Err(DaemonError::CannotConnectGRPC)
+        ));
+        assert!(strategy_condition_met(
+            &transport_error_strategy(),
+            &Err(DaemonError::CannotConnectGRPC)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_exponential_backoff() {
+        // The channel is never used outside of the `AverageBlock` backoff
+        let channel =
+            tonic::transport::Endpoint::from_static("http://127.0.0.1:9090").connect_lazy();
+        let backoff = Backoff::Exponential {
+            initial: Duration::from_millis(100),
+            multiplier: 2.0,
+            max: Duration::from_millis(500),
+            jitter: false,
+        };
+        assert_eq!(
+            backoff.delay(1, channel.clone()).await.unwrap(),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            backoff.delay(3, channel.clone()).await.unwrap(),
+            Duration::from_millis(400)
+        );
+        assert_eq!(
+            backoff.delay(10, channel.clone()).await.unwrap(),
+            Duration::from_millis(500)
+        );
+
+        let jittered = Backoff::exponential(Duration::from_millis(100), Duration::from_secs(1))
+            .delay(2, channel)
+            .await
+            .unwrap();
+        assert!(jittered >= Duration::from_millis(100) && jittered <= Duration::from_millis(200));
+    }
 }
@@ -9,7 +9,7 @@ use super::{
 };
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 
-use cosmwasm_std::{to_json_binary, Binary, StdError, StdResult};
+use cosmwasm_std::{to_json_binary, Binary, StdError, StdResult, Timestamp};
 use cw_orch_core::environment::IndexResponse;
 use serde::{Deserialize, Serialize};
 
@@ -192,6 +192,20 @@ impl IndexResponse for CosmTxResponse {
         }
     }
 
+    fn tx_hash(&self) -> Option<String> {
+        Some(self.txhash.clone())
+    }
+
+    fn gas_used(&self) -> Option<u64> {
+        Some(self.gas_used)
+    }
+
+    fn tx_timestamp(&self) -> Option<Timestamp> {
+        self.timestamp
+            .timestamp_nanos_opt()
+            .map(|nanos| Timestamp::from_nanos(nanos as u64))
+    }
+
     fn event_attr_value(&self, event_type: &str, attr_key: &str) -> StdResult<String> {
         for event in &self.events {
             if event.r#type == event_type {
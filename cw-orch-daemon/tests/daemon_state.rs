@@ -219,8 +219,7 @@ fn reuse_same_state_multichain() {
 
 #[test]
 #[serial_test::serial]
-#[should_panic]
-fn panic_when_someone_holds_json_file() {
+fn error_when_someone_holds_json_file() {
     let path = std::env::temp_dir()
         .join("should_panic_state")
         .into_os_string()
@@ -229,13 +228,16 @@ fn panic_when_someone_holds_json_file() {
     match unsafe { nix::unistd::fork() } {
         Ok(nix::unistd::ForkResult::Child) => {
             // Occur lock for file for 100 millis
-            let _state = JsonLockedState::new(&path);
+            let _state = JsonLockedState::new(&path).unwrap();
             std::thread::sleep(std::time::Duration::from_millis(100));
         }
         Ok(nix::unistd::ForkResult::Parent { .. }) => {
             // Wait a bit for child to occur lock and try to lock already locked file by child
             std::thread::sleep(std::time::Duration::from_millis(50));
-            let _state = JsonLockedState::new(&path);
+            assert!(matches!(
+                JsonLockedState::new(&path),
+                Err(DaemonError::StateAlreadyLocked(_))
+            ));
         }
         Err(_) => (),
     }
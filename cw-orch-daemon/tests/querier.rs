@@ -8,12 +8,14 @@ mod queriers {
     use cw_orch_daemon::{parse_cw_coins, queriers::Bank, GrpcChannel};
     use cw_orch_networks::networks;
     use mock_contract::InstantiateMsg;
-    use speculoos::{asserting, result::ResultAssertions};
+    use speculoos::{
+        asserting, boolean::BooleanAssertions, option::OptionAssertions, result::ResultAssertions,
+    };
     use std::str::FromStr;
 
     use cw_orch_daemon::{
         queriers::StakingBondStatus,
-        queriers::{CosmWasm, Gov, Ibc, Node, Staking},
+        queriers::{CosmWasm, Distribution, Gov, Ibc, Mint, Node, Slashing, Staking, Upgrade},
         Daemon,
     };
     use tokio::runtime::Runtime;
@@ -86,6 +88,114 @@ mod queriers {
         asserting!("params is ok").that(&params).is_ok();
     }
 
+    /*
+        Querier - Distribution
+    */
+    #[test]
+    fn distribution() {
+        let rt = Runtime::new().unwrap();
+        let channel = rt.block_on(build_channel());
+
+        let distribution = Distribution::new_async(channel);
+
+        let params = rt.block_on(distribution._params());
+        asserting!("params is ok").that(&params).is_ok();
+        asserting!("withdraw address is enabled")
+            .that(&params.unwrap().withdraw_addr_enabled)
+            .is_true();
+
+        let community_pool = rt.block_on(distribution._community_pool());
+        asserting!("community_pool is ok")
+            .that(&community_pool)
+            .is_ok();
+
+        let delegator = Addr::unchecked("juno16g2rahf5846rxzp3fwlswy08fz8ccuwk03k57y");
+        let withdraw_address = rt.block_on(distribution._delegator_withdraw_address(&delegator));
+        asserting!("withdraw address defaults to the delegator")
+            .that(&withdraw_address.unwrap())
+            .is_equal_to(delegator);
+    }
+
+    /*
+        Querier - Mint
+    */
+    #[test]
+    fn mint() {
+        let rt = Runtime::new().unwrap();
+        let channel = rt.block_on(build_channel());
+
+        let mint = Mint::new_async(channel);
+
+        let params = rt.block_on(mint._params());
+        asserting!("params is ok").that(&params).is_ok();
+        let params = params.unwrap();
+
+        let inflation = rt.block_on(mint._inflation());
+        asserting!("inflation is ok").that(&inflation).is_ok();
+        asserting!("inflation is within the bounds")
+            .that(&(params.inflation_min..=params.inflation_max).contains(&inflation.unwrap()))
+            .is_true();
+
+        let annual_provisions = rt.block_on(mint._annual_provisions());
+        asserting!("annual_provisions is ok")
+            .that(&annual_provisions)
+            .is_ok();
+    }
+
+    /*
+        Querier - Slashing
+    */
+    #[test]
+    fn slashing() {
+        let rt = Runtime::new().unwrap();
+        let channel = rt.block_on(build_channel());
+
+        let slashing = Slashing::new_async(channel);
+
+        let params = rt.block_on(slashing._params());
+        asserting!("params is ok").that(&params).is_ok();
+
+        let signing_infos = rt.block_on(slashing._signing_infos(None));
+        asserting!("signing_infos is ok")
+            .that(&signing_infos)
+            .is_ok();
+        let signing_infos = signing_infos.unwrap();
+        asserting!("the local validator has a signing info")
+            .that(&signing_infos.len())
+            .is_equal_to(1);
+
+        let signing_info = rt.block_on(slashing._signing_info(&signing_infos[0].address));
+        asserting!("signing_info matches the listed one")
+            .that(&signing_info.unwrap())
+            .is_equal_to(&signing_infos[0]);
+    }
+
+    /*
+        Querier - Upgrade
+    */
+    #[test]
+    fn upgrade() {
+        let rt = Runtime::new().unwrap();
+        let channel = rt.block_on(build_channel());
+
+        let upgrade = Upgrade::new_async(channel);
+
+        let current_plan = rt.block_on(upgrade._current_plan());
+        asserting!("no upgrade is scheduled")
+            .that(&current_plan.unwrap())
+            .is_none();
+
+        let applied_plan = rt.block_on(upgrade._applied_plan("unknown-upgrade"));
+        asserting!("unknown upgrade was not applied")
+            .that(&applied_plan.unwrap())
+            .is_none();
+
+        let module_versions = rt.block_on(upgrade._module_versions("bank"));
+        asserting!("bank module has a version")
+            .that(&module_versions.unwrap().len())
+            .is_equal_to(1);
+    }
+
     /*
         Querier - Bank
     */
@@ -19,6 +19,9 @@ pub mod prelude {
     };
     #[cfg(feature = "daemon")]
     pub use cw_orch_starship::Starship;
+
+    #[cfg(feature = "clone-testing")]
+    pub use cw_orch_interchain_mock::CloneInterchainEnv;
 }
 
 #[cfg(not(target_arch = "wasm32"))]
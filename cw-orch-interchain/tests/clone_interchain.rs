@@ -0,0 +1,75 @@
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcTimeout};
+use cw_orch::{
+    daemon::networks::{JUNO_1, OSMOSIS_1},
+    prelude::*,
+};
+use cw_orch_clone_testing::cw_multi_test::Executor;
+use cw_orch_interchain::prelude::*;
+use cw_orch_interchain_mock::ibc_denom;
+
+fn transfer_channel(interchain: &CloneInterchainEnv) -> cw_orch::anyhow::Result<(String, String)> {
+    let channel = interchain.create_channel(
+        "juno-1",
+        "osmosis-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let (juno_port, osmosis_port) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    Ok((
+        juno_port.channel.unwrap().to_string(),
+        osmosis_port.channel.unwrap().to_string(),
+    ))
+}
+
+#[test]
+fn relays_between_forked_chains() -> cw_orch::anyhow::Result<()> {
+    let interchain = CloneInterchainEnv::new(vec![JUNO_1, OSMOSIS_1])?;
+    let (juno_channel, osmosis_channel) = transfer_channel(&interchain)?;
+    let juno = interchain.get_chain("juno-1")?;
+    let osmosis = interchain.get_chain("osmosis-1")?;
+
+    let receiver = osmosis.init_account();
+    juno.add_balance(&juno.sender_addr(), vec![coin(100, "ujuno")])?;
+    let tx = juno.app.borrow_mut().execute(
+        juno.sender_addr(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: juno_channel,
+            to_address: receiver.to_string(),
+            amount: coin(100, "ujuno"),
+            timeout: IbcTimeout::with_timestamp(juno.block_info()?.time.plus_seconds(600)),
+            memo: None,
+        }),
+    )?;
+
+    let flow = interchain.await_and_check_packets("juno-1", tx.into())?;
+    assert_eq!(flow.packets.len(), 1);
+
+    let voucher = ibc_denom(&format!("transfer/{osmosis_channel}/ujuno"));
+    assert_eq!(osmosis.query_balance(&receiver, &voucher)?.u128(), 100);
+    Ok(())
+}
+
+#[test]
+fn closes_channels_between_forked_chains() -> cw_orch::anyhow::Result<()> {
+    let interchain = CloneInterchainEnv::new(vec![JUNO_1, OSMOSIS_1])?;
+    let channel = interchain.create_channel(
+        "juno-1",
+        "osmosis-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+
+    interchain.close_channel("juno-1", &channel.interchain_channel)?;
+
+    let closed = interchain.closed_channels();
+    assert_eq!(closed.len(), 2);
+    assert_eq!(closed[0].chain_id, "juno-1");
+    assert_eq!(closed[1].chain_id, "osmosis-1");
+    Ok(())
+}
@@ -79,6 +79,46 @@ pub fn full_ica_test<Chain: IbcQueryHandler, IBC: InterchainEnv<Chain>>(
     Ok(())
 }
 
+/// Closes the ICA channel and checks that both contracts handled `ibc_channel_close`
+pub fn ica_channel_close_test<Chain: IbcQueryHandler, IBC: InterchainEnv<Chain>>(
+    interchain: &IBC,
+    host_chain_id: &str,
+    controller_chain_id: &str,
+) -> cw_orch::anyhow::Result<()>
+where
+    IBC::Error: From<InterchainError>,
+{
+    let host_chain = interchain.get_chain(host_chain_id)?;
+    let controller_chain = interchain.get_chain(controller_chain_id)?;
+
+    let cw1 = Cw1::new("cw1", host_chain.clone());
+    let host = Host::new("host", host_chain.clone());
+    let controller = Controller::new("controller", controller_chain.clone());
+
+    deploy_contracts(&cw1, &host, &controller)?;
+
+    let channel = interchain.create_contract_channel(
+        &controller,
+        &host,
+        "simple-ica-v2",
+        Some(cosmwasm_std::IbcOrder::Unordered),
+    )?;
+    let remote_accounts: controller_msgs::ListAccountsResponse =
+        controller.query(&controller_msgs::QueryMsg::ListAccounts {})?;
+    assert_that!(remote_accounts.accounts.len()).is_equal_to(1);
+
+    let closed = interchain.close_channel(controller_chain_id, &channel.interchain_channel)?;
+    // Both contracts were called during the closing handshake
+    assert_that!(closed.init.events().is_empty()).is_false();
+    assert_that!(closed.confirm.events().is_empty()).is_false();
+
+    // The controller forgets the account of the closed channel
+    let remote_accounts: controller_msgs::ListAccountsResponse =
+        controller.query(&controller_msgs::QueryMsg::ListAccounts {})?;
+    assert_that!(remote_accounts.accounts.len()).is_equal_to(0);
+    Ok(())
+}
+
 fn deploy_contracts<Chain: CwEnv>(
     cw1: &Cw1<Chain>,
     host: &Host<Chain>,
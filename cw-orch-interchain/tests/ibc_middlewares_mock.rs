@@ -0,0 +1,269 @@
+use cosmwasm_std::{
+    coin, to_json_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, IbcMsg, IbcTimeout,
+    IbcTimeoutBlock, MessageInfo, Response, StdResult,
+};
+use cw_orch::{
+    environment::{BankQuerier, QueryHandler, TxHandler},
+    mock::cw_multi_test::{AppResponse, ContractWrapper, Executor},
+};
+use cw_orch_interchain_core::{IbcAckParser, IbcPacketOutcome, InterchainEnv};
+use cw_orch_interchain_mock::{ibc_denom, MockInterchainEnv};
+use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+/// Creates a transfer channel and returns its channel ids on the source and destination chains
+fn transfer_channel(
+    interchain: &MockInterchainEnv,
+    src_chain: &str,
+    dst_chain: &str,
+) -> cw_orch::anyhow::Result<(String, String)> {
+    let channel = interchain.create_channel(
+        src_chain,
+        dst_chain,
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let (src_port, dst_port) = channel
+        .interchain_channel
+        .get_ordered_ports_from(src_chain)?;
+    Ok((
+        src_port.channel.unwrap().to_string(),
+        dst_port.channel.unwrap().to_string(),
+    ))
+}
+
+/// Returns the escrow account receiving the tokens sent in `tx`
+fn escrow(tx: &AppResponse, sender: &Addr) -> Addr {
+    let transfer = tx
+        .events
+        .iter()
+        .find(|e| {
+            e.ty == "transfer"
+                && e.attributes
+                    .iter()
+                    .any(|a| a.key == "sender" && a.value == sender.as_str())
+        })
+        .expect("the tokens should be escrowed");
+    let recipient = transfer
+        .attributes
+        .iter()
+        .find(|a| a.key == "recipient")
+        .unwrap();
+    Addr::unchecked(&recipient.value)
+}
+
+// Contract called by the wasm hook, storing the sender of its last execution
+fn hook_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn hook_execute(deps: DepsMut, _: Env, info: MessageInfo, _: Empty) -> StdResult<Response> {
+    deps.storage.set(b"sender", info.sender.as_bytes());
+    Ok(Response::new().set_data(to_json_binary(&info.funds)?))
+}
+
+fn hook_query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    let sender = deps.storage.get(b"sender").unwrap_or_default();
+    to_json_binary(&String::from_utf8_lossy(&sender))
+}
+
+fn transfer_with_memo(
+    interchain: &MockInterchainEnv,
+    channel_id: String,
+    to_address: String,
+    memo: String,
+) -> cw_orch::anyhow::Result<cw_orch::mock::cw_multi_test::AppResponse> {
+    let juno = interchain.get_chain("juno-1")?;
+    juno.add_balance(&juno.sender_addr(), vec![coin(100, "ujuno")])?;
+    let response = juno.app.borrow_mut().execute(
+        juno.sender_addr(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id,
+            to_address,
+            amount: coin(100, "ujuno"),
+            timeout: IbcTimeout::with_block(IbcTimeoutBlock {
+                revision: 1,
+                height: juno.block_info()?.height + 10,
+            }),
+            memo: Some(memo),
+        }),
+    )?;
+    Ok(response)
+}
+
+#[test]
+fn packet_forward_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![
+        ("juno-1", "sender"),
+        ("osmosis-1", "sender"),
+        ("stargaze-1", "sender"),
+    ]);
+    let (juno_osmosis, _) = transfer_channel(&interchain, "juno-1", "osmosis-1")?;
+    let (osmosis_stargaze, _) = transfer_channel(&interchain, "osmosis-1", "stargaze-1")?;
+    let osmosis = interchain.get_chain("osmosis-1")?;
+    let stargaze = interchain.get_chain("stargaze-1")?;
+
+    let memo = format!(
+        r#"{{"forward":{{"receiver":"{}","port":"transfer","channel":"{}"}}}}"#,
+        stargaze.sender_addr(),
+        osmosis_stargaze
+    );
+    let tx = transfer_with_memo(
+        &interchain,
+        juno_osmosis,
+        osmosis.sender_addr().to_string(),
+        memo,
+    )?;
+
+    // The forwarded packet is followed and acknowledged as well
+    let flow = interchain.await_and_check_packets("juno-1", tx)?;
+    assert_eq!(flow.packets[0].receive_tx.packets.len(), 1);
+
+    assert!(osmosis
+        .bank_querier()
+        .balance(&osmosis.sender_addr(), None)?
+        .is_empty());
+    let stargaze_balance = stargaze
+        .bank_querier()
+        .balance(&stargaze.sender_addr(), None)?;
+    assert_eq!(stargaze_balance.len(), 1);
+    assert_eq!(stargaze_balance[0].amount.u128(), 100);
+    assert!(stargaze_balance[0].denom.starts_with("ibc/"));
+
+    Ok(())
+}
+
+#[test]
+fn packet_forward_any_receiver_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![
+        ("juno-1", "sender"),
+        ("osmosis-1", "sender"),
+        ("stargaze-1", "sender"),
+    ]);
+    let (juno_osmosis, _) = transfer_channel(&interchain, "juno-1", "osmosis-1")?;
+    let (osmosis_stargaze, _) = transfer_channel(&interchain, "osmosis-1", "stargaze-1")?;
+    let stargaze = interchain.get_chain("stargaze-1")?;
+
+    // The receiver of a forwarded packet isn't required to be an address of the intermediate chain
+    let memo = format!(
+        r#"{{"forward":{{"receiver":"{}","port":"transfer","channel":"{}"}}}}"#,
+        stargaze.sender_addr(),
+        osmosis_stargaze
+    );
+    let tx = transfer_with_memo(&interchain, juno_osmosis, "pfm".to_string(), memo)?;
+
+    let flow = interchain.await_and_check_packets("juno-1", tx)?;
+    assert_eq!(flow.packets[0].receive_tx.packets.len(), 1);
+
+    let stargaze_balance = stargaze
+        .bank_querier()
+        .balance(&stargaze.sender_addr(), None)?;
+    assert_eq!(stargaze_balance.len(), 1);
+    assert_eq!(stargaze_balance[0].amount.u128(), 100);
+
+    Ok(())
+}
+
+#[test]
+fn failed_wasm_hook_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("osmosis-1", "sender")]);
+    let (juno_osmosis, osmosis_juno) = transfer_channel(&interchain, "juno-1", "osmosis-1")?;
+    let juno = interchain.get_chain("juno-1")?;
+    let osmosis = interchain.get_chain("osmosis-1")?;
+
+    // The receiver of the transfer isn't the contract of the hook
+    let memo = format!(
+        r#"{{"wasm":{{"contract":"{}","msg":{{}}}}}}"#,
+        osmosis.addr_make("contract")
+    );
+    let tx = transfer_with_memo(
+        &interchain,
+        juno_osmosis,
+        osmosis.sender_addr().to_string(),
+        memo,
+    )?;
+    let escrow = escrow(&tx, &juno.sender_addr());
+
+    let flow = interchain.await_packets("juno-1", tx)?;
+    let IbcPacketOutcome::Success { ack, .. } = &flow.packets[0] else {
+        panic!("the transfer should be acknowledged")
+    };
+    assert!(IbcAckParser::ics20_ack(ack).is_err());
+
+    // The vouchers received on osmosis are reverted
+    let voucher = ibc_denom(&format!("transfer/{osmosis_juno}/ujuno"));
+    assert!(osmosis.bank_querier().supply_of(voucher)?.amount.is_zero());
+
+    // The error acknowledgement releases the escrowed tokens back to the sender
+    assert!(juno
+        .bank_querier()
+        .balance(&escrow, Some("ujuno".to_string()))?
+        .iter()
+        .all(|c| c.amount.is_zero()));
+    assert_eq!(juno.bank_querier().supply_of("ujuno")?.amount.u128(), 100);
+    assert!(osmosis
+        .bank_querier()
+        .balance(&osmosis.sender_addr(), None)?
+        .is_empty());
+    assert_eq!(
+        juno.bank_querier()
+            .balance(&juno.sender_addr(), Some("ujuno".to_string()))?[0]
+            .amount
+            .u128(),
+        100
+    );
+
+    Ok(())
+}
+
+#[test]
+fn wasm_hook_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("osmosis-1", "sender")]);
+    let (juno_osmosis, osmosis_juno) = transfer_channel(&interchain, "juno-1", "osmosis-1")?;
+    let juno = interchain.get_chain("juno-1")?;
+    let osmosis = interchain.get_chain("osmosis-1")?;
+
+    let code_id = osmosis
+        .app
+        .borrow_mut()
+        .store_code(Box::new(ContractWrapper::new(
+            hook_execute,
+            hook_instantiate,
+            hook_query,
+        )));
+    let contract = osmosis.app.borrow_mut().instantiate_contract(
+        code_id,
+        osmosis.sender_addr(),
+        &Empty {},
+        &[],
+        "hook",
+        None,
+    )?;
+
+    let memo = format!(r#"{{"wasm":{{"contract":"{contract}","msg":{{}}}}}}"#);
+    let tx = transfer_with_memo(&interchain, juno_osmosis, contract.to_string(), memo)?;
+
+    let flow = interchain.await_packets("juno-1", tx)?;
+    let IbcPacketOutcome::Success { ack, .. } = &flow.packets[0] else {
+        panic!("the transfer should be acknowledged")
+    };
+    IbcAckParser::ibc_hooks_ack(ack)?;
+
+    // The contract is executed with the tokens, by the intermediate sender
+    let voucher = ibc_denom(&format!("transfer/{osmosis_juno}/ujuno"));
+    assert_eq!(osmosis.query_balance(&contract, &voucher)?.u128(), 100);
+    let hooks_sender = interchain.ibc_hooks_sender(
+        "osmosis-1",
+        &osmosis_juno.parse()?,
+        juno.sender_addr().as_str(),
+    )?;
+    let sender: String = osmosis.query(&Empty {}, &contract)?;
+    assert_eq!(sender, hooks_sender.to_string());
+    assert!(osmosis
+        .bank_querier()
+        .balance(&hooks_sender, None)?
+        .is_empty());
+
+    Ok(())
+}
@@ -27,3 +27,14 @@ fn mock_ica_demo() -> cw_orch::anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn mock_ica_channel_close() -> cw_orch::anyhow::Result<()> {
+    let common_sender = "sender";
+    let interchain = MockInterchainEnv::new(vec![(JUNO, common_sender), (OSMOSIS, common_sender)]);
+
+    common::ica_demo::ica_channel_close_test(&interchain, JUNO, OSMOSIS)?;
+
+    assert_eq!(interchain.closed_channels().len(), 2);
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcTimeout, IbcTimeoutBlock};
+use cw_orch::{
+    environment::{QueryHandler, TxHandler},
+    mock::cw_multi_test::Executor,
+};
+use cw_orch_interchain_core::{IbcPacketOutcome, InterchainEnv};
+use cw_orch_interchain_mock::MockInterchainEnv;
+use ibc_relayer_types::core::ics24_host::identifier::PortId;
+
+#[test]
+fn packet_control_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        None,
+    )?;
+    let juno = interchain.get_chain("juno-1")?;
+    let stargaze = interchain.get_chain("stargaze-1")?;
+    let channel_id = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?
+        .0
+        .channel
+        .unwrap()
+        .to_string();
+
+    juno.add_balance(&juno.sender_addr(), vec![coin(300, "ujuno")])?;
+    let transfer = |amount: u128| {
+        juno.app.borrow_mut().execute(
+            juno.sender_addr(),
+            CosmosMsg::Ibc(IbcMsg::Transfer {
+                channel_id: channel_id.clone(),
+                to_address: stargaze.sender_addr().to_string(),
+                amount: coin(amount, "ujuno"),
+                timeout: IbcTimeout::with_block(IbcTimeoutBlock {
+                    revision: 1,
+                    height: stargaze.block_info().unwrap().height + 10,
+                }),
+                memo: None,
+            }),
+        )
+    };
+
+    let first = interchain.hold_packets("juno-1", &transfer(100)?)?;
+    let second = interchain.hold_packets("juno-1", &transfer(100)?)?;
+    let third = interchain.hold_packets("juno-1", &transfer(100)?)?;
+    assert_eq!(interchain.pending_packets().len(), 3);
+
+    // Packets are relayed out of order
+    let flow = interchain.relay(&second[0])?;
+    assert!(matches!(flow.outcome, IbcPacketOutcome::Success { .. }));
+
+    let flow = interchain.timeout(&first[0])?;
+    assert!(matches!(flow.outcome, IbcPacketOutcome::Timeout { .. }));
+
+    interchain.drop_packet(&third[0])?;
+    assert!(interchain.pending_packets().is_empty());
+    assert!(interchain.relay(&third[0]).is_err());
+
+    // Only the relayed packet was received, the timed out transfer was refunded
+    assert_eq!(
+        stargaze.query_all_balances(&stargaze.sender_addr())?.len(),
+        1
+    );
+    assert_eq!(
+        juno.query_balance(&juno.sender_addr(), "ujuno")?.u128(),
+        100
+    );
+
+    Ok(())
+}
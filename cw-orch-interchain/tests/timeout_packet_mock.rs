@@ -1,4 +1,4 @@
-use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcTimeout, IbcTimeoutBlock};
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcOrder, IbcTimeout, IbcTimeoutBlock};
 use cw_orch::{
     environment::{QueryHandler, TxHandler},
     mock::cw_multi_test::Executor,
@@ -55,3 +55,54 @@ fn timeout_packet_mock() -> cw_orch::anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn timeout_closes_ordered_channel_mock() -> cw_orch::anyhow::Result<()> {
+    let interchain = MockInterchainEnv::new(vec![("juno-1", "sender"), ("stargaze-1", "sender")]);
+
+    let channel = interchain.create_channel(
+        "juno-1",
+        "stargaze-1",
+        &PortId::transfer(),
+        &PortId::transfer(),
+        "ics20-1",
+        Some(IbcOrder::Ordered),
+    )?;
+    let juno = interchain.get_chain("juno-1")?;
+    let stargaze = interchain.get_chain("stargaze-1")?;
+    let (juno_port, _) = channel
+        .interchain_channel
+        .get_ordered_ports_from("juno-1")?;
+    let juno_channel = juno_port.channel.unwrap();
+
+    juno.add_balance(&juno.sender_addr(), vec![coin(100, "ujuno")])?;
+    let tx_resp = juno.app.borrow_mut().execute(
+        juno.sender_addr(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: juno_channel.to_string(),
+            to_address: stargaze.sender_addr().to_string(),
+            amount: coin(100, "ujuno"),
+            timeout: IbcTimeout::with_block(IbcTimeoutBlock {
+                revision: 1,
+                height: stargaze.block_info()?.height + 10,
+            }),
+            memo: None,
+        }),
+    )?;
+
+    let packets = interchain.hold_packets("juno-1", &tx_resp)?;
+    let flow = interchain.timeout(&packets[0])?;
+    assert!(matches!(
+        flow.outcome,
+        cw_orch_interchain_core::IbcPacketOutcome::Timeout { .. }
+    ));
+
+    // Only the end of the chain that sent the packet is closed
+    let closed = interchain.closed_channels();
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].chain_id, "juno-1");
+    assert_eq!(closed[0].port, PortId::transfer());
+    assert_eq!(closed[0].channel, juno_channel);
+
+    Ok(())
+}
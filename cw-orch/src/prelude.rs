@@ -14,10 +14,10 @@
 pub use crate::contract::interface_traits::{
     CallAs, ConditionalMigrate, ConditionalUpload, ContractInstance, CwOrchExecute,
     CwOrchInstantiate, CwOrchMigrate, CwOrchQuery, CwOrchUpload, ExecutableContract,
-    InstantiableContract, MigratableContract, QueryableContract, Uploadable,
+    InstantiableContract, MigratableContract, QueryableContract, Uploadable, VerifiableContract,
 };
 
-pub use cw_orch_core::contract::Deploy;
+pub use cw_orch_core::contract::{Deploy, VerifyDeployment};
 
 pub use crate::environment::ChainState;
 pub use crate::environment::StateInterface;
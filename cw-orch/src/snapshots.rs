@@ -1,18 +1,10 @@
-//! Defined the snapshot testing macro on the Mock environment
+//! Defined the snapshot testing macro on all environments implementing [`StorageDumper`](crate::environment::StorageDumper)
 //! This is included here and not in the mock package because it needs to import traits to work
 
-/// Function helper used to parse storage into readable strings
-pub fn parse_storage(storage: &[(Vec<u8>, Vec<u8>)]) -> Vec<(String, String)> {
-    storage
-        .iter()
-        .map(|(key, value)| {
-            (
-                String::from_utf8_lossy(key).to_string(),
-                String::from_utf8_lossy(value).to_string(),
-            )
-        })
-        .collect::<Vec<_>>()
-}
+/// Function helper used to parse storage into readable keys and values.
+/// Keys are decoded from the cw-storage-plus format and JSON values are parsed, so snapshots are stable across backends.
+pub use cw_orch_core::environment::parse_storage;
+
 /// This functions allows for making sure the tests are stabilised and changes made to contracts don't have an impact on the internal storage
 /// This should ONLY be used when cw_orch is in scope
 /// Usage:
@@ -26,18 +18,11 @@ pub fn parse_storage(storage: &[(Vec<u8>, Vec<u8>)]) -> Vec<(String, String)> {
 macro_rules! take_storage_snapshot {
     ($chain: ident, $name: literal) => {
         // We register and test a snapshot for all contracts storage
-        use ::cw_orch::environment::{ChainState as _, StateInterface as _};
-        let all_contracts = $chain.state().get_all_addresses()?;
-        let all_storage: ::std::collections::BTreeMap<_, _> = all_contracts
+        use ::cw_orch::environment::StorageDumper as _;
+        let all_storage: ::std::collections::BTreeMap<_, _> = $chain
+            .storage_dump()?
             .iter()
-            .map(|(id, contract_addr)| {
-                (
-                    id,
-                    ::cw_orch::snapshots::parse_storage(
-                        &$chain.app.borrow().dump_wasm_raw(&contract_addr),
-                    ),
-                )
-            })
+            .map(|(id, storage)| (id.clone(), ::cw_orch::snapshots::parse_storage(storage)))
             .collect();
 
         ::cw_orch::insta::assert_yaml_snapshot!(
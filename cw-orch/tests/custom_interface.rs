@@ -0,0 +1,166 @@
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, Api, Binary, BlockInfo, CustomMsg, CustomQuery, Deps, DepsMut,
+    Empty, Env, MessageInfo, Querier, Response, StdResult, Storage, Uint128,
+};
+use cw_orch::{
+    interface,
+    mock::{
+        cw_multi_test::{AnyResult, AppResponse, BankSudo, CosmosRouter, Module, SudoMsg},
+        MockBase, MockState,
+    },
+    prelude::*,
+};
+use mock_contract::{InstantiateMsg, QueryMsg};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainMsg {
+    Mint { amount: Uint128 },
+}
+impl CustomMsg for ChainMsg {}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChainQuery {
+    MintDenom {},
+}
+impl CustomQuery for ChainQuery {}
+
+const DENOM: &str = "uchain";
+
+/// Emulates the chain module minting tokens to the sender of the custom messages
+struct ChainModule;
+
+impl Module for ChainModule {
+    type ExecT = ChainMsg;
+    type QueryT = ChainQuery;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: ChainMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let ChainMsg::Mint { amount } = msg;
+        router.sudo(
+            api,
+            storage,
+            block,
+            SudoMsg::Bank(BankSudo::Mint {
+                to_address: sender.to_string(),
+                amount: coins(amount.u128(), DENOM),
+            }),
+        )
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: ChainQuery,
+    ) -> AnyResult<Binary> {
+        let ChainQuery::MintDenom {} = request;
+        Ok(to_json_binary(DENOM)?)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        anyhow::bail!("sudo is not supported")
+    }
+}
+
+type ChainMock = MockBase<cosmwasm_std::testing::MockApi, MockState, ChainModule>;
+
+fn execute(
+    _deps: DepsMut<ChainQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response<ChainMsg>> {
+    Ok(Response::new().add_message(cosmwasm_std::CosmosMsg::Custom(ChainMsg::Mint { amount })))
+}
+
+fn instantiate(
+    _deps: DepsMut<ChainQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response<ChainMsg>> {
+    Ok(Response::new())
+}
+
+fn query(deps: Deps<ChainQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    let denom: String = deps.querier.query(&cosmwasm_std::QueryRequest::Custom(
+        ChainQuery::MintDenom {},
+    ))?;
+    to_json_binary(&denom)
+}
+
+#[interface(Empty, Uint128, Empty, Empty, custom = (ChainMsg, ChainQuery))]
+pub struct Minter;
+
+impl<Chain> Uploadable for Minter<Chain> {
+    fn custom_wrapper() -> Box<dyn MockContract<ChainMsg, ChainQuery>> {
+        Box::new(ContractWrapper::new(execute, instantiate, query))
+    }
+}
+
+#[test]
+fn custom_messages_and_queries() -> anyhow::Result<()> {
+    let chain = ChainMock::new_with_custom_module("sender", MockState::new(), ChainModule);
+    let minter = Minter::new("minter", chain.clone());
+
+    minter.upload()?;
+    minter.instantiate(&Empty {}, None, &[])?;
+    minter.execute(&Uint128::new(100), &[])?;
+
+    assert_eq!(
+        chain.query_all_balances(&minter.address()?)?,
+        coins(100, DENOM)
+    );
+    assert_eq!(minter.query::<String>(&Empty {})?, DENOM);
+    Ok(())
+}
+
+#[test]
+fn empty_contracts_in_custom_environment() -> anyhow::Result<()> {
+    let chain = ChainMock::new_with_custom_module("sender", MockState::new(), ChainModule);
+
+    let contract = mock_contract::MockContract::new("test:mock_contract", chain.clone());
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, &[])?;
+
+    let response: String = contract.query(&QueryMsg::FirstQuery {})?;
+    assert!(!response.is_empty());
+    Ok(())
+}
+
+#[test]
+fn custom_contracts_in_empty_environment() {
+    let chain = Mock::new("sender");
+    let minter = Minter::new("minter", chain);
+
+    // The custom types of the contract don't match the ones of the environment
+    assert!(minter.upload().is_err());
+}
@@ -0,0 +1,40 @@
+//! Records the data of contracts in the clone testing cache, so that tests using them can run offline.
+//!
+//! Usage: `cargo run --example prewarm_cache -- <chain-id> <contract-address>...`
+//! The cache folder can be changed with `CW_ORCH_CLONE_CACHE_DIR`.
+use std::path::PathBuf;
+
+use cosmwasm_std::Addr;
+use cw_orch_clone_testing::{
+    cache::CLONE_CACHE_DIR_ENV_NAME, CacheMode, CloneTesting, RemoteCache,
+};
+use cw_orch_core::environment::QueryHandler;
+use cw_orch_daemon::{networks::parse_network, RUNTIME};
+
+pub fn main() -> anyhow::Result<()> {
+    pretty_env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let chain_id = args.next().ok_or(anyhow::anyhow!(
+        "Usage: prewarm_cache <chain-id> <contract-address>..."
+    ))?;
+    let contracts: Vec<Addr> = args.map(Addr::unchecked).collect();
+
+    let chain = parse_network(&chain_id).map_err(anyhow::Error::msg)?;
+    let dir = match std::env::var(CLONE_CACHE_DIR_ENV_NAME) {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => RemoteCache::default_dir()?,
+    };
+    let cache = RemoteCache::new(dir, CacheMode::Record);
+    let mock = CloneTesting::new_with_cache(&RUNTIME, chain, cache.clone())?;
+    mock.prewarm(&contracts)?;
+
+    log::info!(
+        "Cached {} contracts of {} at height {} in {}",
+        contracts.len(),
+        chain_id,
+        mock.block_info()?.height,
+        cache.dir().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,555 @@
+//! Disk cache of the data fetched from the forked chain.
+//!
+//! The remote chain is accessed through a local gRPC proxy. In [`CacheMode::Record`], the proxy forwards the requests
+//! to the chain at a fixed block height and stores the responses on disk. Without cache, the proxy is also used
+//! to fork the chain at a past height.
+//! In [`CacheMode::Replay`], the responses are only read from disk, which allows running clone tests without network.
+//!
+//! Every remote fetch (contract code, raw storage, balances, contract and code info, blocks) is cached,
+//! keyed by the chain id, the block height and the request.
+
+use std::{
+    convert::Infallible,
+    future::{ready, Ready},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use bytes::{Buf, BufMut, Bytes};
+use cw_orch_core::{environment::ChainInfoOwned, CwEnvError};
+use cw_orch_daemon::{env::default_state_folder, queriers::Node, GrpcChannel};
+use http_body_util::{combinators::WithTrailers, BodyExt, Full};
+use hyper::{
+    body::Incoming,
+    header::{HeaderValue, CONTENT_TYPE},
+    server::conn::http2,
+    service::service_fn,
+    HeaderMap, Request, Response,
+};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use sha2::{Digest, Sha256};
+use tokio::{net::TcpListener, runtime::Runtime};
+use tonic::{
+    client::Grpc,
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::http::uri::PathAndQuery,
+    transport::Channel,
+    Code, Status,
+};
+
+/// Record or replay mode of the cache, see [`RemoteCache`]
+pub const CLONE_CACHE_ENV_NAME: &str = "CW_ORCH_CLONE_CACHE";
+/// Folder of the cache, see [`RemoteCache`]
+pub const CLONE_CACHE_DIR_ENV_NAME: &str = "CW_ORCH_CLONE_CACHE_DIR";
+
+/// Header used by cosmos-sdk nodes to answer queries at a given height
+const BLOCK_HEIGHT_HEADER: &str = "x-cosmos-block-height";
+
+/// How the [`RemoteCache`] uses the remote chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Fetch the data missing from the cache from the chain and store it
+    Record,
+    /// Only read the data from the cache, requests missing from the cache fail
+    Replay,
+}
+
+impl FromStr for CacheMode {
+    type Err = CwEnvError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "record" => Ok(CacheMode::Record),
+            "replay" => Ok(CacheMode::Replay),
+            _ => Err(CwEnvError::StdErr(format!(
+                "Unknown clone testing cache mode {s}, expected `record` or `replay`"
+            ))),
+        }
+    }
+}
+
+/// Disk cache of the data [`CloneTesting`](crate::CloneTesting) fetches from the forked chain.
+///
+/// The cache is stored in `<dir>/<chain-id>/<height>/`, one file per request.
+/// It can be enabled for all clone tests with the `CW_ORCH_CLONE_CACHE` environment variable (`record` or `replay`)
+/// and its location changed with `CW_ORCH_CLONE_CACHE_DIR` (defaults to `~/.cw-orchestrator/clone-cache`).
+///
+/// ## Example
+/// ```no_run
+/// use cw_orch_clone_testing::{CloneTesting, RemoteCache};
+/// use cw_orch_daemon::RUNTIME;
+///
+/// let chain = cw_orch_daemon::networks::JUNO_1;
+/// // Record the data used by the test, at the latest height of the chain
+/// let cache = RemoteCache::record().unwrap();
+/// let mock = CloneTesting::new_with_cache(&RUNTIME, chain.clone(), cache).unwrap();
+///
+/// // Later, without network, at the last recorded height
+/// let cache = RemoteCache::replay().unwrap();
+/// let mock = CloneTesting::new_with_cache(&RUNTIME, chain, cache).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct RemoteCache {
+    dir: PathBuf,
+    mode: CacheMode,
+    height: Option<u64>,
+}
+
+impl RemoteCache {
+    /// Create a cache stored in `dir`
+    pub fn new(dir: impl Into<PathBuf>, mode: CacheMode) -> Self {
+        Self {
+            dir: dir.into(),
+            mode,
+            height: None,
+        }
+    }
+
+    /// Cache in the default folder recording the missing data
+    pub fn record() -> Result<Self, CwEnvError> {
+        Ok(Self::new(Self::default_dir()?, CacheMode::Record))
+    }
+
+    /// Cache in the default folder replaying the recorded data
+    pub fn replay() -> Result<Self, CwEnvError> {
+        Ok(Self::new(Self::default_dir()?, CacheMode::Replay))
+    }
+
+    /// Use the data at the given height.
+    /// Defaults to the latest height of the chain when recording and to the last recorded height when replaying.
+    pub fn at_height(mut self, height: u64) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Cache configured with the `CW_ORCH_CLONE_CACHE` and `CW_ORCH_CLONE_CACHE_DIR` environment variables, if any
+    pub fn from_env() -> Result<Option<Self>, CwEnvError> {
+        let Ok(mode) = std::env::var(CLONE_CACHE_ENV_NAME) else {
+            return Ok(None);
+        };
+        let dir = match std::env::var(CLONE_CACHE_DIR_ENV_NAME) {
+            Ok(dir) => PathBuf::from(dir),
+            Err(_) => Self::default_dir()?,
+        };
+        Ok(Some(Self::new(dir, mode.parse()?)))
+    }
+
+    /// Default folder of the cache, `~/.cw-orchestrator/clone-cache`
+    pub fn default_dir() -> Result<PathBuf, CwEnvError> {
+        Ok(default_state_folder()?.join("clone-cache"))
+    }
+
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Heights recorded for a chain, in ascending order
+    pub fn recorded_heights(&self, chain_id: &str) -> Result<Vec<u64>, CwEnvError> {
+        let chain_dir = self.dir.join(chain_id);
+        if !chain_dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut heights = std::fs::read_dir(chain_dir)?
+            .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+            .collect::<Vec<u64>>();
+        heights.sort();
+        Ok(heights)
+    }
+
+    /// Starts the proxy serving the chain through the cache, returns its gRPC url and the height of the data
+    pub(crate) fn serve(
+        &self,
+        rt: &Runtime,
+        chain: &ChainInfoOwned,
+    ) -> Result<(String, u64), CwEnvError> {
+        let (upstream, height) = match self.mode {
+            CacheMode::Record => {
+                let channel =
+                    rt.block_on(GrpcChannel::connect(&chain.grpc_urls, &chain.chain_id))?;
+                let height = match self.height {
+                    Some(height) => height,
+                    None => rt.block_on(Node::new_async(channel.clone())._block_height())?,
+                };
+                (Some(channel), height)
+            }
+            CacheMode::Replay => {
+                let height = match self.height {
+                    Some(height) => height,
+                    None => self
+                        .recorded_heights(&chain.chain_id)?
+                        .pop()
+                        .ok_or_else(|| {
+                            CwEnvError::StdErr(format!(
+                                "Nothing recorded for {} in the clone testing cache at {}",
+                                chain.chain_id,
+                                self.dir.display()
+                            ))
+                        })?,
+                };
+                (None, height)
+            }
+        };
+
+        let dir = self.dir.join(&chain.chain_id).join(height.to_string());
+        std::fs::create_dir_all(&dir)?;
+        let proxy = CacheProxy {
+            dir: Some(dir),
+            upstream,
+            height,
+        };
+        Ok((proxy.start(rt)?, height))
+    }
+}
+
+/// Starts a proxy forwarding the requests to the chain at `height`, without caching them. Returns its gRPC url.
+pub(crate) fn serve_at_height(
+    rt: &Runtime,
+    chain: &ChainInfoOwned,
+    height: u64,
+) -> Result<String, CwEnvError> {
+    let channel = rt.block_on(GrpcChannel::connect(&chain.grpc_urls, &chain.chain_id))?;
+    let proxy = CacheProxy {
+        dir: None,
+        upstream: Some(channel),
+        height,
+    };
+    proxy.start(rt)
+}
+
+/// gRPC server answering the requests from the cache, and from the chain at a fixed height when recording
+struct CacheProxy {
+    /// Folder of the cached responses at `height`, if caching
+    dir: Option<PathBuf>,
+    upstream: Option<Channel>,
+    height: u64,
+}
+
+/// Message of a gRPC response followed by its status in the trailers
+type GrpcBody = WithTrailers<Full<Bytes>, Ready<Option<Result<HeaderMap, Infallible>>>>;
+
+impl CacheProxy {
+    /// Serve the proxy on a local port, returns its url
+    fn start(self, rt: &Runtime) -> Result<String, CwEnvError> {
+        let listener = rt.block_on(TcpListener::bind("127.0.0.1:0"))?;
+        let url = format!("http://{}", listener.local_addr()?);
+        rt.spawn(Arc::new(self).run(listener));
+        Ok(url)
+    }
+
+    async fn run(self: Arc<Self>, listener: TcpListener) {
+        loop {
+            let Ok((stream, _)) = listener.accept().await else {
+                continue;
+            };
+            let proxy = self.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |request| proxy.clone().handle(request));
+                if let Err(e) = http2::Builder::new(TokioExecutor::new())
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    log::debug!("Clone testing cache connection closed: {e}");
+                }
+            });
+        }
+    }
+
+    async fn handle(
+        self: Arc<Self>,
+        request: Request<Incoming>,
+    ) -> Result<Response<GrpcBody>, Infallible> {
+        let path = request.uri().path().to_string();
+        let body = match request.into_body().collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(e) => return Ok(grpc_response(Err(Status::internal(e.to_string())))),
+        };
+        // Skip the gRPC message header (compression flag and length)
+        let message = body.get(5..).unwrap_or_default().to_vec();
+        Ok(grpc_response(self.fetch(&path, message).await))
+    }
+
+    async fn fetch(&self, path: &str, message: Vec<u8>) -> Result<Vec<u8>, Status> {
+        let file = self
+            .dir
+            .as_ref()
+            .map(|dir| dir.join(entry_name(path, &message)));
+        if let Some(file) = &file {
+            if let Ok(entry) = tokio::fs::read(file).await {
+                return decode_entry(&entry);
+            }
+        }
+        let Some(upstream) = &self.upstream else {
+            return Err(Status::unavailable(format!(
+                "{path} is not in the clone testing cache, record it first"
+            )));
+        };
+
+        let mut request = tonic::Request::new(message);
+        request
+            .metadata_mut()
+            .insert(BLOCK_HEIGHT_HEADER, self.height.into());
+        let mut grpc = Grpc::new(upstream.clone());
+        grpc.ready()
+            .await
+            .map_err(|e| Status::unavailable(e.to_string()))?;
+        let path =
+            PathAndQuery::try_from(path).map_err(|e| Status::invalid_argument(e.to_string()))?;
+        let response = grpc
+            .unary(request, path, RawCodec)
+            .await
+            .map(tonic::Response::into_inner);
+
+        // Transient failures are not cached
+        let cached = match &response {
+            Err(status) => !matches!(
+                status.code(),
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::Cancelled
+                    | Code::Unknown
+                    | Code::Internal
+                    | Code::ResourceExhausted
+            ),
+            Ok(_) => true,
+        };
+        if let (Some(file), true) = (file, cached) {
+            write_entry(&file, encode_entry(&response))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?;
+        }
+        response
+    }
+}
+
+/// Writes a cache entry atomically: the entry is written to a temporary file of the same directory and renamed,
+/// so that concurrent readers, including other processes sharing the cache, never read a partially written entry
+async fn write_entry(file: &Path, entry: Vec<u8>) -> std::io::Result<()> {
+    static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+    let tmp = file.with_extension(format!(
+        "{}-{}.tmp",
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&tmp, entry).await?;
+    if let Err(e) = tokio::fs::rename(&tmp, file).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Name of the file caching a request
+fn entry_name(path: &str, message: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(path.as_bytes());
+    hasher.update([0]);
+    hasher.update(message);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Cached responses are the message prefixed by `0`, or the error code and message prefixed by `1`
+fn encode_entry(response: &Result<Vec<u8>, Status>) -> Vec<u8> {
+    match response {
+        Ok(message) => [[0].as_slice(), message].concat(),
+        Err(status) => [
+            [1].as_slice(),
+            (status.code() as i32).to_be_bytes().as_slice(),
+            status.message().as_bytes(),
+        ]
+        .concat(),
+    }
+}
+
+fn decode_entry(entry: &[u8]) -> Result<Vec<u8>, Status> {
+    match entry.split_first() {
+        Some((0, message)) => Ok(message.to_vec()),
+        Some((1, error)) if error.len() >= 4 => {
+            let (code, message) = error.split_at(4);
+            Err(Status::new(
+                Code::from_i32(i32::from_be_bytes(code.try_into().unwrap())),
+                String::from_utf8_lossy(message),
+            ))
+        }
+        _ => Err(Status::data_loss("corrupted clone testing cache entry")),
+    }
+}
+
+fn grpc_response(result: Result<Vec<u8>, Status>) -> Response<GrpcBody> {
+    let (data, trailers) = match result {
+        Ok(message) => {
+            let mut data = Vec::with_capacity(message.len() + 5);
+            data.put_u8(0);
+            data.put_u32(message.len() as u32);
+            data.extend(message);
+            let mut trailers = HeaderMap::new();
+            trailers.insert("grpc-status", HeaderValue::from(Code::Ok as i32));
+            (Bytes::from(data), trailers)
+        }
+        Err(status) => (Bytes::new(), status.to_header_map().unwrap_or_default()),
+    };
+    let body = Full::new(data).with_trailers(ready(Some(Ok(trailers))));
+    let mut response = Response::new(body);
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+    response
+}
+
+/// Codec forwarding the encoded protobuf messages as they are
+#[derive(Clone, Copy, Default)]
+struct RawCodec;
+
+impl Codec for RawCodec {
+    type Encode = Vec<u8>;
+    type Decode = Vec<u8>;
+    type Encoder = RawCodec;
+    type Decoder = RawCodec;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        *self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        *self
+    }
+}
+
+impl Encoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        dst.put_slice(&item);
+        Ok(())
+    }
+}
+
+impl Decoder for RawCodec {
+    type Item = Vec<u8>;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(Some(src.copy_to_bytes(src.remaining()).to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache_entries_roundtrip() {
+        let ok = Ok(vec![1, 2, 3]);
+        assert_eq!(decode_entry(&encode_entry(&ok)).unwrap(), vec![1, 2, 3]);
+
+        let not_found = Err(Status::not_found("no such contract"));
+        let status = decode_entry(&encode_entry(&not_found)).unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "no such contract");
+
+        assert_ne!(entry_name("/a", &[1]), entry_name("/b", &[1]));
+    }
+
+    const RAW_STATE_PATH: &str = "/cosmwasm.wasm.v1.Query/RawContractState";
+
+    async fn query(url: String, message: &[u8]) -> Result<Vec<u8>, Status> {
+        let channel = Channel::from_shared(url).unwrap().connect().await.unwrap();
+        let mut grpc = Grpc::new(channel);
+        grpc.ready().await.unwrap();
+        grpc.unary(
+            tonic::Request::new(message.to_vec()),
+            PathAndQuery::from_static(RAW_STATE_PATH),
+            RawCodec,
+        )
+        .await
+        .map(tonic::Response::into_inner)
+    }
+
+    #[test]
+    fn records_and_replays_offline() {
+        let rt = Runtime::new().unwrap();
+        let dir = std::env::temp_dir().join(format!("cw-orch-clone-cache-{}", std::process::id()));
+        let (chain_dir, cache_dir) = (dir.join("chain"), dir.join("cache"));
+        std::fs::create_dir_all(&chain_dir).unwrap();
+        std::fs::create_dir_all(&cache_dir).unwrap();
+
+        // The chain is emulated by a replaying proxy
+        let entries = [
+            (b"key".as_slice(), Ok(b"value".to_vec())),
+            (b"missing".as_slice(), Err(Status::not_found("no such key"))),
+        ];
+        for (key, response) in &entries {
+            std::fs::write(
+                chain_dir.join(entry_name(RAW_STATE_PATH, key)),
+                encode_entry(response),
+            )
+            .unwrap();
+        }
+        let chain = CacheProxy {
+            dir: Some(chain_dir),
+            upstream: None,
+            height: 10,
+        }
+        .start(&rt)
+        .unwrap();
+        let upstream = rt
+            .block_on(Channel::from_shared(chain).unwrap().connect())
+            .unwrap();
+
+        let recording = CacheProxy {
+            dir: Some(cache_dir.clone()),
+            upstream: Some(upstream),
+            height: 10,
+        }
+        .start(&rt)
+        .unwrap();
+        assert_eq!(
+            rt.block_on(query(recording.clone(), b"key")).unwrap(),
+            b"value"
+        );
+        let status = rt.block_on(query(recording, b"missing")).unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+
+        // The entries are renamed in place once written
+        let mut cached: Vec<_> = std::fs::read_dir(&cache_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        cached.sort();
+        let mut expected: Vec<_> = entries
+            .iter()
+            .map(|(key, _)| entry_name(RAW_STATE_PATH, key))
+            .collect();
+        expected.sort();
+        assert_eq!(cached, expected);
+
+        // Only the cache is used when replaying
+        let replaying = CacheProxy {
+            dir: Some(cache_dir),
+            upstream: None,
+            height: 10,
+        }
+        .start(&rt)
+        .unwrap();
+        assert_eq!(
+            rt.block_on(query(replaying.clone(), b"key")).unwrap(),
+            b"value"
+        );
+        let status = rt
+            .block_on(query(replaying.clone(), b"missing"))
+            .unwrap_err();
+        assert_eq!(status.code(), Code::NotFound);
+        let status = rt.block_on(query(replaying, b"other")).unwrap_err();
+        assert_eq!(status.code(), Code::Unavailable);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
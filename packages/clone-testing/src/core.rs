@@ -2,31 +2,52 @@ use std::{cell::RefCell, fmt::Debug, io::Read, rc::Rc};
 
 use clone_cw_multi_test::{
     addons::{MockAddressGenerator, MockApiBech32},
+    ibc::IbcSimpleModule,
     wasm_emulation::{channel::RemoteChannel, storage::analyzer::StorageAnalyzer},
-    App, AppBuilder, BankKeeper, Contract, Executor, WasmKeeper,
+    App, AppBuilder, BankKeeper, Contract, DistributionKeeper, Executor, FailingModule,
+    GovFailingModule, StakeKeeper, WasmKeeper,
 };
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Event, StdError, StdResult,
-    Uint128, WasmMsg,
+    testing::MockStorage, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, Event,
+    StdError, StdResult, Uint128, WasmMsg,
 };
 use cw_orch_core::{
     contract::interface_traits::{ContractInstance, Uploadable},
     environment::{
         AccessConfig, BankQuerier, BankSetter, ChainInfoOwned, ChainState, DefaultQueriers,
-        IndexResponse, StateInterface, TxHandler,
+        IndexResponse, NodeQuerier, StateInterface, StorageDiff, StorageDumper, TxHandler,
     },
     CwEnvError,
 };
-use cw_orch_daemon::{queriers::Node, read_network_config, DEFAULT_DEPLOYMENT, RUNTIME};
+use cw_orch_daemon::{
+    queriers::{CosmWasm, Node},
+    read_network_config, DaemonError, DEFAULT_DEPLOYMENT, RUNTIME,
+};
 use cw_utils::NativeBalance;
 use serde::Serialize;
 use tokio::runtime::Runtime;
 
-use crate::{contract::CloneTestingContract, queriers::bank::CloneBankQuerier};
+use crate::{
+    cache::{serve_at_height, RemoteCache},
+    contract::CloneTestingContract,
+    queriers::bank::CloneBankQuerier,
+    stargate::CloneStargate,
+};
 
 use super::state::MockState;
 
-pub type CloneTestingApp = App<BankKeeper, MockApiBech32>;
+pub type CloneTestingApp = App<
+    BankKeeper,
+    MockApiBech32,
+    MockStorage,
+    FailingModule<Empty, Empty, Empty>,
+    WasmKeeper<Empty, Empty>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcSimpleModule,
+    GovFailingModule,
+    CloneStargate,
+>;
 
 /// Wrapper around a cw-multi-test [`App`](cw_multi_test::App) backend.
 ///
@@ -74,6 +95,13 @@ pub struct CloneTesting<S: StateInterface = MockState> {
     pub state: Rc<RefCell<S>>,
     /// Inner mutable cw-multi-test app backend
     pub app: Rc<RefCell<CloneTestingApp>>,
+    /// Connection to the forked chain
+    pub remote: RemoteChannel,
+    /// Stargate/Any message handlers of the app, see [`CloneTesting::register_stargate_exec`]
+    pub stargate: CloneStargate,
+    /// Whether impersonated accounts are topped up with [`IMPERSONATION_FEE_BALANCE`](crate::IMPERSONATION_FEE_BALANCE)
+    /// of the gas denom, see [`CloneTesting::impersonate`]. Enabled by default.
+    pub top_up_impersonated: bool,
 }
 
 impl CloneTesting {
@@ -186,6 +214,48 @@ impl CloneTesting<MockState> {
         )
     }
 
+    /// Create a mock environment with the default mock state, fetching the remote data through the provided cache.
+    pub fn new_with_cache(
+        rt: &Runtime,
+        chain: impl Into<ChainInfoOwned>,
+        cache: RemoteCache,
+    ) -> Result<Self, CwEnvError> {
+        let chain_data = chain.into();
+        CloneTesting::new_custom_with_cache(
+            rt,
+            chain_data.clone(),
+            MockState::new(chain_data, DEFAULT_DEPLOYMENT),
+            Some(cache),
+        )
+    }
+
+    /// Create a mock environment with the default mock state, forking the chain at a past block height.
+    ///
+    /// All the data fetched from the chain (contract code, storage, balances) is queried at `height`
+    /// and the block info of the environment starts at this height.
+    /// The gRPC endpoints of the chain need to serve historical queries (archive nodes) for heights that are pruned on regular nodes.
+    ///
+    /// ```no_run
+    /// use cw_orch_clone_testing::CloneTesting;
+    /// use cw_orch_daemon::RUNTIME;
+    ///
+    /// let chain = cw_orch_daemon::networks::JUNO_1;
+    /// let mock = CloneTesting::new_at_height(&RUNTIME, chain, 18_000_000).unwrap();
+    /// ```
+    pub fn new_at_height(
+        rt: &Runtime,
+        chain: impl Into<ChainInfoOwned>,
+        height: u64,
+    ) -> Result<Self, CwEnvError> {
+        let chain_data = chain.into();
+        CloneTesting::new_custom_at_height(
+            rt,
+            chain_data.clone(),
+            MockState::new(chain_data, DEFAULT_DEPLOYMENT),
+            height,
+        )
+    }
+
     pub fn new_with_deployment_id(
         rt: &Runtime,
         chain: impl Into<ChainInfoOwned>,
@@ -203,10 +273,50 @@ impl CloneTesting<MockState> {
 impl<S: StateInterface> CloneTesting<S> {
     /// Create a mock environment with a custom mock state.
     /// The state is customizable by implementing the `StateInterface` trait on a custom struct and providing it on the custom constructor.
+    ///
+    /// The remote data is cached if configured with the `CW_ORCH_CLONE_CACHE` environment variable, see [`RemoteCache`].
     pub fn new_custom(
         rt: &Runtime,
         chain: impl Into<ChainInfoOwned>,
         custom_state: S,
+    ) -> Result<Self, CwEnvError> {
+        Self::new_custom_with_cache(rt, chain, custom_state, RemoteCache::from_env()?)
+    }
+
+    /// Create a mock environment with a custom mock state, fetching the remote data through the provided cache.
+    pub fn new_custom_with_cache(
+        rt: &Runtime,
+        chain: impl Into<ChainInfoOwned>,
+        custom_state: S,
+        cache: Option<RemoteCache>,
+    ) -> Result<Self, CwEnvError> {
+        Self::new_custom_with_remote(rt, chain, custom_state, cache, None)
+    }
+
+    /// Create a mock environment with a custom mock state, forking the chain at a past block height.
+    /// See [`CloneTesting::new_at_height`].
+    pub fn new_custom_at_height(
+        rt: &Runtime,
+        chain: impl Into<ChainInfoOwned>,
+        custom_state: S,
+        height: u64,
+    ) -> Result<Self, CwEnvError> {
+        Self::new_custom_with_remote(
+            rt,
+            chain,
+            custom_state,
+            RemoteCache::from_env()?,
+            Some(height),
+        )
+    }
+
+    /// Create a mock environment forking the chain at `height` (latest block if `None`), through the cache if any.
+    fn new_custom_with_remote(
+        rt: &Runtime,
+        chain: impl Into<ChainInfoOwned>,
+        custom_state: S,
+        cache: Option<RemoteCache>,
+        height: Option<u64>,
     ) -> Result<Self, CwEnvError> {
         let chain: ChainInfoOwned = chain.into();
         let chain = if let Some(chain_info) = read_network_config(&chain.chain_id) {
@@ -216,14 +326,24 @@ impl<S: StateInterface> CloneTesting<S> {
         };
         let state = Rc::new(RefCell::new(custom_state));
 
+        // With a cache or a past height, the chain is accessed through a local proxy at a fixed height
+        let (grpc_urls, fixed_height) = match (cache, height) {
+            (Some(cache), height) => {
+                let cache = match height {
+                    Some(height) => cache.at_height(height),
+                    None => cache,
+                };
+                let (url, height) = cache.serve(rt, &chain)?;
+                (vec![url], Some(height))
+            }
+            (None, Some(height)) => (vec![serve_at_height(rt, &chain, height)?], Some(height)),
+            (None, None) => (chain.grpc_urls.clone(), None),
+        };
+
         let pub_address_prefix = chain.network_info.pub_address_prefix.clone();
         let remote_channel = RemoteChannel::new(
             rt,
-            &chain
-                .grpc_urls
-                .iter()
-                .map(|s| s.as_str())
-                .collect::<Vec<_>>(),
+            &grpc_urls.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
             &chain.chain_id,
             &chain.network_info.pub_address_prefix,
         )
@@ -236,15 +356,26 @@ impl<S: StateInterface> CloneTesting<S> {
         let bank = BankKeeper::new().with_remote(remote_channel.clone());
 
         // We update the block_height
-        let block_info = remote_channel
-            .rt
-            .block_on(Node::new_async(remote_channel.channel.clone())._block_info())
-            .unwrap();
+        let block_info = match fixed_height {
+            Some(height) => Node {
+                channel: remote_channel.channel.clone(),
+                rt_handle: Some(rt.handle().clone()),
+            }
+            .block_by_height(height)?,
+            None => remote_channel
+                .rt
+                .block_on(Node::new_async(remote_channel.channel.clone())._block_info())
+                .unwrap(),
+        };
+
+        let stargate = CloneStargate::default();
 
         // Finally we instantiate a new app
         let app = AppBuilder::default()
             .with_wasm(wasm)
             .with_bank(bank)
+            .with_ibc(IbcSimpleModule)
+            .with_stargate(stargate.clone())
             .with_api(MockApiBech32::new(&pub_address_prefix))
             .with_block(block_info)
             .with_remote(remote_channel.clone());
@@ -257,12 +388,99 @@ impl<S: StateInterface> CloneTesting<S> {
             sender: sender.clone(),
             state,
             app,
+            remote: remote_channel,
+            stargate,
+            top_up_impersonated: true,
         })
     }
 
     pub fn storage_analysis(&self) -> StorageAnalyzer {
         StorageAnalyzer::new(&self.app.borrow()).unwrap()
     }
+
+    /// Fetch the remote data used when interacting with the contracts: their info, code, storage and balances.
+    ///
+    /// Used with a recording [`RemoteCache`], this stores the data on disk so that tests using these contracts can run offline.
+    /// Every entry of the storage of the contracts is fetched, as a whole and key by key as the contracts read them.
+    pub fn prewarm(&self, contracts: &[Addr]) -> Result<(), CwEnvError> {
+        let app = self.app.borrow();
+        let querier = app.wrap();
+        let remote = CosmWasm::new_async(self.remote.channel.clone());
+        for contract in contracts {
+            let info = querier.query_wasm_contract_info(contract)?;
+            querier.query_wasm_code_info(info.code_id)?;
+            querier.query_all_balances(contract)?;
+            self.remote.rt.block_on(async {
+                remote._code_data(info.code_id).await?;
+                for (key, _) in remote._all_contract_storage(contract).await? {
+                    remote._contract_raw_state(contract, key).await?;
+                }
+                Ok::<_, DaemonError>(())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Only the storage written locally is dumped, the storage of the contracts on the forked chain is not included.
+///
+/// The previous values of the keys written for the first time locally are fetched from the forked chain in [`StorageDumper::storage_diff`],
+/// so that the diff shows the changes compared to the chain state. Useful to rehearse migrations:
+/// ```ignore
+/// let (_, diff) = chain.storage_diff(|| contract.migrate(&MigrateMsg {}, new_code_id))?;
+/// println!("{diff}");
+/// ```
+impl<S: StateInterface> StorageDumper for CloneTesting<S> {
+    fn dump_contract_storage(&self, address: &Addr) -> Result<Vec<(Vec<u8>, Vec<u8>)>, CwEnvError> {
+        Ok(self.app.borrow().dump_wasm_raw(address))
+    }
+
+    fn storage_diff<T, E: Into<CwEnvError>>(
+        &self,
+        tx: impl FnOnce() -> Result<T, E>,
+    ) -> Result<(T, StorageDiff), CwEnvError> {
+        let mut before = self.storage_dump()?;
+        let result = tx().map_err(Into::into)?;
+        let after = self.storage_dump()?;
+
+        let addresses = self.state.borrow().get_all_addresses()?;
+        for (contract_id, storage) in &after {
+            let Some(address) = addresses.get(contract_id) else {
+                continue;
+            };
+            let local = before.entry(contract_id.clone()).or_default();
+            self.add_remote_storage(address, local, storage);
+        }
+        Ok((result, StorageDiff::new(&before, &after)))
+    }
+}
+
+impl<S: StateInterface> CloneTesting<S> {
+    /// Adds to the `before` dump of a contract the values on the forked chain of the keys present in `after`.
+    /// The storage of the forked contracts is only fetched when read, so the keys written without being read first are missing locally.
+    pub(crate) fn add_remote_storage(
+        &self,
+        address: &Addr,
+        before: &mut Vec<(Vec<u8>, Vec<u8>)>,
+        after: &[(Vec<u8>, Vec<u8>)],
+    ) {
+        let querier = CosmWasm::new_async(self.remote.channel.clone());
+        for (key, _) in after {
+            if before.iter().any(|(k, _)| k == key) {
+                continue;
+            }
+            // Contracts created locally don't exist on the forked chain
+            let remote = self
+                .remote
+                .rt
+                .block_on(querier._contract_raw_state(address, key.clone()))
+                .map(|response| response.data)
+                .unwrap_or_default();
+            if !remote.is_empty() {
+                before.push((key.clone(), remote));
+            }
+        }
+    }
 }
 
 impl<S: StateInterface> ChainState for CloneTesting<S> {
@@ -517,7 +735,7 @@ mod test {
         to_json_binary, Addr, Coin, Deps, DepsMut, Env, MessageInfo, Response, Uint128,
     };
     use cw20::{BalanceResponse, MinterResponse};
-    use cw_orch_core::contract::WasmPath;
+    use cw_orch_core::contract::{interface_traits::ContractCustomTypes, WasmPath};
     use cw_orch_core::environment::QueryHandler;
     use cw_orch_daemon::networks::JUNO_1;
     use cw_orch_mock::cw_multi_test::{Contract as MockContract, ContractWrapper};
@@ -551,6 +769,11 @@ mod test {
             _ => unimplemented!(),
         }
     }
+    impl ContractCustomTypes for MockCw20 {
+        type ExecC = Empty;
+        type QueryC = Empty;
+    }
+
     impl Uploadable for MockCw20 {
         fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
             unimplemented!()
@@ -0,0 +1,141 @@
+//! Impersonation of the accounts of the forked chain.
+//!
+//! Transactions aren't signed in [`CloneTesting`], so any address of the forked chain can send them:
+//! a multisig, a DAO or a contract. This is used to rehearse migrations as the real admin of a contract.
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Display},
+};
+
+use cosmwasm_std::{Addr, Coin, Uint128};
+use cw_orch_core::{
+    environment::{StateInterface, StorageDiff, StorageDumper, TxHandler},
+    CwEnvError,
+};
+use cw_utils::NativeBalance;
+use serde::Serialize;
+
+use crate::{core::AppResponse, CloneTesting};
+
+/// Balance of the gas denom given to impersonated accounts that have less, to pay for the fees of their transactions
+pub const IMPERSONATION_FEE_BALANCE: u128 = 1_000_000_000;
+
+/// Outcome of a migration executed with [`CloneTesting::migrate_as_admin`]
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// Migrated contract
+    pub contract: Addr,
+    /// On-chain admin of the contract, that executed the migration
+    pub admin: Addr,
+    /// Response of the migration transaction
+    pub response: AppResponse,
+    /// Changes made by the migration to the storage of the contract, indexed by its address
+    pub storage_diff: StorageDiff,
+}
+
+impl Display for MigrationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Migrated {} as {}", self.contract, self.admin)?;
+        write!(f, "{}", self.storage_diff)
+    }
+}
+
+impl<S: StateInterface> CloneTesting<S> {
+    /// Returns an environment sending its transactions as `address`, which can be any account of the forked chain, including contracts.
+    /// The account receives [`IMPERSONATION_FEE_BALANCE`] of the gas denom of the chain if its balance is lower,
+    /// unless [`CloneTesting::top_up_impersonated`] is disabled.
+    ///
+    /// To send a single transaction through a contract interface, use [`CallAs::call_as`](cw_orch_core::contract::interface_traits::CallAs::call_as) with the remote address.
+    /// ```no_run
+    /// use cosmwasm_std::Addr;
+    /// use cw_orch_clone_testing::CloneTesting;
+    ///
+    /// # fn main() -> Result<(), cw_orch_core::CwEnvError> {
+    /// let chain = CloneTesting::new(cw_orch_daemon::networks::JUNO_1)?;
+    /// let dao = chain.impersonate(&Addr::unchecked("juno1dao..."))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn impersonate(&self, address: &Addr) -> Result<Self, CwEnvError> {
+        if self.top_up_impersonated {
+            self.top_up_fees(address)?;
+        }
+        let mut chain = self.clone();
+        chain.set_sender(address.clone());
+        Ok(chain)
+    }
+
+    /// Migrates a contract as its on-chain admin, as read in its contract info, and reports the changes made to its storage.
+    /// The contract doesn't need to be registered in the state.
+    ///
+    /// ```ignore
+    /// let new_code_id = chain.upload(&contract)?.uploaded_code_id()?;
+    /// let report = chain.migrate_as_admin(&contract.address()?, &MigrateMsg {}, new_code_id)?;
+    /// println!("{report}");
+    /// ```
+    pub fn migrate_as_admin<M: Serialize + Debug>(
+        &self,
+        contract_address: &Addr,
+        migrate_msg: &M,
+        new_code_id: u64,
+    ) -> Result<MigrationReport, CwEnvError> {
+        let info = self
+            .app
+            .borrow()
+            .wrap()
+            .query_wasm_contract_info(contract_address)?;
+        let admin = info.admin.ok_or_else(|| {
+            CwEnvError::StdErr(format!(
+                "Contract {contract_address} has no admin and can't be migrated"
+            ))
+        })?;
+
+        let mut before = self.dump_contract_storage(contract_address)?;
+        let response =
+            self.impersonate(&admin)?
+                .migrate(migrate_msg, new_code_id, contract_address)?;
+        let after = self.dump_contract_storage(contract_address)?;
+        self.add_remote_storage(contract_address, &mut before, &after);
+
+        let id = contract_address.to_string();
+        let storage_diff = StorageDiff::new(
+            &BTreeMap::from([(id.clone(), before)]),
+            &BTreeMap::from([(id, after)]),
+        );
+
+        Ok(MigrationReport {
+            contract: contract_address.clone(),
+            admin,
+            response,
+            storage_diff,
+        })
+    }
+
+    /// Tops up the gas denom balance of `address` to [`IMPERSONATION_FEE_BALANCE`]
+    fn top_up_fees(&self, address: &Addr) -> Result<(), CwEnvError> {
+        let denom = self.chain.gas_denom.clone();
+        if denom.is_empty() {
+            return Ok(());
+        }
+        let balances = self.app.borrow().wrap().query_all_balances(address)?;
+        let current = balances
+            .iter()
+            .find(|coin| coin.denom == denom)
+            .map(|coin| coin.amount)
+            .unwrap_or_default();
+        let missing = Uint128::new(IMPERSONATION_FEE_BALANCE).saturating_sub(current);
+        if missing.is_zero() {
+            return Ok(());
+        }
+
+        let new_balance = NativeBalance(balances) + NativeBalance(vec![Coin::new(missing, denom)]);
+        self.app
+            .borrow_mut()
+            .init_modules(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, address, new_balance.into_vec())
+            })
+            .map_err(Into::into)
+    }
+}
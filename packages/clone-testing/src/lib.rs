@@ -1,14 +1,20 @@
 //! Integration testing execution environment backed by a [cw-multi-test](cw_multi_test) App.
 //! It has an associated state that stores deployment information for easy retrieval and contract interactions.
 
+pub mod cache;
 mod contract_instance;
 mod core;
+mod impersonation;
 pub mod queriers;
+pub mod stargate;
 mod state;
 
 pub use self::core::CloneTesting;
+pub use cache::{CacheMode, RemoteCache};
 pub use clone_cw_multi_test as cw_multi_test;
 pub use contract_instance::WasmUpload;
+pub use impersonation::{MigrationReport, IMPERSONATION_FEE_BALANCE};
+pub use stargate::CloneStargate;
 pub use state::MockState;
 
 // We define a new structure to reunite the ContractWrapper objects
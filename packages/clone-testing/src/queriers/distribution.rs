@@ -0,0 +1,76 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Coin};
+use cw_orch_core::{
+    environment::{DistributionQuerier, Querier, QuerierGetter, StakingQuerier, StateInterface},
+    CwEnvError,
+};
+use cw_orch_daemon::queriers::Distribution;
+use cw_utils::NativeBalance;
+
+use crate::{core::CloneTestingApp, CloneTesting};
+
+use super::staking::CloneStakingQuerier;
+
+/// Queries the rewards of the local staking module first and falls back to the forked chain
+/// for the delegations that don't exist locally, like [`CloneStakingQuerier`].
+pub struct CloneDistributionQuerier {
+    app: Rc<RefCell<CloneTestingApp>>,
+    staking: CloneStakingQuerier,
+    remote: Distribution,
+}
+
+impl CloneDistributionQuerier {
+    fn new<S: StateInterface>(mock: &CloneTesting<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+            staking: CloneStakingQuerier::new(mock),
+            remote: Distribution {
+                channel: mock.remote.channel.clone(),
+                rt_handle: Some(mock.remote.rt.clone()),
+            },
+        }
+    }
+}
+
+impl Querier for CloneDistributionQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<CloneDistributionQuerier> for CloneTesting<S> {
+    fn querier(&self) -> CloneDistributionQuerier {
+        CloneDistributionQuerier::new(self)
+    }
+}
+
+impl DistributionQuerier for CloneDistributionQuerier {
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        let delegation = self
+            .app
+            .borrow()
+            .wrap()
+            .query_delegation(delegator, validator)?;
+        match delegation {
+            Some(d) => Ok(d.accumulated_rewards),
+            None if self.staking.delegation(delegator, validator)?.is_some() => {
+                Ok(self.remote.delegation_rewards(delegator, validator)?)
+            }
+            None => Ok(vec![]),
+        }
+    }
+
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+        let delegations = self.staking.delegator_delegations(delegator)?;
+        let mut rewards = NativeBalance::default();
+        for delegation in delegations {
+            rewards =
+                rewards + NativeBalance(self.delegation_rewards(delegator, &delegation.validator)?);
+        }
+        rewards.normalize();
+        Ok(rewards.into_vec())
+    }
+}
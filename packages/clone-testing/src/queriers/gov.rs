@@ -0,0 +1,16 @@
+use cw_orch_core::environment::{QuerierGetter, StateInterface};
+use cw_orch_daemon::queriers::Gov;
+
+use crate::CloneTesting;
+
+/// Governance proposals are queried on the forked chain.
+/// Unlike the staking and distribution queriers, there is no local state to query first:
+/// the local app runs a failing gov module, so proposals can't be created in the clone.
+impl<S: StateInterface> QuerierGetter<Gov> for CloneTesting<S> {
+    fn querier(&self) -> Gov {
+        Gov {
+            channel: self.remote.channel.clone(),
+            rt_handle: Some(self.remote.rt.clone()),
+        }
+    }
+}
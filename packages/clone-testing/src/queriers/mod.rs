@@ -7,8 +7,11 @@ use cw_orch_core::{
     CwEnvError,
 };
 pub mod bank;
+pub mod distribution;
 mod env;
+mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
 impl QueryHandler for CloneTesting {
@@ -40,4 +43,7 @@ impl DefaultQueriers for CloneTesting {
     type Bank = bank::CloneBankQuerier;
     type Wasm = wasm::CloneWasmQuerier<MockState>;
     type Node = node::CloneNodeQuerier;
+    type Staking = staking::CloneStakingQuerier;
+    type Distribution = distribution::CloneDistributionQuerier;
+    type Gov = cw_orch_daemon::queriers::Gov;
 }
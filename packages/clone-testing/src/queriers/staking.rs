@@ -0,0 +1,89 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Delegation, Validator};
+use cw_orch_core::{
+    environment::{Querier, QuerierGetter, StakingQuerier, StateInterface},
+    CwEnvError,
+};
+use cw_orch_daemon::queriers::Staking;
+
+use crate::{core::CloneTestingApp, CloneTesting};
+
+/// Queries the local staking module first and falls back to the forked chain
+/// for the validators and delegations that don't exist locally.
+/// The staking state of the forked chain is not cloned, so the local delegations hide the remote ones.
+pub struct CloneStakingQuerier {
+    app: Rc<RefCell<CloneTestingApp>>,
+    remote: Staking,
+}
+
+impl CloneStakingQuerier {
+    pub(crate) fn new<S: StateInterface>(mock: &CloneTesting<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+            remote: Staking {
+                channel: mock.remote.channel.clone(),
+                rt_handle: Some(mock.remote.rt.clone()),
+            },
+        }
+    }
+}
+
+impl Querier for CloneStakingQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<CloneStakingQuerier> for CloneTesting<S> {
+    fn querier(&self) -> CloneStakingQuerier {
+        CloneStakingQuerier::new(self)
+    }
+}
+
+impl StakingQuerier for CloneStakingQuerier {
+    fn bonded_denom(&self) -> Result<String, Self::Error> {
+        Ok(self.app.borrow().wrap().query_bonded_denom()?)
+    }
+
+    fn validator(&self, validator: &str) -> Result<Validator, Self::Error> {
+        match self.app.borrow().wrap().query_validator(validator)? {
+            Some(validator) => Ok(validator),
+            None => Ok(self.remote.validator(validator)?),
+        }
+    }
+
+    fn validators(&self) -> Result<Vec<Validator>, Self::Error> {
+        let mut validators = self.app.borrow().wrap().query_all_validators()?;
+        for remote in self.remote.validators()? {
+            if !validators.iter().any(|v| v.address == remote.address) {
+                validators.push(remote);
+            }
+        }
+        Ok(validators)
+    }
+
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<Delegation>, Self::Error> {
+        let delegation = self
+            .app
+            .borrow()
+            .wrap()
+            .query_delegation(delegator, validator)?;
+        match delegation {
+            Some(d) => Ok(Some(Delegation::new(d.delegator, d.validator, d.amount))),
+            None => Ok(self.remote.delegation(delegator, validator)?),
+        }
+    }
+
+    fn delegator_delegations(&self, delegator: &Addr) -> Result<Vec<Delegation>, Self::Error> {
+        let mut delegations = self.app.borrow().wrap().query_all_delegations(delegator)?;
+        for remote in self.remote.delegator_delegations(delegator)? {
+            if !delegations.iter().any(|d| d.validator == remote.validator) {
+                delegations.push(remote);
+            }
+        }
+        Ok(delegations)
+    }
+}
@@ -4,7 +4,7 @@ use std::{cell::RefCell, rc::Rc};
 use crate::{core::CloneTestingApp, CloneTesting};
 use clone_cw_multi_test::AddressGenerator;
 use clone_cw_multi_test::CosmosRouter;
-use cosmwasm_std::{instantiate2_address, Addr, Api, Checksum, ContractInfoResponse};
+use cosmwasm_std::{instantiate2_address, Addr, Api, Checksum, ContractInfoResponse, StdError};
 use cw_orch_core::{
     contract::interface_traits::{ContractInstance, Uploadable},
     environment::{Querier, QuerierGetter, StateInterface, WasmQuerier},
@@ -45,7 +45,15 @@ impl<S: StateInterface> WasmQuerier for CloneWasmQuerier<S> {
 
     /// Returns the code_info structure of the provided contract
     fn contract_info(&self, address: &Addr) -> Result<ContractInfoResponse, CwEnvError> {
-        let info = self.app.borrow().wrap().query_wasm_contract_info(address)?;
+        let app = self.app.borrow();
+        // The querier only forwards the message of the errors, the contract data tells whether the contract exists
+        if let Err(e) = app.contract_data(address) {
+            return Err(match e.downcast_ref::<StdError>() {
+                Some(StdError::NotFound { .. }) => CwEnvError::AddrNotInStore(address.to_string()),
+                _ => e.into(),
+            });
+        }
+        let info = app.wrap().query_wasm_contract_info(address)?;
         Ok(info)
     }
 
@@ -122,6 +130,11 @@ impl<S: StateInterface> WasmQuerier for CloneWasmQuerier<S> {
             .addr_humanize(&canonical_addr)?
             .to_string())
     }
+
+    /// Only missing contracts are detected, the app doesn't tell missing codes apart from other errors
+    fn is_not_found(&self, error: &CwEnvError) -> bool {
+        matches!(error, CwEnvError::AddrNotInStore(_))
+    }
 }
 
 impl<S> AddressGenerator for CloneWasmQuerier<S> {}
@@ -0,0 +1,133 @@
+//! Registry of Stargate/Any message handlers for the [`CloneTesting`] environment.
+//!
+//! The forked chain modules don't run locally, so `CosmosMsg::Stargate`/`CosmosMsg::Any` messages and gRPC queries
+//! are dispatched to the handlers registered on the [`CloneStargate`] module of the app, which emulate the chain modules that process them.
+//!
+//! ## Example
+//! ```no_run
+//! use cosmwasm_std::coins;
+//! use cw_orch_clone_testing::{cw_multi_test::{BankSudo, SudoMsg}, CloneTesting};
+//!
+//! let chain = CloneTesting::new(cw_orch_daemon::networks::OSMOSIS_1).unwrap();
+//! // Emulate the tokenfactory mint message
+//! chain.register_stargate_exec("/osmosis.tokenfactory.v1beta1.MsgMint", |ctx, sender, _value| {
+//!     ctx.sudo(SudoMsg::Bank(BankSudo::Mint {
+//!         to_address: sender.to_string(),
+//!         amount: coins(100, format!("factory/{sender}/token")),
+//!     }))
+//! });
+//! ```
+use clone_cw_multi_test::{AnyResult, AppResponse};
+use cosmwasm_std::{Addr, Binary, CosmosMsg};
+use cw_orch_core::{
+    environment::{StateInterface, TxHandler},
+    CwEnvError,
+};
+
+use crate::CloneTesting;
+
+cw_orch_mock::stargate_module!(
+    clone_cw_multi_test,
+    /// Stargate module of the [`CloneTesting`] app, dispatching messages and queries to the registered handlers.
+    /// Messages and queries without handler fail.
+    CloneStargate
+);
+
+impl<S: StateInterface> CloneTesting<S> {
+    /// Registers the handler of the Stargate/Any messages with the given type URL
+    pub fn register_stargate_exec(
+        &self,
+        type_url: impl Into<String>,
+        handler: impl Fn(&mut StargateExecContext, Addr, Binary) -> AnyResult<AppResponse> + 'static,
+    ) {
+        self.stargate.register_exec(type_url, handler)
+    }
+
+    /// Registers the handler of the Stargate/gRPC queries on the given path
+    pub fn register_stargate_query(
+        &self,
+        path: impl Into<String>,
+        handler: impl Fn(&StargateQueryContext, Binary) -> AnyResult<Binary> + 'static,
+    ) {
+        self.stargate.register_query(path, handler)
+    }
+}
+
+impl<S: StateInterface> cw_orch_traits::Stargate for CloneTesting<S> {
+    /// Executes the messages with the registered Stargate handlers, in a single transaction
+    fn commit_any(
+        &self,
+        msgs: Vec<prost_types::Any>,
+        _memo: Option<&str>,
+    ) -> Result<Self::Response, Self::Error> {
+        #[allow(deprecated)]
+        let msgs: Vec<CosmosMsg> = msgs
+            .into_iter()
+            .map(|any| CosmosMsg::Stargate {
+                type_url: any.type_url,
+                value: any.value.into(),
+            })
+            .collect();
+
+        let responses = self
+            .app
+            .borrow_mut()
+            .execute_multi(self.sender_addr(), msgs)
+            .map_err(CwEnvError::from)?;
+
+        Ok(crate::core::AppResponse {
+            events: responses
+                .iter()
+                .flat_map(|resp| resp.events.clone())
+                .collect(),
+            data: responses.into_iter().rev().find_map(|resp| resp.data),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use clone_cw_multi_test::{BankSudo, SudoMsg};
+    use cosmwasm_std::{coins, to_json_binary, Empty, QueryRequest};
+    use cw_orch_daemon::networks::JUNO_1;
+    use cw_orch_traits::Stargate;
+
+    use super::*;
+
+    #[test]
+    fn registered_handlers() -> anyhow::Result<()> {
+        let chain = CloneTesting::new(JUNO_1)?;
+        let sender = chain.sender_addr();
+
+        let any = prost_types::Any {
+            type_url: "/test.MsgMint".to_string(),
+            value: vec![],
+        };
+        assert!(chain.commit_any(vec![any.clone()], None).is_err());
+
+        chain.register_stargate_exec("/test.MsgMint", |ctx, sender, _value| {
+            ctx.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: sender.to_string(),
+                amount: coins(10, "utest"),
+            }))
+        });
+        chain.register_stargate_query("/test.Query/Answer", |_ctx, _data| {
+            Ok(to_json_binary(&42u64)?)
+        });
+
+        chain.commit_any(vec![any], None)?;
+        assert_eq!(chain.query_balance(&sender, "utest")?.u128(), 10);
+
+        #[allow(deprecated)]
+        let answer: u64 = chain
+            .app
+            .borrow()
+            .wrap()
+            .query(&QueryRequest::<Empty>::Stargate {
+                path: "/test.Query/Answer".to_string(),
+                data: Binary::default(),
+            })?;
+        assert_eq!(answer, 42);
+        Ok(())
+    }
+}
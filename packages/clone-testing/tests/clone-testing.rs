@@ -14,9 +14,9 @@ use cw_orch::prelude::CwOrchMigrate;
 use cw_orch::prelude::CwOrchUpload;
 use cw_orch::prelude::Uploadable;
 use cw_orch::prelude::*;
-use cw_orch_clone_testing::CloneTesting;
+use cw_orch_clone_testing::{CloneTesting, IMPERSONATION_FEE_BALANCE};
 
-use cosmwasm_std::Empty;
+use cosmwasm_std::{Binary, DepsMut, Empty, Response, StdResult};
 
 /// For those Who don't know, CAVERN PROTOCOL was a money market
 #[test]
@@ -186,3 +186,68 @@ fn query_contract_info() -> cw_orch::anyhow::Result<()> {
     app.wasm_querier().contract_info(&market.address()?)?;
     Ok(())
 }
+
+#[cw_orch::interface(Empty, Empty, Empty, Empty)]
+pub struct Migrator;
+
+impl Uploadable for Migrator<CloneTesting> {
+    fn wrapper() -> Box<dyn MockContract<Empty, Empty>> {
+        Box::new(
+            ContractWrapper::new_with_empty(
+                |_, _, _, _: Empty| StdResult::Ok(Response::new()),
+                |_, _, _, _: Empty| StdResult::Ok(Response::new()),
+                |_, _, _: Empty| StdResult::Ok(Binary::default()),
+            )
+            .with_migrate(|deps: DepsMut, _, _: Empty| {
+                deps.storage.set(b"migrated", b"true");
+                StdResult::Ok(Response::new())
+            }),
+        )
+    }
+}
+
+#[test]
+fn migrate_as_admin_unregistered_contract() -> cw_orch::anyhow::Result<()> {
+    let app = CloneTesting::new(PHOENIX_1)?;
+    let migrator = Migrator::new("migrator", app.clone());
+    migrator.upload()?;
+
+    // The market isn't registered in the state
+    let market_addr = Addr::unchecked(MARKET_ADDR);
+    let admin = app
+        .wasm_querier()
+        .contract_info(&market_addr)?
+        .admin
+        .unwrap();
+    let report = app.migrate_as_admin(&market_addr, &Empty {}, migrator.code_id()?)?;
+
+    assert_eq!(report.admin.as_str(), admin);
+    let changes = report.storage_diff.contract(MARKET_ADDR);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].key(), "migrated");
+    assert_eq!(
+        app.wasm_querier().contract_info(&market_addr)?.code_id,
+        migrator.code_id()?
+    );
+    Ok(())
+}
+
+#[test]
+fn impersonate_tops_up_fees() -> cw_orch::anyhow::Result<()> {
+    let mut app = CloneTesting::new(PHOENIX_1)?;
+    let gas_denom = app.chain.gas_denom.clone();
+
+    let account = app.init_account();
+    let impersonated = app.impersonate(&account)?;
+    assert_eq!(impersonated.sender_addr(), account);
+    assert_eq!(
+        app.query_balance(&account, &gas_denom)?.u128(),
+        IMPERSONATION_FEE_BALANCE
+    );
+
+    app.top_up_impersonated = false;
+    let account = app.init_account();
+    app.impersonate(&account)?;
+    assert!(app.query_balance(&account, &gas_denom)?.is_zero());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use cosmwasm_std::{coin, CosmosMsg, IbcMsg, IbcOrder, IbcTimeout};
+use cw_orch::daemon::networks::{JUNO_1, OSMOSIS_1};
+use cw_orch::prelude::*;
+use cw_orch_clone_testing::{
+    cw_multi_test::{
+        ibc::relayer::{create_channel, create_connection, relay_packet, RelayingResult},
+        Executor,
+    },
+    CloneTesting,
+};
+
+#[test]
+fn ibc_transfer_between_forks() -> anyhow::Result<()> {
+    let juno = CloneTesting::new(JUNO_1)?;
+    let osmosis = CloneTesting::new(OSMOSIS_1)?;
+
+    // The IBC module of the clone apps opens connections and channels locally
+    let (connection_id, _) =
+        create_connection(&mut juno.app.borrow_mut(), &mut osmosis.app.borrow_mut())?;
+    let channel = create_channel(
+        &mut juno.app.borrow_mut(),
+        &mut osmosis.app.borrow_mut(),
+        connection_id,
+        "transfer".to_string(),
+        "transfer".to_string(),
+        "ics20-1".to_string(),
+        IbcOrder::Unordered,
+    )?;
+
+    let receiver = osmosis.init_account();
+    juno.add_balance(&juno.sender_addr(), vec![coin(100, "ujuno")])?;
+    juno.app.borrow_mut().execute(
+        juno.sender_addr(),
+        CosmosMsg::Ibc(IbcMsg::Transfer {
+            channel_id: channel.src_channel.clone(),
+            to_address: receiver.to_string(),
+            amount: coin(100, "ujuno"),
+            timeout: IbcTimeout::with_timestamp(juno.block_info()?.time.plus_seconds(600)),
+            memo: None,
+        }),
+    )?;
+
+    let relayed = relay_packet(
+        &mut juno.app.borrow_mut(),
+        &mut osmosis.app.borrow_mut(),
+        "transfer".to_string(),
+        channel.src_channel,
+        1,
+    )?;
+    assert!(matches!(
+        relayed.result,
+        RelayingResult::Acknowledgement { .. }
+    ));
+
+    let received = osmosis.query_all_balances(&receiver)?;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].amount.u128(), 100);
+    assert!(received[0].denom.starts_with("ibc/"));
+    assert_eq!(juno.query_balance(&juno.sender_addr(), "ujuno")?.u128(), 0);
+    Ok(())
+}
@@ -3,7 +3,8 @@ use super::interface_traits::Uploadable;
 use crate::{
     env::CoreEnvVars,
     environment::{
-        AsyncWasmQuerier, ChainState, IndexResponse, StateInterface, TxHandler, TxResponse,
+        AsyncWasmQuerier, ChainState, DeploymentAction, DeploymentRecord, IndexResponse,
+        StateInterface, TxHandler, TxResponse,
     },
     error::CwEnvError,
     log::{contract_target, transaction_target},
@@ -11,7 +12,7 @@ use crate::{
 
 use crate::environment::AccessConfig;
 use crate::environment::QueryHandler;
-use cosmwasm_std::{Addr, Binary, Coin};
+use cosmwasm_std::{Addr, Binary, Checksum, Coin};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
 
@@ -97,10 +98,46 @@ impl<Chain: ChainState> Contract<Chain> {
     pub fn remove_code_id(&self) {
         self.chain.state().remove_code_id(&self.id)
     }
+
+    /// Returns the deployment history of the contract, oldest first
+    pub fn deployment_history(&self) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        self.chain.state().get_deployment_history(&self.id)
+    }
+
+    /// Records a deployment action in the deployment history of the contract
+    fn record_deployment(&self, record: DeploymentRecord) -> Result<(), CwEnvError> {
+        self.chain.state().record_deployment(&self.id, record)
+    }
+
+    /// Looks for the checksum of an uploaded code id in the deployment history of the contract
+    pub(crate) fn uploaded_checksum(&self, code_id: u64) -> Option<Checksum> {
+        self.deployment_history()
+            .ok()?
+            .into_iter()
+            .rev()
+            .find(|record| {
+                record.action == DeploymentAction::Upload && record.code_id == Some(code_id)
+            })
+            .and_then(|record| record.checksum)
+    }
 }
 
 /// Expose chain and state function to call them on the contract
 impl<Chain: TxHandler> Contract<Chain> {
+    /// Creates a deployment record with the information contained in the transaction response
+    fn deployment_record(
+        &self,
+        action: DeploymentAction,
+        resp: &TxResponse<Chain>,
+    ) -> DeploymentRecord {
+        DeploymentRecord {
+            tx_hash: resp.tx_hash(),
+            timestamp: resp.tx_timestamp(),
+            deployer: Some(self.chain.sender_addr()),
+            ..DeploymentRecord::new(action)
+        }
+    }
+
     // Chain interfaces
 
     /// Upload a contract given its source and specify the permissions for instantiating
@@ -121,6 +158,11 @@ impl<Chain: TxHandler> Contract<Chain> {
             .map_err(Into::into)?;
         let code_id = resp.uploaded_code_id()?;
         self.set_code_id(code_id);
+        self.record_deployment(DeploymentRecord {
+            code_id: Some(code_id),
+            checksum: resp.uploaded_code_checksum(),
+            ..self.deployment_record(DeploymentAction::Upload, &resp)
+        })?;
         log::info!(
             target: &contract_target(),
             "[{}][Uploaded] code_id {}",
@@ -201,13 +243,20 @@ impl<Chain: TxHandler> Contract<Chain> {
             log_serialize_message(msg)?
         );
 
+        let code_id = self.code_id()?;
         let resp = self
             .chain
-            .instantiate(self.code_id()?, msg, Some(&self.id), admin, coins)
+            .instantiate(code_id, msg, Some(&self.id), admin, coins)
             .map_err(Into::into)?;
         let contract_address = resp.instantiated_contract_address()?;
 
         self.set_address(&contract_address);
+        self.record_deployment(DeploymentRecord {
+            code_id: Some(code_id),
+            address: Some(contract_address.clone()),
+            checksum: self.uploaded_checksum(code_id),
+            ..self.deployment_record(DeploymentAction::Instantiate, &resp)
+        })?;
 
         log::info!(
             target: &&contract_target(),
@@ -246,13 +295,20 @@ impl<Chain: TxHandler> Contract<Chain> {
             log_serialize_message(msg)?
         );
 
+        let code_id = self.code_id()?;
         let resp = self
             .chain
-            .instantiate2(self.code_id()?, msg, Some(&self.id), admin, coins, salt)
+            .instantiate2(code_id, msg, Some(&self.id), admin, coins, salt)
             .map_err(Into::into)?;
         let contract_address = resp.instantiated_contract_address()?;
 
         self.set_address(&contract_address);
+        self.record_deployment(DeploymentRecord {
+            code_id: Some(code_id),
+            address: Some(contract_address.clone()),
+            checksum: self.uploaded_checksum(code_id),
+            ..self.deployment_record(DeploymentAction::Instantiate, &resp)
+        })?;
 
         log::info!(
             target: &&contract_target(),
@@ -295,6 +351,12 @@ impl<Chain: TxHandler> Contract<Chain> {
             .chain
             .migrate(migrate_msg, new_code_id, &self.address()?)
             .map_err(Into::into)?;
+        self.record_deployment(DeploymentRecord {
+            code_id: Some(new_code_id),
+            address: Some(self.address()?),
+            checksum: self.uploaded_checksum(new_code_id),
+            ..self.deployment_record(DeploymentAction::Migrate, &resp)
+        })?;
 
         log::info!(
             target: &contract_target(),
@@ -386,17 +448,17 @@ fn log_serialize_message<E: Serialize + Debug>(msg: &E) -> Result<String, CwEnvE
     }
 }
 
-/// Helper to get the name of a struct
-fn get_struct_name<E: Serialize + Debug>(msg: &E) -> Result<String, CwEnvError> {
-    let serialized = serde_json::to_value(msg)?;
-    let value = serialized
-        .as_object()
-        .ok_or("Can't get struct name of non object")
-        .unwrap()
-        .into_iter()
-        .next()
-        .ok_or("Can't get struct name of non object")
-        .unwrap();
-
-    Ok(value.0.clone())
+/// Helper to get the name of a struct, i.e. the message variant for `{"variant": {..}}` or `"variant"`
+pub(crate) fn get_struct_name<E: Serialize + Debug>(msg: &E) -> Result<String, CwEnvError> {
+    match serde_json::to_value(msg)? {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .next()
+            .map(|(name, _)| name)
+            .ok_or_else(|| CwEnvError::StdErr("Can't get struct name of empty object".into())),
+        serde_json::Value::String(variant) => Ok(variant),
+        _ => Err(CwEnvError::StdErr(
+            "Can't get struct name of non object".into(),
+        )),
+    }
 }
@@ -1,10 +1,14 @@
 //! Introduces the Deploy trait only
+use std::collections::HashMap;
 use std::error::Error;
 
+use cosmwasm_std::{Addr, Checksum};
+
 use crate::environment::CwEnv;
 use crate::CwEnvError;
 
-use super::interface_traits::ContractInstance;
+use super::drift::DriftReport;
+use super::interface_traits::{ContractInstance, VerifiableContract};
 
 /// Indicates the ability to deploy an application to a mock chain.
 ///
@@ -72,3 +76,40 @@ pub trait Deploy<Chain: CwEnv>: Sized {
     /// In order to leverage the deployed state, don't forget to call `Self::set_contracts_state` after loading the contract objects
     fn load_from(chain: Chain) -> Result<Self, Self::Error>;
 }
+
+/// Verification of a deployment against the chain, available on every [`Deploy`] implementation.
+///
+/// ## Example:
+/// ```ignore
+/// let mut app = MyApplication::load_from(chain)?;
+/// let local_checksums = HashMap::from([(app.token.id(), app.token.local_checksum()?)]);
+/// let report = app.verify_deployment(&local_checksums, Some(&admin))?;
+/// assert!(report.is_up_to_date());
+/// ```
+pub trait VerifyDeployment<Chain: CwEnv>: Deploy<Chain> {
+    /// Compares the state of every contract returned by [`Deploy::get_contracts_mut`] with the chain.
+    /// Reports contracts not migrated to their latest code, missing codes or instances,
+    /// and contracts that were migrated or whose admin was changed outside of this deployment.
+    ///
+    /// Contracts whose local WASM file differs from the deployed code are reported when the checksum of their file is in `local_checksums`,
+    /// indexed by contract id. It is returned by [`ConditionalUpload::local_checksum`](super::interface_traits::ConditionalUpload::local_checksum).
+    ///
+    /// The admin of the contracts is only checked when an `expected_admin` is provided.
+    fn verify_deployment(
+        &mut self,
+        local_checksums: &HashMap<String, Checksum>,
+        expected_admin: Option<&Addr>,
+    ) -> Result<DriftReport, Self::Error> {
+        let contracts = self
+            .get_contracts_mut()
+            .into_iter()
+            .map(|contract| {
+                let local_checksum = local_checksums.get(&contract.id()).cloned();
+                contract.check_drift(local_checksum, expected_admin)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(DriftReport { contracts })
+    }
+}
+
+impl<Chain: CwEnv, T: Deploy<Chain>> VerifyDeployment<Chain> for T {}
@@ -0,0 +1,260 @@
+//! Detection of drift between the local deployment state and the contracts on-chain
+use cosmwasm_std::{Addr, Checksum};
+
+use crate::{
+    environment::{CwEnv, Environment, WasmQuerier},
+    CwEnvError,
+};
+
+use super::interface_traits::ContractInstance;
+
+/// Difference found between the local state of a contract and its on-chain state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftIssue {
+    /// No code id is registered for the contract in the state
+    CodeIdMissing,
+    /// The code id registered in the state doesn't exist on-chain
+    CodeNotFound {
+        /// Code id registered in the state
+        code_id: u64,
+    },
+    /// No contract exists on-chain at the address registered in the state
+    ContractNotFound {
+        /// Address registered in the state
+        address: Addr,
+    },
+    /// The local wasm file differs from the code registered in the state
+    StaleCode {
+        /// Checksum of the local wasm file
+        local: Checksum,
+        /// Checksum of the code registered in the state
+        on_chain: Checksum,
+    },
+    /// The on-chain code differs from the code that was recorded when uploading the contract
+    ChecksumMismatch {
+        /// Checksum recorded in the deployment history
+        recorded: Checksum,
+        /// Checksum of the code on-chain
+        on_chain: Checksum,
+    },
+    /// The contract is running a code uploaded before the one registered in the state,
+    /// the latest code was uploaded but the contract was not migrated to it
+    NotMigrated {
+        /// Code id registered in the state
+        latest: u64,
+        /// Code id the contract is running on-chain
+        running: u64,
+    },
+    /// The contract is running a code uploaded after the one registered in the state,
+    /// it was migrated outside of this deployment
+    CodeIdMismatch {
+        /// Code id registered in the state
+        expected: u64,
+        /// Code id the contract is running on-chain
+        running: u64,
+    },
+    /// The contract admin is not the expected one
+    AdminMismatch {
+        /// Expected admin of the contract
+        expected: Option<Addr>,
+        /// Admin of the contract on-chain
+        actual: Option<Addr>,
+    },
+}
+
+impl DriftIssue {
+    /// Returns whether the issue indicates that the local code was not deployed yet
+    pub fn is_stale(&self) -> bool {
+        matches!(
+            self,
+            DriftIssue::StaleCode { .. } | DriftIssue::NotMigrated { .. }
+        )
+    }
+
+    /// Returns whether the issue indicates that a code or contract can't be found
+    pub fn is_missing(&self) -> bool {
+        matches!(
+            self,
+            DriftIssue::CodeIdMissing
+                | DriftIssue::CodeNotFound { .. }
+                | DriftIssue::ContractNotFound { .. }
+        )
+    }
+
+    /// Returns whether the issue indicates that the contract was changed outside of this deployment
+    pub fn is_tampered(&self) -> bool {
+        matches!(
+            self,
+            DriftIssue::ChecksumMismatch { .. }
+                | DriftIssue::CodeIdMismatch { .. }
+                | DriftIssue::AdminMismatch { .. }
+        )
+    }
+}
+
+/// Comparison of the local state of a single contract with its on-chain state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractDrift {
+    /// Id of the contract
+    pub contract_id: String,
+    /// Code id registered in the state
+    pub code_id: Option<u64>,
+    /// Address registered in the state, `None` if the contract was not instantiated
+    pub address: Option<Addr>,
+    /// Checksum of the local wasm file, if it was provided
+    pub local_checksum: Option<Checksum>,
+    /// Checksum of the code registered in the state, as stored on-chain
+    pub on_chain_checksum: Option<Checksum>,
+    /// Code id the contract is running on-chain
+    pub running_code_id: Option<u64>,
+    /// Admin of the contract on-chain
+    pub admin: Option<Addr>,
+    /// Differences found between the local and the on-chain state
+    pub issues: Vec<DriftIssue>,
+}
+
+impl ContractDrift {
+    /// Compares the local state of `contract` with its on-chain state.
+    ///
+    /// `local_checksum` is compared with the checksum of the code registered in the state to detect stale code.
+    /// The admin of the contract is only checked when an `expected_admin` is provided.
+    ///
+    /// Codes and contracts that can't be found, as told by [`WasmQuerier::is_not_found`], are reported as issues.
+    /// Other query errors are returned.
+    pub fn check<Chain: CwEnv>(
+        contract: &(impl ContractInstance<Chain> + ?Sized),
+        local_checksum: Option<Checksum>,
+        expected_admin: Option<&Addr>,
+    ) -> Result<Self, CwEnvError> {
+        let wasm_querier = contract.environment().wasm_querier();
+        let mut drift = ContractDrift {
+            contract_id: contract.id(),
+            code_id: contract.code_id().ok(),
+            address: contract.address().ok(),
+            local_checksum,
+            on_chain_checksum: None,
+            running_code_id: None,
+            admin: None,
+            issues: vec![],
+        };
+
+        match drift.code_id {
+            None => drift.issues.push(DriftIssue::CodeIdMissing),
+            Some(code_id) => match wasm_querier.code_id_hash(code_id) {
+                Ok(on_chain) => {
+                    drift.on_chain_checksum = Some(on_chain);
+                    if let Some(local) = local_checksum.filter(|local| *local != on_chain) {
+                        drift.issues.push(DriftIssue::StaleCode { local, on_chain });
+                    }
+                    if let Some(recorded) = contract
+                        .as_instance()
+                        .uploaded_checksum(code_id)
+                        .filter(|recorded| *recorded != on_chain)
+                    {
+                        drift
+                            .issues
+                            .push(DriftIssue::ChecksumMismatch { recorded, on_chain });
+                    }
+                }
+                Err(e) if wasm_querier.is_not_found(&e) => {
+                    drift.issues.push(DriftIssue::CodeNotFound { code_id })
+                }
+                Err(e) => return Err(e.into()),
+            },
+        }
+
+        if let Some(address) = drift.address.clone() {
+            match wasm_querier.contract_info(&address) {
+                Ok(info) => {
+                    drift.running_code_id = Some(info.code_id);
+                    drift.admin = info.admin;
+                    match drift.code_id {
+                        // Code ids are incremental, so the contract is running an older code
+                        Some(latest) if info.code_id < latest => {
+                            drift.issues.push(DriftIssue::NotMigrated {
+                                latest,
+                                running: info.code_id,
+                            })
+                        }
+                        Some(expected) if info.code_id > expected => {
+                            drift.issues.push(DriftIssue::CodeIdMismatch {
+                                expected,
+                                running: info.code_id,
+                            })
+                        }
+                        _ => {}
+                    }
+                    if let Some(expected) = expected_admin {
+                        if drift.admin.as_ref() != Some(expected) {
+                            drift.issues.push(DriftIssue::AdminMismatch {
+                                expected: Some(expected.clone()),
+                                actual: drift.admin.clone(),
+                            });
+                        }
+                    }
+                }
+                Err(e) if wasm_querier.is_not_found(&e) => {
+                    drift.issues.push(DriftIssue::ContractNotFound { address })
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(drift)
+    }
+
+    /// Returns whether the contract matches its on-chain state
+    pub fn is_up_to_date(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns whether the local code differs from the deployed code
+    pub fn is_stale(&self) -> bool {
+        self.issues.iter().any(DriftIssue::is_stale)
+    }
+
+    /// Returns whether the code or the contract can't be found on-chain
+    pub fn is_missing(&self) -> bool {
+        self.issues.iter().any(DriftIssue::is_missing)
+    }
+
+    /// Returns whether the contract was changed outside of this deployment
+    pub fn is_tampered(&self) -> bool {
+        self.issues.iter().any(DriftIssue::is_tampered)
+    }
+}
+
+/// Comparison of the local state of all the contracts of a deployment with their on-chain state.
+/// Returned by [`VerifyDeployment::verify_deployment`](super::VerifyDeployment::verify_deployment).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DriftReport {
+    /// Drift of every contract of the deployment
+    pub contracts: Vec<ContractDrift>,
+}
+
+impl DriftReport {
+    /// Returns whether all the contracts match their on-chain state
+    pub fn is_up_to_date(&self) -> bool {
+        self.contracts.iter().all(ContractDrift::is_up_to_date)
+    }
+
+    /// Contracts whose local code differs from the deployed code
+    pub fn stale(&self) -> impl Iterator<Item = &ContractDrift> {
+        self.contracts.iter().filter(|c| c.is_stale())
+    }
+
+    /// Contracts whose code or instance can't be found on-chain
+    pub fn missing(&self) -> impl Iterator<Item = &ContractDrift> {
+        self.contracts.iter().filter(|c| c.is_missing())
+    }
+
+    /// Contracts that were changed outside of this deployment
+    pub fn tampered(&self) -> impl Iterator<Item = &ContractDrift> {
+        self.contracts.iter().filter(|c| c.is_tampered())
+    }
+
+    /// Returns the drift of the contract with the given id
+    pub fn get(&self, contract_id: &str) -> Option<&ContractDrift> {
+        self.contracts.iter().find(|c| c.contract_id == contract_id)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Contract, WasmPath};
+use super::{drift::ContractDrift, Contract, WasmPath};
 use crate::environment::AccessConfig;
 use crate::{
     environment::{
@@ -8,7 +8,7 @@ use crate::{
     error::CwEnvError,
     log::contract_target,
 };
-use cosmwasm_std::{Addr, Binary, Coin, Empty};
+use cosmwasm_std::{Addr, Binary, Checksum, Coin, CustomMsg, CustomQuery, Empty};
 use cw_multi_test::Contract as MockContract;
 use cw_storage_plus::{Item, Map, PrimaryKey};
 use serde::{de::DeserializeOwned, Serialize};
@@ -241,11 +241,30 @@ pub trait CwOrchMigrate<Chain: TxHandler>: MigratableContract + ContractInstance
 
 impl<T: MigratableContract + ContractInstance<Chain>, Chain: TxHandler> CwOrchMigrate<Chain> for T {}
 
+/// Custom message and query types of a contract.
+/// Implemented by the `interface` macro, the types are [`Empty`] unless provided with `custom = (CustomMsg, CustomQuery)`.
+///
+/// Contracts that implement [`Uploadable`] without the `interface` macro need to implement it manually:
+/// ```ignore
+/// impl ContractCustomTypes for MyContract {
+///     type ExecC = Empty;
+///     type QueryC = Empty;
+/// }
+/// ```
+pub trait ContractCustomTypes {
+    /// Custom message type of the contract responses
+    type ExecC: CustomMsg + 'static;
+    /// Custom query type of the contract
+    type QueryC: CustomQuery + 'static;
+}
+
 /// Trait to implement on the contract to enable it to be uploaded
 ///
 /// Should return [`WasmPath`](crate::contract::interface_traits::WasmPath) for `Chain = Daemon`
 /// and [`Box<&dyn Contract>`] for `Chain = Mock`
-pub trait Uploadable {
+///
+/// Requires [`ContractCustomTypes`], which is implemented by the `interface` macro.
+pub trait Uploadable: ContractCustomTypes {
     /// Return an object that can be used to upload the contract to a WASM-supported environment.
     fn wasm(_chain: &ChainInfoOwned) -> WasmPath {
         unimplemented!("no wasm file provided for this contract")
@@ -255,6 +274,12 @@ pub trait Uploadable {
     fn wrapper() -> Box<dyn MockContract<Empty, Empty>> {
         unimplemented!("no wrapper function implemented for this contract")
     }
+
+    /// Return the wrapper object for contracts with custom messages and queries (see [`ContractCustomTypes`]).
+    /// Used by mock environments with the same custom types, instead of [`Uploadable::wrapper`].
+    fn custom_wrapper() -> Box<dyn MockContract<Self::ExecC, Self::QueryC>> {
+        unimplemented!("no custom wrapper function implemented for this contract")
+    }
 }
 
 /// Trait that indicates that the contract can be uploaded.
@@ -337,10 +362,46 @@ pub trait ConditionalUpload<Chain: CwEnv>: CwOrchUpload<Chain> {
             .map_err(Into::into)?;
         Ok(latest_uploaded_code_id == info.code_id)
     }
+
+    /// Returns the checksum of the local WASM file of this contract
+    fn local_checksum(&self) -> Result<Checksum, CwEnvError> {
+        self.environment().wasm_querier().local_hash(self)
+    }
+
+    /// Compares the local state of this contract with its on-chain state.
+    /// Unlike [`ConditionalUpload::is_running_latest`], reports every difference found.
+    fn drift(&self, expected_admin: Option<&Addr>) -> Result<ContractDrift, CwEnvError> {
+        ContractDrift::check(self, Some(self.local_checksum()?), expected_admin)
+    }
 }
 
 impl<T, Chain: CwEnv> ConditionalUpload<Chain> for T where T: CwOrchUpload<Chain> {}
 
+/// Object-safe comparison of a contract with its on-chain state, used to verify all the contracts of a deployment.
+/// Implemented for every contract instance, including the ones returned by [`Deploy::get_contracts_mut`](super::Deploy::get_contracts_mut).
+pub trait VerifiableContract<Chain: CwEnv> {
+    /// Compares the local state of this contract with its on-chain state.
+    /// `local_checksum` is the checksum of the local WASM file of the contract, if it is known.
+    fn check_drift(
+        &self,
+        local_checksum: Option<Checksum>,
+        expected_admin: Option<&Addr>,
+    ) -> Result<ContractDrift, CwEnvError>;
+}
+
+impl<T, Chain: CwEnv> VerifiableContract<Chain> for T
+where
+    T: ContractInstance<Chain> + ?Sized,
+{
+    fn check_drift(
+        &self,
+        local_checksum: Option<Checksum>,
+        expected_admin: Option<&Addr>,
+    ) -> Result<ContractDrift, CwEnvError> {
+        ContractDrift::check(self, local_checksum, expected_admin)
+    }
+}
+
 /// Helper methods for conditional migration of a contract.
 pub trait ConditionalMigrate<Chain: CwEnv>:
     CwOrchMigrate<Chain> + ConditionalUpload<Chain>
@@ -1,10 +1,15 @@
 mod contract_instance;
 mod deploy;
+mod drift;
 pub mod interface_traits;
 mod paths;
+mod plan;
 
+pub(crate) use contract_instance::get_struct_name;
 pub use contract_instance::Contract;
-pub use deploy::Deploy;
+pub use deploy::{Deploy, VerifyDeployment};
+pub use drift::{ContractDrift, DriftIssue, DriftReport};
+pub use plan::{ActionKind, ContractStep, DeploymentPlan, PlanContext, PlanSummary, PlannedAction};
 
 pub use paths::from_workspace as artifacts_dir_from_workspace;
 pub use paths::{ArtifactsDir, WasmPath};
@@ -0,0 +1,302 @@
+//! Declarative deployment plans
+//!
+//! A [`DeploymentPlan`] lists the contracts of a deployment together with their dependencies.
+//! The plan orders the steps so that every contract is deployed after its dependencies
+//! and skips the steps that are already registered in the state, which allows resuming a half-finished deployment.
+//!
+//! ## Example
+//! ```ignore
+//! use cw_orch::prelude::*;
+//! use cw_orch::contract::{ContractStep, DeploymentPlan};
+//!
+//! let plan = DeploymentPlan::new(chain.clone())
+//!     .step(ContractStep::instantiate(&minter, |ctx| {
+//!         Ok(minter::InstantiateMsg {
+//!             token: ctx.address("my-token")?.to_string(),
+//!         })
+//!     }).depends_on("my-token"))
+//!     .step(ContractStep::instantiate(&token, |_| Ok(token_init_msg())));
+//!
+//! // Prints the actions that would be executed
+//! println!("{}", plan.dry_run()?);
+//! plan.execute()?;
+//! ```
+use std::fmt::{self, Display};
+
+use cosmwasm_std::{Addr, Coin};
+
+use crate::{
+    environment::{ChainState, CwEnv, StateInterface},
+    log::contract_target,
+    CwEnvError,
+};
+
+use super::interface_traits::{ContractInstance, CwOrchInstantiate, CwOrchUpload};
+
+type UploadFn = Box<dyn Fn() -> Result<(), CwEnvError>>;
+type InstantiateFn<Chain> =
+    Box<dyn Fn(&PlanContext<Chain>, Option<&Addr>, &[Coin]) -> Result<(), CwEnvError>>;
+type StatusFn = Box<dyn Fn() -> bool>;
+
+/// Information about the already deployed contracts, available when building instantiate messages.
+pub struct PlanContext<Chain> {
+    chain: Chain,
+}
+
+impl<Chain: ChainState> PlanContext<Chain> {
+    /// Returns the address of the contract with the given id, as registered in the state
+    pub fn address(&self, contract_id: &str) -> Result<Addr, CwEnvError> {
+        self.chain.state().get_address(contract_id)
+    }
+
+    /// Returns the code id of the contract with the given id, as registered in the state
+    pub fn code_id(&self, contract_id: &str) -> Result<u64, CwEnvError> {
+        self.chain.state().get_code_id(contract_id)
+    }
+
+    /// Returns the environment the plan is executed on
+    pub fn chain(&self) -> &Chain {
+        &self.chain
+    }
+}
+
+/// Deployment of a single contract in a [`DeploymentPlan`]
+pub struct ContractStep<Chain> {
+    contract_id: String,
+    dependencies: Vec<String>,
+    admin: Option<Addr>,
+    funds: Vec<Coin>,
+    is_uploaded: StatusFn,
+    upload: UploadFn,
+    is_instantiated: Option<StatusFn>,
+    instantiate: Option<InstantiateFn<Chain>>,
+}
+
+impl<Chain: CwEnv> ContractStep<Chain> {
+    /// Only uploads the contract
+    pub fn upload<T>(contract: &T) -> Self
+    where
+        T: CwOrchUpload<Chain> + Clone + 'static,
+    {
+        let uploaded = contract.clone();
+        let to_upload = contract.clone();
+        Self {
+            contract_id: contract.id(),
+            dependencies: vec![],
+            admin: None,
+            funds: vec![],
+            is_uploaded: Box::new(move || uploaded.code_id().is_ok()),
+            upload: Box::new(move || to_upload.upload().map(|_| ())),
+            is_instantiated: None,
+            instantiate: None,
+        }
+    }
+
+    /// Uploads and instantiates the contract.
+    /// The instantiate message is built when the step is executed, so it can reference the contracts deployed in previous steps.
+    pub fn instantiate<T, F>(contract: &T, msg: F) -> Self
+    where
+        T: CwOrchUpload<Chain> + CwOrchInstantiate<Chain> + Clone + 'static,
+        F: Fn(&PlanContext<Chain>) -> Result<T::InstantiateMsg, CwEnvError> + 'static,
+    {
+        let instantiated = contract.clone();
+        let to_instantiate = contract.clone();
+        let mut step = Self::upload(contract);
+        step.is_instantiated = Some(Box::new(move || instantiated.address().is_ok()));
+        step.instantiate = Some(Box::new(move |ctx, admin, funds| {
+            let msg = msg(ctx)?;
+            to_instantiate.instantiate(&msg, admin, funds).map(|_| ())
+        }));
+        step
+    }
+
+    /// Requires the contract with the given id to be deployed before this one
+    pub fn depends_on(mut self, contract_id: impl Into<String>) -> Self {
+        self.dependencies.push(contract_id.into());
+        self
+    }
+
+    /// Sets the admin of the contract
+    pub fn admin(mut self, admin: &Addr) -> Self {
+        self.admin = Some(admin.clone());
+        self
+    }
+
+    /// Sets the funds sent along the instantiation
+    pub fn funds(mut self, funds: &[Coin]) -> Self {
+        self.funds = funds.to_vec();
+        self
+    }
+
+    /// Id of the deployed contract
+    pub fn contract_id(&self) -> &str {
+        &self.contract_id
+    }
+
+    /// Actions of this step, in execution order
+    fn actions(&self) -> Vec<PlannedAction> {
+        let mut actions = vec![PlannedAction {
+            contract_id: self.contract_id.clone(),
+            kind: ActionKind::Upload,
+            skipped: (self.is_uploaded)(),
+        }];
+        if let Some(is_instantiated) = &self.is_instantiated {
+            actions.push(PlannedAction {
+                contract_id: self.contract_id.clone(),
+                kind: ActionKind::Instantiate,
+                skipped: is_instantiated(),
+            });
+        }
+        actions
+    }
+}
+
+/// Kind of action executed by a deployment plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    /// Upload the contract code
+    Upload,
+    /// Instantiate the contract
+    Instantiate,
+}
+
+/// Action of a deployment plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedAction {
+    /// Id of the contract
+    pub contract_id: String,
+    /// Action on the contract
+    pub kind: ActionKind,
+    /// Whether the action is skipped because it is already registered in the state
+    pub skipped: bool,
+}
+
+/// Ordered actions of a deployment plan
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlanSummary {
+    /// Actions in execution order
+    pub actions: Vec<PlannedAction>,
+}
+
+impl PlanSummary {
+    /// Actions that are (or were) executed
+    pub fn executed(&self) -> impl Iterator<Item = &PlannedAction> {
+        self.actions.iter().filter(|a| !a.skipped)
+    }
+
+    /// Actions that are skipped
+    pub fn skipped(&self) -> impl Iterator<Item = &PlannedAction> {
+        self.actions.iter().filter(|a| a.skipped)
+    }
+}
+
+impl Display for PlanSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, action) in self.actions.iter().enumerate() {
+            let kind = match action.kind {
+                ActionKind::Upload => "upload",
+                ActionKind::Instantiate => "instantiate",
+            };
+            let status = if action.skipped { " (skipped)" } else { "" };
+            writeln!(f, "{}. {} {}{}", i + 1, kind, action.contract_id, status)?;
+        }
+        Ok(())
+    }
+}
+
+/// Declarative deployment of multiple contracts, see the [module documentation](self).
+pub struct DeploymentPlan<Chain> {
+    chain: Chain,
+    steps: Vec<ContractStep<Chain>>,
+}
+
+impl<Chain: CwEnv> DeploymentPlan<Chain> {
+    /// Creates an empty plan on the given environment
+    pub fn new(chain: Chain) -> Self {
+        Self {
+            chain,
+            steps: vec![],
+        }
+    }
+
+    /// Adds a step to the plan
+    pub fn step(mut self, step: ContractStep<Chain>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Returns the steps in execution order, every contract being placed after its dependencies.
+    /// Steps without dependencies between them keep the order in which they were added.
+    pub fn ordered_steps(&self) -> Result<Vec<&ContractStep<Chain>>, CwEnvError> {
+        for step in &self.steps {
+            if let Some(dependency) = step
+                .dependencies
+                .iter()
+                .find(|dep| !self.steps.iter().any(|s| &s.contract_id == *dep))
+            {
+                return Err(CwEnvError::UnknownPlanDependency(
+                    step.contract_id.clone(),
+                    dependency.clone(),
+                ));
+            }
+        }
+
+        let mut ordered: Vec<&ContractStep<Chain>> = vec![];
+        let mut remaining: Vec<&ContractStep<Chain>> = self.steps.iter().collect();
+        while !remaining.is_empty() {
+            let Some(position) = remaining.iter().position(|step| {
+                step.dependencies
+                    .iter()
+                    .all(|dep| ordered.iter().any(|s| &s.contract_id == dep))
+            }) else {
+                return Err(CwEnvError::PlanDependencyCycle(
+                    remaining[0].contract_id.clone(),
+                ));
+            };
+            ordered.push(remaining.remove(position));
+        }
+        Ok(ordered)
+    }
+
+    /// Returns the actions that would be executed, without executing them
+    pub fn dry_run(&self) -> Result<PlanSummary, CwEnvError> {
+        let actions = self
+            .ordered_steps()?
+            .into_iter()
+            .flat_map(ContractStep::actions)
+            .collect();
+        Ok(PlanSummary { actions })
+    }
+
+    /// Executes the plan, skipping the actions already registered in the state.
+    /// Returns the actions in execution order.
+    pub fn execute(&self) -> Result<PlanSummary, CwEnvError> {
+        let ctx = PlanContext {
+            chain: self.chain.clone(),
+        };
+        let mut summary = PlanSummary::default();
+        for step in self.ordered_steps()? {
+            for action in step.actions() {
+                if action.skipped {
+                    log::info!(
+                        target: &contract_target(),
+                        "[{}] {:?} already deployed, skipping",
+                        step.contract_id,
+                        action.kind
+                    );
+                } else {
+                    match action.kind {
+                        ActionKind::Upload => (step.upload)()?,
+                        ActionKind::Instantiate => {
+                            if let Some(instantiate) = &step.instantiate {
+                                instantiate(&ctx, step.admin.as_ref(), &step.funds)?
+                            }
+                        }
+                    }
+                }
+                summary.actions.push(action);
+            }
+        }
+        Ok(summary)
+    }
+}
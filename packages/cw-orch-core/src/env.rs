@@ -15,6 +15,7 @@ use cosmwasm_std::StdError;
 pub const ARTIFACTS_DIR_ENV_NAME: &str = "ARTIFACTS_DIR";
 pub const SERIALIZE_ENV_NAME: &str = "CW_ORCH_SERIALIZE_JSON";
 pub const MANUAL_INTERACTION_ENV_NAME: &str = "CW_ORCH_MANUAL_INTERACTION";
+pub const UPDATE_GAS_BASELINE_ENV_NAME: &str = "CW_ORCH_UPDATE_GAS_BASELINE";
 
 pub struct CoreEnvVars;
 
@@ -59,6 +60,17 @@ impl CoreEnvVars {
             true
         }
     }
+
+    /// Optional - boolean
+    /// Defaults to false
+    /// If equals to true, gas baselines checked with `GasReport::check_baseline` are overwritten with the current report
+    pub fn update_gas_baseline() -> bool {
+        if let Ok(str_value) = env::var(UPDATE_GAS_BASELINE_ENV_NAME) {
+            parse_with_log(str_value, UPDATE_GAS_BASELINE_ENV_NAME)
+        } else {
+            false
+        }
+    }
 }
 
 fn parse_with_log<F: FromStr<Err = E>, E: std::fmt::Display>(
@@ -5,7 +5,7 @@ use super::{
     queriers::{bank::BankQuerier, QuerierGetter},
     QueryHandler, TxHandler,
 };
-use cosmwasm_std::{Addr, Coin};
+use cosmwasm_std::{Addr, Coin, Decimal};
 use cw_utils::NativeBalance;
 
 /// Describes a structure that contains an underlying execution environment
@@ -43,3 +43,52 @@ pub trait BankSetter: TxHandler + QuerierGetter<Self::T> {
         Ok(())
     }
 }
+
+/// Parameters of the staking module of a test environment
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakingParams {
+    /// Denom of the tokens that can be delegated
+    pub bonded_denom: String,
+    /// Time in seconds before undelegated tokens are released
+    pub unbonding_time: u64,
+    /// Yearly rate at which delegation rewards accrue
+    pub apr: Decimal,
+}
+
+/// Allows setting up the staking module of a test environment.
+///
+/// There is no method to distribute rewards to a validator: the staking modules of the test environments
+/// compute the rewards of each delegation from [`StakingParams::apr`], the validator commission and the elapsed block time,
+/// without a rewards pool that could be credited. Tests set the reward rate with the APR
+/// and accrue rewards by advancing the block time, for instance with [`QueryHandler::wait_seconds`].
+pub trait StakingSetter: TxHandler {
+    /// Sets the bonded denom, unbonding time and rewards rate of the staking module.
+    /// Must be called before any validator is added.
+    fn set_staking_params(
+        &mut self,
+        params: StakingParams,
+    ) -> Result<(), <Self as TxHandler>::Error>;
+
+    /// Registers a validator with the given commission rate on rewards
+    fn add_validator(
+        &mut self,
+        validator: &str,
+        commission: Decimal,
+    ) -> Result<(), <Self as TxHandler>::Error>;
+
+    /// Delegates `amount` of the bonded denom from `delegator` to `validator`.
+    /// The delegator needs to hold the delegated tokens.
+    fn delegate_from(
+        &mut self,
+        delegator: &Addr,
+        validator: &str,
+        amount: Coin,
+    ) -> Result<(), <Self as TxHandler>::Error>;
+
+    /// Slashes the stake delegated to `validator` by `percentage`
+    fn slash(
+        &mut self,
+        validator: &str,
+        percentage: Decimal,
+    ) -> Result<(), <Self as TxHandler>::Error>;
+}
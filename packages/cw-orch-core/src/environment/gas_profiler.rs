@@ -0,0 +1,588 @@
+//! Environment wrapper that records the gas used by the transactions sent through it.
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use cosmwasm_std::{Addr, Binary, BlockInfo, Checksum, Coin, Delegation, Validator};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    contract::{
+        get_struct_name,
+        interface_traits::{ContractInstance, Uploadable},
+    },
+    CwEnvError,
+};
+
+use super::{
+    queriers::{
+        bank::BankQuerier,
+        distribution::DistributionQuerier,
+        env::{EnvironmentInfo, EnvironmentQuerier},
+        gov::{GovDeposit, GovProposal, GovQuerier, GovVote, ProposalStatus, TallyResult},
+        node::NodeQuerier,
+        staking::StakingQuerier,
+        wasm::WasmQuerier,
+        DefaultQueriers, Querier, QuerierGetter, QueryHandler,
+    },
+    AccessConfig, ChainInfoOwned, ChainState, GasEntryPoint, GasReport, GasReportKey,
+    IndexResponse, NetworkInfoOwned, StateInterface, TxHandler,
+};
+
+/// Event attribute holding the fees paid by a transaction
+const FEE_EVENT: (&str, &str) = ("tx", "fee");
+
+/// Environment recording the gas used and the fees paid by every transaction sent through it.
+///
+/// Calls are aggregated per contract id (from the state of the environment), entry point and message:
+/// the variant of execute messages and the type of instantiate and migrate messages.
+/// Uploads and instantiations are attributed to the contract that stored the uploaded code id or the instantiated address,
+/// which the contract interfaces save in the state once the transaction returns.
+/// Only environments reporting the gas used by their transactions ([`IndexResponse::gas_used`]) are profiled,
+/// like `Daemon`, test-tube environments or `Mock` with gas metering.
+///
+/// ```ignore
+/// let chain = GasProfiler::new(daemon);
+/// let counter = CounterContract::new(chain.clone());
+/// counter.upload()?;
+/// counter.instantiate(&InstantiateMsg { count: 0 }, None, &[])?;
+/// counter.increment()?;
+///
+/// println!("{}", chain.report().to_markdown());
+/// chain.report().check_baseline("gas-baseline.json", 5)?;
+/// ```
+#[derive(Clone)]
+pub struct GasProfiler<Chain> {
+    chain: Chain,
+    calls: Arc<Mutex<ProfiledCalls>>,
+}
+
+/// Contract a profiled call was sent to, resolved to its contract id from the state
+#[derive(Debug, Clone)]
+enum ContractRef {
+    /// Code uploaded by the call
+    CodeId(u64),
+    Address(Addr),
+}
+
+/// Call waiting for its contract to be saved in the state
+struct PendingCall {
+    contract: ContractRef,
+    entry_point: GasEntryPoint,
+    message: String,
+    gas: u64,
+    fees: Vec<Coin>,
+}
+
+#[derive(Default)]
+struct ProfiledCalls {
+    report: GasReport,
+    pending: Vec<PendingCall>,
+}
+
+impl<Chain> GasProfiler<Chain> {
+    pub fn new(chain: Chain) -> Self {
+        Self {
+            chain,
+            calls: Default::default(),
+        }
+    }
+
+    /// Environment the calls are delegated to
+    pub fn inner(&self) -> &Chain {
+        &self.chain
+    }
+
+    /// Unwraps the profiled environment
+    pub fn into_inner(self) -> Chain {
+        self.chain
+    }
+
+    /// Clear the gas report, for instance after the setup of a test
+    pub fn reset_report(&self) {
+        let mut calls = self.calls.lock().unwrap();
+        calls.report.clear();
+        calls.pending.clear();
+    }
+}
+
+impl<Chain: ChainState> GasProfiler<Chain> {
+    /// Gas used by the transactions sent so far
+    pub fn report(&self) -> GasReport {
+        let mut calls = self.calls.lock().unwrap();
+        self.resolve_pending(&mut calls);
+        calls.report.clone()
+    }
+
+    /// Contract id of the contract a call was sent to,
+    /// or its address or uploaded code id if it's not in the state
+    fn contract_id(&self, contract: &ContractRef) -> String {
+        let state = self.chain.state();
+        match contract {
+            ContractRef::Address(address) => state
+                .get_all_addresses()
+                .ok()
+                .and_then(|addresses| {
+                    addresses
+                        .into_iter()
+                        .filter(|(_, a)| a == address)
+                        .map(|(id, _)| id)
+                        .min()
+                })
+                .unwrap_or_else(|| address.to_string()),
+            ContractRef::CodeId(code_id) => state
+                .get_all_code_ids()
+                .ok()
+                .and_then(|code_ids| {
+                    code_ids
+                        .into_iter()
+                        .filter(|(_, id)| id == code_id)
+                        .map(|(id, _)| id)
+                        .min()
+                })
+                .unwrap_or_else(|| code_id.to_string()),
+        }
+    }
+
+    /// Add the pending calls to the report.
+    /// Their contracts were saved in the state when the previous transaction returned.
+    fn resolve_pending(&self, calls: &mut ProfiledCalls) {
+        for call in std::mem::take(&mut calls.pending) {
+            calls.report.record_with_fees(
+                GasReportKey::new(
+                    self.contract_id(&call.contract),
+                    call.entry_point,
+                    call.message,
+                ),
+                call.gas,
+                &call.fees,
+            );
+        }
+    }
+
+    fn record(
+        &self,
+        contract: ContractRef,
+        entry_point: GasEntryPoint,
+        message: String,
+        response: &impl IndexResponse,
+    ) {
+        let mut calls = self.calls.lock().unwrap();
+        self.resolve_pending(&mut calls);
+        let Some(gas) = response.gas_used() else {
+            return;
+        };
+        let fees = response
+            .event_attr_value(FEE_EVENT.0, FEE_EVENT.1)
+            .map(|fees| parse_coins(&fees))
+            .unwrap_or_default();
+        calls.pending.push(PendingCall {
+            contract,
+            entry_point,
+            message,
+            gas,
+            fees,
+        });
+    }
+
+    fn record_upload(&self, response: &impl IndexResponse) {
+        if let Ok(code_id) = response.uploaded_code_id() {
+            self.record(
+                ContractRef::CodeId(code_id),
+                GasEntryPoint::Upload,
+                String::new(),
+                response,
+            );
+        }
+    }
+
+    fn record_instantiate<I>(&self, code_id: u64, response: &impl IndexResponse) {
+        let contract = response
+            .instantiated_contract_address()
+            .map(ContractRef::Address)
+            .unwrap_or(ContractRef::CodeId(code_id));
+        self.record(
+            contract,
+            GasEntryPoint::Instantiate,
+            short_type_name::<I>(),
+            response,
+        );
+    }
+}
+
+/// Name of the type without its path and generics, `Counter` for `counter::interface::Counter<Mock>`
+fn short_type_name<T>() -> String {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+/// Parses a list of coins like `1000uatom,10ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2`
+fn parse_coins(coins: &str) -> Vec<Coin> {
+    coins
+        .split(',')
+        .filter_map(|coin| {
+            let coin = coin.trim();
+            let split = coin.find(|c: char| !c.is_ascii_digit())?;
+            let (amount, denom) = coin.split_at(split);
+            Some(Coin::new(amount.parse::<u128>().ok()?, denom))
+        })
+        .collect()
+}
+
+impl<Chain: ChainState> ChainState for GasProfiler<Chain> {
+    type Out = Chain::Out;
+
+    fn state(&self) -> Self::Out {
+        self.chain.state()
+    }
+
+    fn can_load_state_from_state_file(&self) -> bool {
+        self.chain.can_load_state_from_state_file()
+    }
+}
+
+impl<Chain: TxHandler> TxHandler for GasProfiler<Chain> {
+    type Response = Chain::Response;
+    type Error = Chain::Error;
+    type ContractSource = Chain::ContractSource;
+    type Sender = Chain::Sender;
+
+    fn sender(&self) -> &Self::Sender {
+        self.chain.sender()
+    }
+
+    fn sender_addr(&self) -> Addr {
+        self.chain.sender_addr()
+    }
+
+    fn set_sender(&mut self, sender: Self::Sender) {
+        self.chain.set_sender(sender)
+    }
+
+    fn upload<T: Uploadable>(&self, contract_source: &T) -> Result<Self::Response, Self::Error> {
+        let response = self.chain.upload(contract_source)?;
+        self.record_upload(&response);
+        Ok(response)
+    }
+
+    fn upload_with_access_config<T: Uploadable>(
+        &self,
+        contract_source: &T,
+        access_config: Option<AccessConfig>,
+    ) -> Result<Self::Response, Self::Error> {
+        let response = self
+            .chain
+            .upload_with_access_config(contract_source, access_config)?;
+        self.record_upload(&response);
+        Ok(response)
+    }
+
+    fn instantiate<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+    ) -> Result<Self::Response, Self::Error> {
+        let response = self
+            .chain
+            .instantiate(code_id, init_msg, label, admin, coins)?;
+        self.record_instantiate::<I>(code_id, &response);
+        Ok(response)
+    }
+
+    fn instantiate2<I: Serialize + Debug>(
+        &self,
+        code_id: u64,
+        init_msg: &I,
+        label: Option<&str>,
+        admin: Option<&Addr>,
+        coins: &[Coin],
+        salt: Binary,
+    ) -> Result<Self::Response, Self::Error> {
+        let response = self
+            .chain
+            .instantiate2(code_id, init_msg, label, admin, coins, salt)?;
+        self.record_instantiate::<I>(code_id, &response);
+        Ok(response)
+    }
+
+    fn execute<E: Serialize + Debug>(
+        &self,
+        exec_msg: &E,
+        coins: &[Coin],
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error> {
+        let response = self.chain.execute(exec_msg, coins, contract_address)?;
+        self.record(
+            ContractRef::Address(contract_address.clone()),
+            GasEntryPoint::Execute,
+            get_struct_name(exec_msg).unwrap_or_default(),
+            &response,
+        );
+        Ok(response)
+    }
+
+    fn migrate<M: Serialize + Debug>(
+        &self,
+        migrate_msg: &M,
+        new_code_id: u64,
+        contract_address: &Addr,
+    ) -> Result<Self::Response, Self::Error> {
+        let response = self
+            .chain
+            .migrate(migrate_msg, new_code_id, contract_address)?;
+        self.record(
+            ContractRef::Address(contract_address.clone()),
+            GasEntryPoint::Migrate,
+            short_type_name::<M>(),
+            &response,
+        );
+        Ok(response)
+    }
+
+    fn bank_send(&self, receiver: &Addr, amount: &[Coin]) -> Result<Self::Response, Self::Error> {
+        self.chain.bank_send(receiver, amount)
+    }
+}
+
+impl<Chain: QueryHandler + ChainState> QueryHandler for GasProfiler<Chain> {
+    type Error = <Chain as QueryHandler>::Error;
+
+    fn wait_blocks(&self, amount: u64) -> Result<(), Self::Error> {
+        self.chain.wait_blocks(amount)
+    }
+
+    fn wait_seconds(&self, secs: u64) -> Result<(), Self::Error> {
+        self.chain.wait_seconds(secs)
+    }
+
+    fn next_block(&self) -> Result<(), Self::Error> {
+        self.chain.next_block()
+    }
+}
+
+impl<Chain: EnvironmentQuerier> EnvironmentQuerier for GasProfiler<Chain> {
+    fn env_info(&self) -> EnvironmentInfo {
+        self.chain.env_info()
+    }
+}
+
+impl<Chain: DefaultQueriers + ChainState> DefaultQueriers for GasProfiler<Chain> {
+    type Bank = ProfiledQuerier<Chain::Bank>;
+    type Wasm = ProfiledQuerier<Chain::Wasm>;
+    type Node = ProfiledQuerier<Chain::Node>;
+    type Staking = ProfiledQuerier<Chain::Staking>;
+    type Distribution = ProfiledQuerier<Chain::Distribution>;
+    type Gov = ProfiledQuerier<Chain::Gov>;
+}
+
+/// Querier of a [`GasProfiler`], delegating to the querier of the profiled environment.
+pub struct ProfiledQuerier<Q>(Q);
+
+impl<Q> ProfiledQuerier<Q> {
+    /// Querier of the profiled environment
+    pub fn inner(&self) -> &Q {
+        &self.0
+    }
+}
+
+impl<Chain: QuerierGetter<Q>, Q: Querier> QuerierGetter<ProfiledQuerier<Q>> for GasProfiler<Chain> {
+    fn querier(&self) -> ProfiledQuerier<Q> {
+        ProfiledQuerier(self.chain.querier())
+    }
+}
+
+impl<Q: Querier> Querier for ProfiledQuerier<Q> {
+    type Error = Q::Error;
+}
+
+impl<Q: BankQuerier> BankQuerier for ProfiledQuerier<Q> {
+    fn balance(&self, address: &Addr, denom: Option<String>) -> Result<Vec<Coin>, Self::Error> {
+        self.0.balance(address, denom)
+    }
+
+    fn total_supply(&self) -> Result<Vec<Coin>, Self::Error> {
+        self.0.total_supply()
+    }
+
+    fn supply_of(&self, denom: impl Into<String>) -> Result<Coin, Self::Error> {
+        self.0.supply_of(denom)
+    }
+}
+
+impl<Q: WasmQuerier> WasmQuerier for ProfiledQuerier<Q>
+where
+    Q::Chain: EnvironmentQuerier,
+{
+    type Chain = GasProfiler<Q::Chain>;
+
+    fn code_id_hash(&self, code_id: u64) -> Result<Checksum, Self::Error> {
+        self.0.code_id_hash(code_id)
+    }
+
+    fn contract_info(
+        &self,
+        address: &Addr,
+    ) -> Result<cosmwasm_std::ContractInfoResponse, Self::Error> {
+        self.0.contract_info(address)
+    }
+
+    fn raw_query(&self, address: &Addr, query_keys: Vec<u8>) -> Result<Vec<u8>, Self::Error> {
+        self.0.raw_query(address, query_keys)
+    }
+
+    fn smart_query<M: Serialize, T: DeserializeOwned>(
+        &self,
+        address: &Addr,
+        query_msg: &M,
+    ) -> Result<T, Self::Error> {
+        self.0.smart_query(address, query_msg)
+    }
+
+    fn code(&self, code_id: u64) -> Result<cosmwasm_std::CodeInfoResponse, Self::Error> {
+        self.0.code(code_id)
+    }
+
+    fn local_hash<T: Uploadable + ContractInstance<Self::Chain>>(
+        &self,
+        contract: &T,
+    ) -> Result<Checksum, CwEnvError> {
+        let env_info = contract.environment().env_info();
+        let chain_info = ChainInfoOwned {
+            chain_id: env_info.chain_id,
+            network_info: NetworkInfoOwned {
+                chain_name: env_info.chain_name,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        <T as Uploadable>::wasm(&chain_info).checksum()
+    }
+
+    fn instantiate2_addr(
+        &self,
+        code_id: u64,
+        creator: &Addr,
+        salt: Binary,
+    ) -> Result<String, Self::Error> {
+        self.0.instantiate2_addr(code_id, creator, salt)
+    }
+
+    fn is_not_found(&self, error: &Self::Error) -> bool {
+        self.0.is_not_found(error)
+    }
+}
+
+impl<Q: NodeQuerier> NodeQuerier for ProfiledQuerier<Q> {
+    type Response = Q::Response;
+
+    fn latest_block(&self) -> Result<BlockInfo, Self::Error> {
+        self.0.latest_block()
+    }
+
+    fn block_by_height(&self, height: u64) -> Result<BlockInfo, Self::Error> {
+        self.0.block_by_height(height)
+    }
+
+    fn block_height(&self) -> Result<u64, Self::Error> {
+        self.0.block_height()
+    }
+
+    fn block_time(&self) -> Result<u128, Self::Error> {
+        self.0.block_time()
+    }
+
+    fn simulate_tx(&self, tx_bytes: Vec<u8>) -> Result<u64, Self::Error> {
+        self.0.simulate_tx(tx_bytes)
+    }
+
+    fn find_tx(&self, hash: String) -> Result<Self::Response, Self::Error> {
+        self.0.find_tx(hash)
+    }
+}
+
+impl<Q: StakingQuerier> StakingQuerier for ProfiledQuerier<Q> {
+    fn bonded_denom(&self) -> Result<String, Self::Error> {
+        self.0.bonded_denom()
+    }
+
+    fn validator(&self, validator: &str) -> Result<Validator, Self::Error> {
+        self.0.validator(validator)
+    }
+
+    fn validators(&self) -> Result<Vec<Validator>, Self::Error> {
+        self.0.validators()
+    }
+
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<Delegation>, Self::Error> {
+        self.0.delegation(delegator, validator)
+    }
+
+    fn delegator_delegations(&self, delegator: &Addr) -> Result<Vec<Delegation>, Self::Error> {
+        self.0.delegator_delegations(delegator)
+    }
+}
+
+impl<Q: DistributionQuerier> DistributionQuerier for ProfiledQuerier<Q> {
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        self.0.delegation_rewards(delegator, validator)
+    }
+
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+        self.0.delegation_total_rewards(delegator)
+    }
+}
+
+impl<Q: GovQuerier> GovQuerier for ProfiledQuerier<Q> {
+    fn proposal(&self, proposal_id: u64) -> Result<GovProposal, Self::Error> {
+        self.0.proposal(proposal_id)
+    }
+
+    fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<GovProposal>, Self::Error> {
+        self.0.proposals(status)
+    }
+
+    fn vote(&self, proposal_id: u64, voter: &Addr) -> Result<GovVote, Self::Error> {
+        self.0.vote(proposal_id, voter)
+    }
+
+    fn votes(&self, proposal_id: u64) -> Result<Vec<GovVote>, Self::Error> {
+        self.0.votes(proposal_id)
+    }
+
+    fn deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error> {
+        self.0.deposits(proposal_id)
+    }
+
+    fn tally(&self, proposal_id: u64) -> Result<TallyResult, Self::Error> {
+        self.0.tally(proposal_id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_fees_and_type_names() {
+        assert_eq!(
+            parse_coins("1000uatom,25ibc/ABC"),
+            vec![Coin::new(1000u128, "uatom"), Coin::new(25u128, "ibc/ABC")]
+        );
+        assert!(parse_coins("").is_empty());
+        assert_eq!(short_type_name::<GasProfiler<Vec<u8>>>(), "GasProfiler");
+    }
+}
@@ -0,0 +1,316 @@
+use std::{collections::BTreeMap, fmt::Display, path::Path};
+
+use cosmwasm_std::Coin;
+use serde::{Deserialize, Serialize};
+
+use crate::{CoreEnvVars, CwEnvError};
+
+/// Contract entry point a gas measure was taken on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GasEntryPoint {
+    Upload,
+    Instantiate,
+    Execute,
+    Query,
+    Migrate,
+    Sudo,
+    Reply,
+}
+
+impl Display for GasEntryPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            GasEntryPoint::Upload => "upload",
+            GasEntryPoint::Instantiate => "instantiate",
+            GasEntryPoint::Execute => "execute",
+            GasEntryPoint::Query => "query",
+            GasEntryPoint::Migrate => "migrate",
+            GasEntryPoint::Sudo => "sudo",
+            GasEntryPoint::Reply => "reply",
+        };
+        write!(f, "{}", str)
+    }
+}
+
+/// Identifies the calls aggregated in a [`GasReport`] row
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GasReportKey {
+    /// Id of the contract that was called, or its address or code id when it isn't saved in the state
+    pub contract: String,
+    pub entry_point: GasEntryPoint,
+    /// Name of the message variant, for instance `transfer` for `{"transfer": {..}}`
+    pub message: String,
+}
+
+impl GasReportKey {
+    pub fn new(
+        contract: impl Into<String>,
+        entry_point: GasEntryPoint,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            contract: contract.into(),
+            entry_point,
+            message: message.into(),
+        }
+    }
+}
+
+/// Aggregated gas usage of the calls matching a [`GasReportKey`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasUsage {
+    pub calls: u64,
+    pub total: u64,
+    pub min: u64,
+    pub max: u64,
+    /// Total fees paid by the calls, on environments that charge fees
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fees: Vec<Coin>,
+}
+
+impl GasUsage {
+    /// Average gas used per call
+    pub fn average(&self) -> u64 {
+        self.total.checked_div(self.calls).unwrap_or_default()
+    }
+
+    fn record(&mut self, gas: u64) {
+        self.min = if self.calls == 0 {
+            gas
+        } else {
+            self.min.min(gas)
+        };
+        self.max = self.max.max(gas);
+        self.total += gas;
+        self.calls += 1;
+    }
+
+    fn add_fees(&mut self, fees: &[Coin]) {
+        for fee in fees {
+            match self.fees.iter_mut().find(|c| c.denom == fee.denom) {
+                Some(coin) => coin.amount += fee.amount,
+                None => self.fees.push(fee.clone()),
+            }
+        }
+    }
+}
+
+/// Call of a [`GasReport`] that uses more gas than in the baseline report
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasRegression {
+    pub key: GasReportKey,
+    /// Average gas used in the baseline
+    pub baseline: u64,
+    /// Average gas used in the current report
+    pub current: u64,
+}
+
+impl Display for GasRegression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}: {} -> {} gas",
+            self.key.contract, self.key.entry_point, self.key.message, self.baseline, self.current
+        )
+    }
+}
+
+/// Gas used by contract calls, aggregated per contract, entry point and message.
+///
+/// The report can be printed as a markdown table and compared against a baseline stored in a JSON file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GasReport {
+    entries: BTreeMap<GasReportKey, GasUsage>,
+}
+
+/// Flat representation of the report entries, used for the JSON format
+#[derive(Serialize, Deserialize)]
+struct GasReportRow {
+    #[serde(flatten)]
+    key: GasReportKey,
+    #[serde(flatten)]
+    usage: GasUsage,
+}
+
+impl GasReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the gas used by a call
+    pub fn record(&mut self, key: GasReportKey, gas: u64) {
+        self.entries.entry(key).or_default().record(gas);
+    }
+
+    /// Record the gas used and the fees paid by a transaction
+    pub fn record_with_fees(&mut self, key: GasReportKey, gas: u64, fees: &[Coin]) {
+        let usage = self.entries.entry(key).or_default();
+        usage.record(gas);
+        usage.add_fees(fees);
+    }
+
+    /// Gas usage of the calls matching the key, if any
+    pub fn usage(&self, key: &GasReportKey) -> Option<&GasUsage> {
+        self.entries.get(key)
+    }
+
+    /// Iterate over the entries of the report, ordered by contract, entry point and message
+    pub fn iter(&self) -> impl Iterator<Item = (&GasReportKey, &GasUsage)> {
+        self.entries.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Remove all the entries of the report
+    pub fn clear(&mut self) {
+        self.entries.clear()
+    }
+
+    /// Render the report as a markdown table.
+    /// The fees column is only added when some calls paid fees.
+    pub fn to_markdown(&self) -> String {
+        let with_fees = self.entries.values().any(|usage| !usage.fees.is_empty());
+        let mut table =
+            String::from("| Contract | Entry point | Message | Calls | Min | Average | Max |");
+        if with_fees {
+            table.push_str(" Fees |\n|---|---|---|---|---|---|---|---|\n");
+        } else {
+            table.push_str("\n|---|---|---|---|---|---|---|\n");
+        }
+        for (key, usage) in &self.entries {
+            table.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} |",
+                key.contract,
+                key.entry_point,
+                key.message,
+                usage.calls,
+                usage.min,
+                usage.average(),
+                usage.max
+            ));
+            if with_fees {
+                let fees: Vec<_> = usage.fees.iter().map(ToString::to_string).collect();
+                table.push_str(&format!(" {} |", fees.join(", ")));
+            }
+            table.push('\n');
+        }
+        table
+    }
+
+    /// Serialize the report to JSON
+    pub fn to_json(&self) -> Result<String, CwEnvError> {
+        let rows: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(key, usage)| GasReportRow {
+                key: key.clone(),
+                usage: usage.clone(),
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&rows)?)
+    }
+
+    /// Deserialize a report from JSON
+    pub fn from_json(json: &str) -> Result<Self, CwEnvError> {
+        let rows: Vec<GasReportRow> = serde_json::from_str(json)?;
+        Ok(Self {
+            entries: rows.into_iter().map(|row| (row.key, row.usage)).collect(),
+        })
+    }
+
+    /// Write the report to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CwEnvError> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Read a report from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CwEnvError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Calls whose average gas usage exceeds the one of the baseline by more than `tolerance_percent`.
+    /// Calls missing from the baseline are ignored.
+    pub fn regressions(&self, baseline: &GasReport, tolerance_percent: u64) -> Vec<GasRegression> {
+        self.entries
+            .iter()
+            .filter_map(|(key, usage)| {
+                let baseline = baseline.entries.get(key)?.average();
+                let current = usage.average();
+                let allowed = baseline as u128 * (100 + tolerance_percent as u128) / 100;
+                (current as u128 > allowed).then(|| GasRegression {
+                    key: key.clone(),
+                    baseline,
+                    current,
+                })
+            })
+            .collect()
+    }
+
+    /// Compare the report against the baseline stored at `path`, see [`GasReport::regressions`].
+    ///
+    /// The baseline is (re)written with the current report when the file doesn't exist
+    /// or when the `CW_ORCH_UPDATE_GAS_BASELINE` environment variable is set to `true`.
+    pub fn check_baseline(
+        &self,
+        path: impl AsRef<Path>,
+        tolerance_percent: u64,
+    ) -> Result<(), CwEnvError> {
+        let path = path.as_ref();
+        if !path.exists() || CoreEnvVars::update_gas_baseline() {
+            return self.save(path);
+        }
+        let regressions = self.regressions(&Self::load(path)?, tolerance_percent);
+        if regressions.is_empty() {
+            return Ok(());
+        }
+        Err(CwEnvError::GasRegression(
+            regressions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ))
+    }
+}
+
+impl Display for GasReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_markdown())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aggregates_and_detects_regressions() -> Result<(), CwEnvError> {
+        let key = GasReportKey::new("counter", GasEntryPoint::Execute, "increment");
+        let mut baseline = GasReport::new();
+        baseline.record(key.clone(), 100);
+        baseline.record(key.clone(), 300);
+
+        let usage = baseline.usage(&key).unwrap();
+        assert_eq!((usage.calls, usage.min, usage.max), (2, 100, 300));
+        assert_eq!(usage.average(), 200);
+        assert_eq!(GasReport::from_json(&baseline.to_json()?)?, baseline);
+
+        let mut current = GasReport::new();
+        current.record(key.clone(), 210);
+        assert!(current.regressions(&baseline, 5).is_empty());
+        current.record(key.clone(), 250);
+        assert_eq!(
+            current.regressions(&baseline, 5),
+            vec![GasRegression {
+                key,
+                baseline: 200,
+                current: 230
+            }]
+        );
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
-use cosmwasm_std::{Addr, Binary, Event, StdError, StdResult};
+use cosmwasm_std::{Addr, Binary, Checksum, Event, StdError, StdResult, Timestamp};
 use cw_multi_test::AppResponse;
 #[cfg(feature = "eth")]
 use snailquote::unescape;
 
 const CODE_ID_UPLOAD_EVENT: (&str, &str) = ("store_code", "code_id");
 const ADDRESS_INSTANTIATE_EVENT: (&str, &str) = ("instantiate", "_contract_address");
+const CODE_CHECKSUM_UPLOAD_EVENT: (&str, &str) = ("store_code", "code_checksum");
+/// Event added to the responses of test environments that meter gas
+pub const GAS_USED_EVENT: (&str, &str) = ("gas", "gas_used");
 
 #[cfg(feature = "eth")]
 const INJECTIVE_CODE_ID_UPLOAD_EVENT: (&str, &str) =
@@ -29,6 +32,28 @@ pub trait IndexResponse {
     /// Get the data field of the response.
     fn data(&self) -> Option<Binary>;
 
+    /// Get the hash of the transaction, if the environment broadcasts actual transactions.
+    fn tx_hash(&self) -> Option<String> {
+        None
+    }
+
+    /// Get the time of the block in which the transaction was included, if available.
+    fn tx_timestamp(&self) -> Option<Timestamp> {
+        None
+    }
+
+    /// Get the gas used by the transaction, if the environment meters gas.
+    fn gas_used(&self) -> Option<u64> {
+        None
+    }
+
+    /// Shortcut to get the checksum of the code of an upload response, if the environment emits it.
+    fn uploaded_code_checksum(&self) -> Option<Checksum> {
+        self.event_attr_value(CODE_CHECKSUM_UPLOAD_EVENT.0, CODE_CHECKSUM_UPLOAD_EVENT.1)
+            .ok()
+            .and_then(|checksum| Checksum::from_hex(&checksum).ok())
+    }
+
     /// Helper to get the contract address of a instantiate response.
     fn instantiated_contract_address(&self) -> StdResult<Addr> {
         if let Ok(code_id) = self
@@ -76,6 +101,12 @@ impl IndexResponse for AppResponse {
         self.events.clone()
     }
 
+    fn gas_used(&self) -> Option<u64> {
+        self.event_attr_value(GAS_USED_EVENT.0, GAS_USED_EVENT.1)
+            .ok()
+            .and_then(|gas| gas.parse().ok())
+    }
+
     fn data(&self) -> Option<Binary> {
         self.data.clone()
     }
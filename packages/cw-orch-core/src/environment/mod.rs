@@ -1,19 +1,36 @@
 mod chain_info;
 mod envs;
+mod gas_profiler;
+mod gas_report;
 mod index_response;
 mod queriers;
 mod state;
+mod storage_diff;
 mod tx_handler;
 
 pub use chain_info::{ChainInfo, ChainInfoOwned, ChainKind, NetworkInfo, NetworkInfoOwned};
-pub use envs::{BankSetter, CwEnv, Environment, MutCwEnv};
-pub use index_response::IndexResponse;
+pub use envs::{BankSetter, CwEnv, Environment, MutCwEnv, StakingParams, StakingSetter};
+pub use gas_profiler::{GasProfiler, ProfiledQuerier};
+pub use gas_report::{GasEntryPoint, GasRegression, GasReport, GasReportKey, GasUsage};
+pub use index_response::{IndexResponse, GAS_USED_EVENT};
 pub use queriers::{
     bank::BankQuerier,
+    distribution::DistributionQuerier,
     env::{EnvironmentInfo, EnvironmentQuerier},
+    gov::{
+        GovDeposit, GovProposal, GovQuerier, GovVote, GovVoteOption, ProposalMessage,
+        ProposalStatus, TallyResult,
+    },
     node::NodeQuerier,
+    staking::StakingQuerier,
     wasm::{AsyncWasmQuerier, WasmQuerier},
     DefaultQueriers, Querier, QuerierGetter, QueryHandler,
 };
-pub use state::{ChainState, StateInterface};
+pub use state::{
+    ChainState, DeploymentAction, DeploymentHistory, DeploymentRecord, StateInterface,
+};
+pub use storage_diff::{
+    decode_storage_key, format_storage_value, parse_storage, StorageChange, StorageDiff,
+    StorageDump, StorageDumper,
+};
 pub use tx_handler::{AccessConfig, TxHandler, TxResponse};
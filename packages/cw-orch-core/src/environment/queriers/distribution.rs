@@ -0,0 +1,17 @@
+use cosmwasm_std::{Addr, Coin};
+
+use super::Querier;
+
+pub trait DistributionQuerier: Querier {
+    /// Returns the rewards accrued by the delegation of `delegator` to `validator`.
+    /// Amounts are truncated to whole tokens, as they are when withdrawn.
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error>;
+
+    /// Returns the rewards accrued by all the delegations of `delegator`.
+    /// Amounts are truncated to whole tokens, as they are when withdrawn.
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error>;
+}
@@ -0,0 +1,109 @@
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128, VoteOption};
+use serde::{Deserialize, Serialize};
+
+use super::Querier;
+
+/// Status of a governance proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// The proposal didn't reach the minimum deposit yet
+    DepositPeriod,
+    /// The proposal can be voted on
+    VotingPeriod,
+    /// The proposal passed and its messages were executed successfully
+    Passed,
+    /// The proposal was rejected
+    Rejected,
+    /// The proposal passed but the execution of its messages failed
+    Failed,
+}
+
+/// Governance proposal, independent of the environment it was submitted on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovProposal {
+    pub id: u64,
+    pub title: String,
+    pub summary: String,
+    /// Address of the account that submitted the proposal
+    pub proposer: String,
+    /// Messages executed if the proposal passes.
+    /// Empty on the mock, which keeps the messages of its proposals as [`cosmwasm_std::CosmosMsg`]s.
+    pub messages: Vec<ProposalMessage>,
+    pub status: ProposalStatus,
+    pub total_deposit: Vec<Coin>,
+    pub submit_time: Timestamp,
+    pub deposit_end_time: Timestamp,
+    pub voting_start_time: Option<Timestamp>,
+    pub voting_end_time: Option<Timestamp>,
+    /// Result of the tally, set once the voting period ended
+    pub final_tally_result: Option<TallyResult>,
+}
+
+/// Protobuf encoded message of a [`GovProposal`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalMessage {
+    pub type_url: String,
+    pub value: Binary,
+}
+
+/// Voting power of each option of a proposal
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TallyResult {
+    pub yes: Uint128,
+    pub no: Uint128,
+    pub abstain: Uint128,
+    pub no_with_veto: Uint128,
+}
+
+impl TallyResult {
+    /// Total voting power of the votes
+    pub fn total(&self) -> Uint128 {
+        self.yes + self.no + self.abstain + self.no_with_veto
+    }
+}
+
+/// Vote of an account on a proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovVote {
+    pub proposal_id: u64,
+    pub voter: String,
+    /// Options chosen by the voter, the weights sum up to 1
+    pub options: Vec<GovVoteOption>,
+}
+
+/// Weighted option of a [`GovVote`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovVoteOption {
+    pub option: VoteOption,
+    pub weight: Decimal,
+}
+
+/// Deposit of an account on a proposal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovDeposit {
+    pub proposal_id: u64,
+    pub depositor: String,
+    pub amount: Vec<Coin>,
+}
+
+pub trait GovQuerier: Querier {
+    /// Query a proposal by id
+    fn proposal(&self, proposal_id: u64) -> Result<GovProposal, Self::Error>;
+
+    /// Query all the proposals, optionally filtered by status
+    fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<GovProposal>, Self::Error>;
+
+    /// Query the vote of an account on a proposal
+    fn vote(&self, proposal_id: u64, voter: &Addr) -> Result<GovVote, Self::Error>;
+
+    /// Query all the votes on a proposal
+    fn votes(&self, proposal_id: u64) -> Result<Vec<GovVote>, Self::Error>;
+
+    /// Query all the deposits on a proposal
+    fn deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error>;
+
+    /// Query the tally of a proposal.
+    /// Returns the current tally during the voting period and the final tally afterwards.
+    fn tally(&self, proposal_id: u64) -> Result<TallyResult, Self::Error>;
+}
@@ -1,13 +1,19 @@
 use cosmwasm_std::{Addr, BlockInfo, Coin};
 use serde::{de::DeserializeOwned, Serialize};
 
-use self::{bank::BankQuerier, env::EnvironmentQuerier, node::NodeQuerier, wasm::WasmQuerier};
+use self::{
+    bank::BankQuerier, distribution::DistributionQuerier, env::EnvironmentQuerier, gov::GovQuerier,
+    node::NodeQuerier, staking::StakingQuerier, wasm::WasmQuerier,
+};
 use crate::CwEnvError;
 use std::fmt::Debug;
 
 pub mod bank;
+pub mod distribution;
 pub mod env;
+pub mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
 /// This trait acts as the high-level trait bound for supported queries on a `CwEnv` environment.
@@ -59,11 +65,17 @@ pub trait DefaultQueriers:
     QuerierGetter<Self::Bank>
     + QuerierGetter<Self::Wasm>
     + QuerierGetter<Self::Node>
+    + QuerierGetter<Self::Staking>
+    + QuerierGetter<Self::Distribution>
+    + QuerierGetter<Self::Gov>
     + EnvironmentQuerier
 {
     type Bank: BankQuerier;
     type Wasm: WasmQuerier<Chain = Self>;
     type Node: NodeQuerier;
+    type Staking: StakingQuerier;
+    type Distribution: DistributionQuerier;
+    type Gov: GovQuerier;
 
     fn bank_querier(&self) -> Self::Bank {
         self.querier()
@@ -76,6 +88,18 @@ pub trait DefaultQueriers:
     fn node_querier(&self) -> Self::Node {
         self.querier()
     }
+
+    fn staking_querier(&self) -> Self::Staking {
+        self.querier()
+    }
+
+    fn distribution_querier(&self) -> Self::Distribution {
+        self.querier()
+    }
+
+    fn gov_querier(&self) -> Self::Gov {
+        self.querier()
+    }
 }
 
 #[cfg(test)]
@@ -91,7 +115,14 @@ pub mod test {
         CwEnvError,
     };
 
-    use super::{bank::BankQuerier, wasm::WasmQuerier, QuerierGetter, QueryHandler};
+    use super::{
+        bank::BankQuerier,
+        distribution::DistributionQuerier,
+        gov::{GovDeposit, GovProposal, GovQuerier, GovVote, ProposalStatus, TallyResult},
+        staking::StakingQuerier,
+        wasm::WasmQuerier,
+        QuerierGetter, QueryHandler,
+    };
 
     impl crate::environment::queriers::Querier for MockQuerier {
         type Error = CwEnvError;
@@ -198,6 +229,78 @@ pub mod test {
         }
     }
 
+    impl StakingQuerier for MockQuerier {
+        fn bonded_denom(&self) -> Result<String, Self::Error> {
+            unimplemented!()
+        }
+
+        fn validator(&self, _validator: &str) -> Result<cosmwasm_std::Validator, Self::Error> {
+            unimplemented!()
+        }
+
+        fn validators(&self) -> Result<Vec<cosmwasm_std::Validator>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn delegation(
+            &self,
+            _delegator: &Addr,
+            _validator: &str,
+        ) -> Result<Option<cosmwasm_std::Delegation>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn delegator_delegations(
+            &self,
+            _delegator: &Addr,
+        ) -> Result<Vec<cosmwasm_std::Delegation>, Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    impl DistributionQuerier for MockQuerier {
+        fn delegation_rewards(
+            &self,
+            _delegator: &Addr,
+            _validator: &str,
+        ) -> Result<Vec<Coin>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn delegation_total_rewards(&self, _delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    impl GovQuerier for MockQuerier {
+        fn proposal(&self, _proposal_id: u64) -> Result<GovProposal, Self::Error> {
+            unimplemented!()
+        }
+
+        fn proposals(
+            &self,
+            _status: Option<ProposalStatus>,
+        ) -> Result<Vec<GovProposal>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn vote(&self, _proposal_id: u64, _voter: &Addr) -> Result<GovVote, Self::Error> {
+            unimplemented!()
+        }
+
+        fn votes(&self, _proposal_id: u64) -> Result<Vec<GovVote>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn deposits(&self, _proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn tally(&self, _proposal_id: u64) -> Result<TallyResult, Self::Error> {
+            unimplemented!()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct MockQuerier {}
 
@@ -239,6 +342,9 @@ pub mod test {
         type Bank = MockQuerier;
         type Wasm = MockQuerier;
         type Node = MockQuerier;
+        type Staking = MockQuerier;
+        type Distribution = MockQuerier;
+        type Gov = MockQuerier;
     }
 
     impl QueryHandler for MockHandler {
@@ -0,0 +1,24 @@
+use cosmwasm_std::{Addr, Delegation, Validator};
+
+use super::Querier;
+
+pub trait StakingQuerier: Querier {
+    /// Returns the denom of the tokens that can be delegated
+    fn bonded_denom(&self) -> Result<String, Self::Error>;
+
+    /// Returns the validator with the given operator address
+    fn validator(&self, validator: &str) -> Result<Validator, Self::Error>;
+
+    /// Returns the bonded validators of the chain
+    fn validators(&self) -> Result<Vec<Validator>, Self::Error>;
+
+    /// Returns the delegation of `delegator` to `validator`, if any
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<Delegation>, Self::Error>;
+
+    /// Returns all the delegations of `delegator`
+    fn delegator_delegations(&self, delegator: &Addr) -> Result<Vec<Delegation>, Self::Error>;
+}
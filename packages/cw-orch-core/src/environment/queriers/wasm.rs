@@ -66,6 +66,14 @@ pub trait WasmQuerier: Querier {
         creator: &Addr,
         salt: cosmwasm_std::Binary,
     ) -> Result<String, Self::Error>;
+
+    /// Returns whether `error`, returned by [`WasmQuerier::code_id_hash`] or [`WasmQuerier::contract_info`],
+    /// indicates that the queried code or contract doesn't exist.
+    /// Environments that can't tell these errors apart return `false`.
+    #[allow(unused_variables)]
+    fn is_not_found(&self, error: &Self::Error) -> bool {
+        false
+    }
 }
 
 pub trait AsyncWasmQuerier: Querier + Sync {
@@ -1,7 +1,8 @@
 //! State interfaces for execution environments.
 
 use crate::error::CwEnvError;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Checksum, Timestamp};
+use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
 /// State accessor trait.
@@ -44,8 +45,139 @@ pub trait StateInterface: Clone {
 
     /// Get all codes related to this deployment.
     fn get_all_code_ids(&self) -> Result<HashMap<String, u64>, CwEnvError>;
+
+    /// Records a deployment action (upload, instantiation, migration) for a contract.
+    /// States that don't keep a deployment history ignore the record.
+    fn record_deployment(
+        &mut self,
+        _contract_id: &str,
+        _record: DeploymentRecord,
+    ) -> Result<(), CwEnvError> {
+        Ok(())
+    }
+
+    /// Get the deployment history of a contract, oldest first.
+    fn get_deployment_history(
+        &self,
+        _contract_id: &str,
+    ) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        Ok(vec![])
+    }
+
+    /// Get the deployment history of all contracts, oldest first.
+    fn get_all_deployment_history(
+        &self,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        Ok(HashMap::new())
+    }
+}
+
+/// Deployment action recorded in a contract's deployment history
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentAction {
+    /// The contract code was uploaded
+    Upload,
+    /// The contract was instantiated
+    Instantiate,
+    /// The contract was migrated
+    Migrate,
+}
+
+/// Record of a deployment action on a contract
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    /// Action that was performed
+    pub action: DeploymentAction,
+    /// Code id of the contract after the action
+    pub code_id: Option<u64>,
+    /// Address of the contract after the action
+    pub address: Option<Addr>,
+    /// Hash of the transaction that performed the action
+    pub tx_hash: Option<String>,
+    /// Block time of the transaction that performed the action
+    pub timestamp: Option<Timestamp>,
+    /// Sender of the transaction that performed the action
+    pub deployer: Option<Addr>,
+    /// Checksum of the wasm code of the contract after the action
+    pub checksum: Option<Checksum>,
+}
+
+impl DeploymentRecord {
+    /// Creates a new record with no information apart from the action
+    pub fn new(action: DeploymentAction) -> Self {
+        Self {
+            action,
+            code_id: None,
+            address: None,
+            tx_hash: None,
+            timestamp: None,
+            deployer: None,
+            checksum: None,
+        }
+    }
+}
+
+/// Helpers to answer questions about past deployments, available on all [`StateInterface`]
+pub trait DeploymentHistory: StateInterface {
+    /// Returns the last deployment action of a contract that happened at or before `time`.
+    /// Records without a timestamp are ignored.
+    fn deployment_at(
+        &self,
+        contract_id: &str,
+        time: Timestamp,
+    ) -> Result<Option<DeploymentRecord>, CwEnvError> {
+        Ok(self
+            .get_deployment_history(contract_id)?
+            .into_iter()
+            .rev()
+            .find(|record| record.timestamp.is_some_and(|t| t <= time)))
+    }
+
+    /// Returns all the deployment actions that happened between `start` and `end` (both included), indexed by contract id.
+    fn deployments_between(
+        &self,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        Ok(self
+            .get_all_deployment_history()?
+            .into_iter()
+            .filter_map(|(contract_id, history)| {
+                let records: Vec<_> = history
+                    .into_iter()
+                    .filter(|record| record.timestamp.is_some_and(|t| t >= start && t <= end))
+                    .collect();
+                (!records.is_empty()).then_some((contract_id, records))
+            })
+            .collect())
+    }
+
+    /// Returns the code id the contract was running before its last migration.
+    /// This is the code id to migrate back to in order to roll back that migration.
+    fn rollback_code_id(&self, contract_id: &str) -> Result<Option<u64>, CwEnvError> {
+        let history = self.get_deployment_history(contract_id)?;
+        let Some(last_migration) = history
+            .iter()
+            .rposition(|record| record.action == DeploymentAction::Migrate)
+        else {
+            return Ok(None);
+        };
+        Ok(history[..last_migration]
+            .iter()
+            .rev()
+            .find(|record| {
+                matches!(
+                    record.action,
+                    DeploymentAction::Instantiate | DeploymentAction::Migrate
+                )
+            })
+            .and_then(|record| record.code_id))
+    }
 }
 
+impl<S: StateInterface> DeploymentHistory for S {}
+
 impl<S: StateInterface> StateInterface for Rc<RefCell<S>> {
     fn get_address(&self, contract_id: &str) -> Result<Addr, CwEnvError> {
         (**self).borrow().get_address(contract_id)
@@ -78,6 +210,27 @@ impl<S: StateInterface> StateInterface for Rc<RefCell<S>> {
     fn remove_code_id(&mut self, contract_id: &str) {
         (**self).borrow_mut().remove_code_id(contract_id)
     }
+
+    fn record_deployment(
+        &mut self,
+        contract_id: &str,
+        record: DeploymentRecord,
+    ) -> Result<(), CwEnvError> {
+        (**self).borrow_mut().record_deployment(contract_id, record)
+    }
+
+    fn get_deployment_history(
+        &self,
+        contract_id: &str,
+    ) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        (**self).borrow().get_deployment_history(contract_id)
+    }
+
+    fn get_all_deployment_history(
+        &self,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        (**self).borrow().get_all_deployment_history()
+    }
 }
 
 impl<S: StateInterface> StateInterface for Rc<S> {
@@ -112,6 +265,27 @@ impl<S: StateInterface> StateInterface for Rc<S> {
     fn remove_code_id(&mut self, contract_id: &str) {
         (*Rc::make_mut(self)).remove_code_id(contract_id)
     }
+
+    fn record_deployment(
+        &mut self,
+        contract_id: &str,
+        record: DeploymentRecord,
+    ) -> Result<(), CwEnvError> {
+        (*Rc::make_mut(self)).record_deployment(contract_id, record)
+    }
+
+    fn get_deployment_history(
+        &self,
+        contract_id: &str,
+    ) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        (**self).get_deployment_history(contract_id)
+    }
+
+    fn get_all_deployment_history(
+        &self,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        (**self).get_all_deployment_history()
+    }
 }
 
 impl<S: StateInterface> StateInterface for Arc<S> {
@@ -146,4 +320,25 @@ impl<S: StateInterface> StateInterface for Arc<S> {
     fn remove_code_id(&mut self, contract_id: &str) {
         (*Arc::make_mut(self)).remove_code_id(contract_id)
     }
+
+    fn record_deployment(
+        &mut self,
+        contract_id: &str,
+        record: DeploymentRecord,
+    ) -> Result<(), CwEnvError> {
+        (*Arc::make_mut(self)).record_deployment(contract_id, record)
+    }
+
+    fn get_deployment_history(
+        &self,
+        contract_id: &str,
+    ) -> Result<Vec<DeploymentRecord>, CwEnvError> {
+        (**self).get_deployment_history(contract_id)
+    }
+
+    fn get_all_deployment_history(
+        &self,
+    ) -> Result<HashMap<String, Vec<DeploymentRecord>>, CwEnvError> {
+        (**self).get_all_deployment_history()
+    }
 }
@@ -0,0 +1,344 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use cosmwasm_std::Addr;
+use serde::Serialize;
+
+use crate::CwEnvError;
+
+use super::{ChainState, StateInterface};
+
+/// Raw storage (key, value) of contracts, indexed by contract id
+pub type StorageDump = BTreeMap<String, Vec<(Vec<u8>, Vec<u8>)>>;
+
+/// Environments that can read the raw storage of contracts.
+/// Used for storage snapshots ([`parse_storage`]) and diffs ([`StorageDiff`]) on all backends.
+pub trait StorageDumper: ChainState {
+    /// Raw storage (key, value) of a contract, sorted by key
+    fn dump_contract_storage(&self, address: &Addr) -> Result<Vec<(Vec<u8>, Vec<u8>)>, CwEnvError>;
+
+    /// Raw storage of the contracts registered in the state, indexed by contract id
+    fn storage_dump(&self) -> Result<StorageDump, CwEnvError> {
+        self.state()
+            .get_all_addresses()?
+            .into_iter()
+            .map(|(contract_id, address)| Ok((contract_id, self.dump_contract_storage(&address)?)))
+            .collect()
+    }
+
+    /// Run a transaction and return the changes it made to the storage of the contracts registered in the state.
+    fn storage_diff<T, E: Into<CwEnvError>>(
+        &self,
+        tx: impl FnOnce() -> Result<T, E>,
+    ) -> Result<(T, StorageDiff), CwEnvError> {
+        let before = self.storage_dump()?;
+        let result = tx().map_err(Into::into)?;
+        let after = self.storage_dump()?;
+        Ok((result, StorageDiff::new(&before, &after)))
+    }
+}
+
+/// Change of a storage key of a contract, with the key and values in readable form
+/// (see [`decode_storage_key`] and [`format_storage_value`])
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "change")]
+pub enum StorageChange {
+    Added {
+        key: String,
+        value: String,
+    },
+    Changed {
+        key: String,
+        old: String,
+        new: String,
+    },
+    Removed {
+        key: String,
+        old: String,
+    },
+}
+
+impl StorageChange {
+    pub fn key(&self) -> &str {
+        match self {
+            StorageChange::Added { key, .. }
+            | StorageChange::Changed { key, .. }
+            | StorageChange::Removed { key, .. } => key,
+        }
+    }
+}
+
+/// Storage changes of contracts between two [`StorageDump`]s, for instance before and after a transaction.
+///
+/// Printed, the diff lists the added (`+`), changed (`~`) and removed (`-`) keys of each contract:
+/// ```text
+/// counter_contract
+///   ~ state: {"count": 1} -> {"count": 2}
+///   + balances/juno1...: "100"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StorageDiff {
+    contracts: BTreeMap<String, Vec<StorageChange>>,
+}
+
+impl StorageDiff {
+    /// Compute the changes between two dumps. Contracts missing from a dump are considered empty.
+    pub fn new(before: &StorageDump, after: &StorageDump) -> Self {
+        let empty = vec![];
+        let mut contracts = BTreeMap::new();
+        for contract in before.keys().chain(after.keys()) {
+            if contracts.contains_key(contract) {
+                continue;
+            }
+            let old: BTreeMap<_, _> = before
+                .get(contract)
+                .unwrap_or(&empty)
+                .iter()
+                .cloned()
+                .collect();
+            let new: BTreeMap<_, _> = after
+                .get(contract)
+                .unwrap_or(&empty)
+                .iter()
+                .cloned()
+                .collect();
+
+            let mut keys: Vec<&Vec<u8>> = old.keys().chain(new.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            let changes: Vec<_> = keys
+                .into_iter()
+                .filter_map(|raw_key| {
+                    let key = decode_storage_key(raw_key);
+                    match (old.get(raw_key), new.get(raw_key)) {
+                        (None, Some(value)) => Some(StorageChange::Added {
+                            key,
+                            value: format_storage_value(value),
+                        }),
+                        (Some(old), None) => Some(StorageChange::Removed {
+                            key,
+                            old: format_storage_value(old),
+                        }),
+                        (Some(old), Some(new)) if old != new => Some(StorageChange::Changed {
+                            key,
+                            old: format_storage_value(old),
+                            new: format_storage_value(new),
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect();
+            contracts.insert(contract.clone(), changes);
+        }
+        contracts.retain(|_, changes| !changes.is_empty());
+        Self { contracts }
+    }
+
+    /// Changes of a contract
+    pub fn contract(&self, contract_id: &str) -> &[StorageChange] {
+        self.contracts
+            .get(contract_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Iterate over the changed contracts and their changes
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<StorageChange>)> {
+        self.contracts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.contracts.is_empty()
+    }
+
+    /// Serialize the diff to JSON
+    pub fn to_json(&self) -> Result<String, CwEnvError> {
+        Ok(serde_json::to_string_pretty(&self.contracts)?)
+    }
+}
+
+impl Display for StorageDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (contract, changes) in &self.contracts {
+            writeln!(f, "{contract}")?;
+            for change in changes {
+                match change {
+                    StorageChange::Added { key, value } => {
+                        writeln!(f, "  + {key}: {}", indent(value))?
+                    }
+                    StorageChange::Changed { key, old, new } => {
+                        writeln!(f, "  ~ {key}: {} -> {}", indent(old), indent(new))?
+                    }
+                    StorageChange::Removed { key, old } => {
+                        writeln!(f, "  - {key}: {}", indent(old))?
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Indent the lines of multi-line values below the change they belong to
+fn indent(value: &str) -> String {
+    value.replace('\n', "\n    ")
+}
+
+/// Decode a cw-storage-plus storage key into a readable path.
+///
+/// The length-prefixed namespaces and key elements are separated by `/`,
+/// for instance `balances/juno1...` for a `Map<&Addr, Uint128>` named `balances`, and `config` for an `Item` named `config`.
+/// Printable elements are kept as is, unsigned integers are decoded and other elements are hex-encoded.
+/// Integers with their most significant bit set are hex-encoded too: cw-storage-plus stores signed integers
+/// with their sign bit flipped, so they can't be told apart from large unsigned integers.
+pub fn decode_storage_key(key: &[u8]) -> String {
+    let mut elements = vec![];
+    let mut rest = key;
+    // All the elements but the last one are prefixed with their length as a 2 bytes big endian integer
+    while rest.len() > 2 {
+        let len = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+        if len == 0 || 2 + len >= rest.len() {
+            break;
+        }
+        elements.push(format_key_element(&rest[2..2 + len]));
+        rest = &rest[2 + len..];
+    }
+    if !rest.is_empty() {
+        elements.push(format_key_element(rest));
+    }
+    elements.join("/")
+}
+
+fn format_key_element(element: &[u8]) -> String {
+    if let Ok(str) = std::str::from_utf8(element) {
+        if str.chars().all(|c| !c.is_control()) {
+            return str.to_string();
+        }
+    }
+    // cw-storage-plus encodes unsigned integers as big endian bytes
+    match element.len() {
+        2 | 4 | 8 | 16 if element[0] & 0x80 == 0 => {
+            let mut bytes = [0u8; 16];
+            bytes[16 - element.len()..].copy_from_slice(element);
+            u128::from_be_bytes(bytes).to_string()
+        }
+        _ => to_hex(element),
+    }
+}
+
+/// Format a storage value: JSON values are pretty-printed, other values are printed as strings or hex-encoded.
+pub fn format_storage_value(value: &[u8]) -> String {
+    if let Ok(json) = serde_json::from_slice::<serde_json::Value>(value) {
+        if let Ok(pretty) = serde_json::to_string_pretty(&json) {
+            return pretty;
+        }
+    }
+    match std::str::from_utf8(value) {
+        Ok(str) if str.chars().all(|c| !c.is_control()) => str.to_string(),
+        _ => to_hex(value),
+    }
+}
+
+/// Parse the storage of a contract into a stable, readable snapshot:
+/// keys are decoded with [`decode_storage_key`] and JSON values are parsed, other values are kept as strings.
+pub fn parse_storage(storage: &[(Vec<u8>, Vec<u8>)]) -> BTreeMap<String, serde_json::Value> {
+    storage
+        .iter()
+        .map(|(key, value)| {
+            let value = serde_json::from_slice(value)
+                .unwrap_or_else(|_| serde_json::Value::String(format_storage_value(value)));
+            (decode_storage_key(key), value)
+        })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{hex}")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn map_key(namespace: &str, key: &[u8]) -> Vec<u8> {
+        [
+            (namespace.len() as u16).to_be_bytes().as_slice(),
+            namespace.as_bytes(),
+            key,
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn decodes_keys() {
+        assert_eq!(decode_storage_key(b"config"), "config");
+        assert_eq!(
+            decode_storage_key(&map_key("balances", b"juno1addr")),
+            "balances/juno1addr"
+        );
+        assert_eq!(
+            decode_storage_key(&map_key("proposals", &42u64.to_be_bytes())),
+            "proposals/42"
+        );
+        // Signed integer keys, 42i64 here, have their sign bit flipped
+        assert_eq!(
+            decode_storage_key(&map_key("heights", &(42u64 | 1 << 63).to_be_bytes())),
+            "heights/0x800000000000002a"
+        );
+        assert_eq!(decode_storage_key(&[0xff, 0x01, 0x02]), "0xff0102");
+    }
+
+    #[test]
+    fn diffs_storage() {
+        let before = StorageDump::from([(
+            "counter".to_string(),
+            vec![
+                (b"state".to_vec(), br#"{"count":1}"#.to_vec()),
+                (b"owner".to_vec(), br#""juno1owner""#.to_vec()),
+            ],
+        )]);
+        let after = StorageDump::from([(
+            "counter".to_string(),
+            vec![
+                (b"state".to_vec(), br#"{"count":2}"#.to_vec()),
+                (map_key("users", b"juno1user"), b"true".to_vec()),
+            ],
+        )]);
+
+        let diff = StorageDiff::new(&before, &after);
+        assert_eq!(
+            diff.contract("counter"),
+            &[
+                StorageChange::Added {
+                    key: "users/juno1user".to_string(),
+                    value: "true".to_string()
+                },
+                StorageChange::Removed {
+                    key: "owner".to_string(),
+                    old: "\"juno1owner\"".to_string()
+                },
+                StorageChange::Changed {
+                    key: "state".to_string(),
+                    old: "{\n  \"count\": 1\n}".to_string(),
+                    new: "{\n  \"count\": 2\n}".to_string()
+                },
+            ]
+        );
+        assert!(StorageDiff::new(&after, &after).is_empty());
+    }
+
+    #[test]
+    fn parses_storage() {
+        let storage = parse_storage(&[
+            (b"state".to_vec(), br#"{"count":1}"#.to_vec()),
+            (map_key("names", &7u32.to_be_bytes()), b"raw".to_vec()),
+        ]);
+        assert_eq!(
+            storage,
+            BTreeMap::from([
+                ("names/7".to_string(), serde_json::json!("raw")),
+                ("state".to_string(), serde_json::json!({ "count": 1 })),
+            ])
+        );
+    }
+}
@@ -44,6 +44,12 @@ pub enum CwEnvError {
     StdErr(String),
     #[error("Environment variable not defined {0}")]
     EnvVarNotPresentNamed(String),
+    #[error("Contract {0} depends on {1}, which is not part of the deployment plan")]
+    UnknownPlanDependency(String, String),
+    #[error("Deployment plan has a dependency cycle involving {0}")]
+    PlanDependencyCycle(String),
+    #[error("Gas usage regressed compared to the baseline:\n{0}")]
+    GasRegression(String),
 }
 
 impl CwEnvError {
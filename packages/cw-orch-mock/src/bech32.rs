@@ -8,7 +8,14 @@ use cw_orch_core::{
 };
 use cw_utils::NativeBalance;
 
-use crate::{queriers::bank::MockBankQuerier, MockBase, MockBech32, MockState};
+use crate::{
+    core::{CustomModule, FailingCustom},
+    gov::MockGov,
+    queriers::bank::MockBankQuerier,
+    stargate::MockStargate,
+    wasm::WasmExecution,
+    MockBase, MockBech32, MockState,
+};
 
 impl MockBase<MockApiBech32, MockState> {
     /// Create a mock environment with the default mock state.
@@ -27,7 +34,7 @@ impl MockBase<MockApiBech32, MockState> {
     }
 }
 
-impl<S: StateInterface> MockBase<MockApiBech32, S> {
+impl<S: StateInterface, C: CustomModule> MockBase<MockApiBech32, S, C> {
     pub fn addr_make(&self, account_name: impl Into<String>) -> Addr {
         self.app.borrow().api().addr_make(&account_name.into())
     }
@@ -43,7 +50,7 @@ impl<S: StateInterface> MockBase<MockApiBech32, S> {
     }
 }
 
-impl<S: StateInterface> MockBase<MockApi, S> {
+impl<S: StateInterface, C: CustomModule> MockBase<MockApi, S, C> {
     pub fn addr_make(&self, account_name: impl Into<String>) -> Addr {
         self.app.borrow().api().addr_make(&account_name.into())
     }
@@ -70,21 +77,39 @@ impl<S: StateInterface> MockBase<MockApiBech32, S> {
     /// Create a mock environment with a custom mock state.
     /// The state is customizable by implementing the `StateInterface` trait on a custom struct and providing it on the custom constructor.
     pub fn new_custom(prefix: &'static str, custom_state: S) -> Self {
+        Self::new_with_custom_module(prefix, custom_state, FailingCustom::new())
+    }
+}
+
+impl<S: StateInterface, C: CustomModule> MockBase<MockApiBech32, S, C> {
+    /// Create a mock environment with a custom mock state and a module handling the custom messages and queries of the chain.
+    pub fn new_with_custom_module(prefix: &'static str, custom_state: S, custom_module: C) -> Self {
         let state = Rc::new(RefCell::new(custom_state));
+        let stargate = MockStargate::default();
+        MockGov::register_stargate_handlers(&stargate);
         let app = Rc::new(RefCell::new(
             AppBuilder::new_custom()
                 .with_api(MockApiBech32::new(prefix))
+                .with_custom(custom_module)
+                .with_gov(MockGov)
+                .with_stargate(stargate.clone())
                 .build(|_, _, _| {}),
         ));
 
         // We create an address internally
         let sender = app.borrow().api().addr_make("sender");
 
-        Self { sender, state, app }
+        Self {
+            sender,
+            state,
+            app,
+            stargate,
+            wasm: WasmExecution::default(),
+        }
     }
 }
 
-impl<S: StateInterface> MockBech32<S> {
+impl<S: StateInterface, C: CustomModule> MockBase<MockApiBech32, S, C> {
     /// Set the bank balance of an address.
     pub fn set_balance(
         &self,
@@ -151,8 +176,8 @@ impl<S: StateInterface> MockBech32<S> {
     }
 }
 
-impl<S: StateInterface> BankSetter for MockBech32<S> {
-    type T = MockBankQuerier<MockApiBech32>;
+impl<S: StateInterface, C: CustomModule> BankSetter for MockBase<MockApiBech32, S, C> {
+    type T = MockBankQuerier<MockApiBech32, C>;
 
     fn set_balance(
         &mut self,
@@ -2,32 +2,61 @@ use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 use cosmwasm_std::{
     testing::{MockApi, MockStorage},
-    to_json_binary, Addr, Api, BankMsg, Binary, CosmosMsg, Empty, Event, WasmMsg,
+    to_json_binary, Addr, Api, BankMsg, Binary, CosmosMsg, CustomMsg, CustomQuery, Empty, Event,
+    WasmMsg,
 };
 use cw_multi_test::{
     ibc::IbcSimpleModule, App, AppResponse, BankKeeper, Contract, DistributionKeeper, Executor,
-    FailingModule, GovFailingModule, MockApiBech32, StakeKeeper, StargateFailing, WasmKeeper,
+    FailingModule, MockApiBech32, Module, StakeKeeper, WasmKeeper,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 
 use super::state::MockState;
+use crate::{
+    gov::MockGov,
+    stargate::MockStargate,
+    wasm::{ContractRef, WasmExecution},
+};
 use cw_orch_core::{
     contract::interface_traits::Uploadable,
-    environment::{AccessConfig, ChainState, IndexResponse, StateInterface, TxHandler},
+    environment::{
+        AccessConfig, ChainState, GasEntryPoint, IndexResponse, StateInterface, TxHandler,
+    },
     CwEnvError,
 };
 
-pub type MockApp<A = MockApi> = App<
+/// Module handling the custom messages and queries of a [`MockBase`] environment.
+/// Implemented for every cw-multi-test [`Module`] with custom message and query types.
+pub trait CustomModule:
+    Module<
+        ExecT: CustomMsg + DeserializeOwned + 'static,
+        QueryT: CustomQuery + DeserializeOwned + 'static,
+    > + 'static
+{
+}
+
+impl<T> CustomModule for T where
+    T: Module<
+            ExecT: CustomMsg + DeserializeOwned + 'static,
+            QueryT: CustomQuery + DeserializeOwned + 'static,
+        > + 'static
+{
+}
+
+/// Custom module of environments without custom messages and queries
+pub type FailingCustom = FailingModule<Empty, Empty, Empty>;
+
+pub type MockApp<A = MockApi, C = FailingCustom> = App<
     BankKeeper,
     A,
     MockStorage,
-    FailingModule<Empty, Empty, Empty>,
-    WasmKeeper<Empty, Empty>,
+    C,
+    WasmKeeper<<C as Module>::ExecT, <C as Module>::QueryT>,
     StakeKeeper,
     DistributionKeeper,
     IbcSimpleModule,
-    GovFailingModule,
-    StargateFailing,
+    MockGov,
+    MockStargate,
 >;
 
 /// Wrapper around a cw-multi-test [`App`](cw_multi_test::App) backend.
@@ -67,29 +96,58 @@ pub type MockApp<A = MockApi> = App<
 ///
 /// let mock: Mock = Mock::new_custom("sender", CustomState::new());
 /// ```
-pub struct MockBase<A: Api = MockApi, S: StateInterface = MockState> {
+///
+/// ## Example with custom messages and queries
+/// Chains with custom bindings are emulated by providing a cw-multi-test [`Module`] handling the custom messages and queries.
+/// ```
+/// use cosmwasm_std::{CustomMsg, CustomQuery, Empty};
+/// use cw_multi_test::FailingModule;
+/// use cw_orch_mock::{Mock, MockState};
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// # pub struct ChainMsg {}
+/// # impl CustomMsg for ChainMsg {}
+/// # #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// # pub struct ChainQuery {}
+/// # impl CustomQuery for ChainQuery {}
+///
+/// // Replace the failing module with your own implementation of the chain module
+/// let custom_module = FailingModule::<ChainMsg, ChainQuery, Empty>::new();
+/// let mock = Mock::new_with_custom_module("sender", MockState::new(), custom_module);
+/// ```
+pub struct MockBase<
+    A: Api = MockApi,
+    S: StateInterface = MockState,
+    C: CustomModule = FailingCustom,
+> {
     /// Address used for the operations.
     pub sender: Addr,
     /// Inner mutable state storage for contract addresses and code-ids
     pub state: Rc<RefCell<S>>,
     /// Inner mutable cw-multi-test app backend
-    pub app: Rc<RefCell<MockApp<A>>>,
+    pub app: Rc<RefCell<MockApp<A, C>>>,
+    /// Stargate/Any message handlers of the app, see [`MockBase::register_stargate_exec`]
+    pub stargate: MockStargate,
+    /// Wasm execution and gas metering of the contracts, see [`MockBase::gas_report`]
+    pub wasm: WasmExecution,
 }
 
 pub type Mock<S = MockState> = MockBase<MockApi, S>;
 pub type MockBech32<S = MockState> = MockBase<MockApiBech32, S>;
 
-impl<A: Api, S: StateInterface> Clone for MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> Clone for MockBase<A, S, C> {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
             state: self.state.clone(),
             app: self.app.clone(),
+            stargate: self.stargate.clone(),
+            wasm: self.wasm.clone(),
         }
     }
 }
 
-impl<A: Api> MockBase<A, MockState> {
+impl<A: Api, C: CustomModule> MockBase<A, MockState, C> {
     pub fn with_chain_id(&mut self, chain_id: &str) {
         self.state.borrow_mut().set_chain_id(chain_id);
         self.app
@@ -98,13 +156,13 @@ impl<A: Api> MockBase<A, MockState> {
     }
 }
 
-impl<A: Api, S: StateInterface> MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> MockBase<A, S, C> {
     /// Upload a custom contract wrapper.
     /// Support for this is limited.
     pub fn upload_custom(
         &self,
         contract_id: &str,
-        wrapper: Box<dyn Contract<Empty, Empty>>,
+        wrapper: Box<dyn Contract<C::ExecT, C::QueryT>>,
     ) -> Result<AppResponse, CwEnvError> {
         let code_id = self
             .app
@@ -122,7 +180,7 @@ impl<A: Api, S: StateInterface> MockBase<A, S> {
         Ok(resp)
     }
 }
-impl<A: Api, S: StateInterface> ChainState for MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> ChainState for MockBase<A, S, C> {
     type Out = Rc<RefCell<S>>;
 
     fn state(&self) -> Self::Out {
@@ -131,10 +189,10 @@ impl<A: Api, S: StateInterface> ChainState for MockBase<A, S> {
 }
 
 // Execute on the test chain, returns test response type
-impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> TxHandler for MockBase<A, S, C> {
     type Response = AppResponse;
     type Error = CwEnvError;
-    type ContractSource = Box<dyn Contract<Empty, Empty>>;
+    type ContractSource = Box<dyn Contract<C::ExecT, C::QueryT>>;
     type Sender = Addr;
 
     fn sender(&self) -> &Self::Sender {
@@ -150,18 +208,25 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
     }
 
     fn upload<T: Uploadable>(&self, _contract: &T) -> Result<Self::Response, CwEnvError> {
-        let code_id = self
-            .app
-            .borrow_mut()
-            .store_code_with_creator(self.sender_addr(), T::wrapper());
-        // add contract code_id to events manually
-        let mut event = Event::new("store_code");
-        event = event.add_attribute("code_id", code_id.to_string());
-        let resp = AppResponse {
-            events: vec![event],
-            ..Default::default()
-        };
-        Ok(resp)
+        let (code, upload_gas) = self.contract_code::<T>()?;
+        self.metered_tx(|app| {
+            let code_id = app.store_code_with_creator(self.sender_addr(), code);
+            if let Some(gas) = upload_gas {
+                self.wasm.record(
+                    ContractRef::CodeId(code_id),
+                    GasEntryPoint::Upload,
+                    String::new(),
+                    gas,
+                );
+            }
+            // add contract code_id to events manually
+            let mut event = Event::new("store_code");
+            event = event.add_attribute("code_id", code_id.to_string());
+            Ok(AppResponse {
+                events: vec![event],
+                ..Default::default()
+            })
+        })
     }
 
     fn execute<E: Serialize + Debug>(
@@ -170,15 +235,14 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
         coins: &[cosmwasm_std::Coin],
         contract_address: &Addr,
     ) -> Result<Self::Response, CwEnvError> {
-        self.app
-            .borrow_mut()
-            .execute_contract(
+        self.metered_tx(|app| {
+            app.execute_contract(
                 self.sender.clone(),
                 contract_address.to_owned(),
                 exec_msg,
                 coins,
             )
-            .map_err(From::from)
+        })
     }
 
     fn instantiate<I: Serialize + Debug>(
@@ -196,16 +260,7 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
             msg: to_json_binary(init_msg)?,
             funds: coins.to_vec(),
         };
-        let app = self
-            .app
-            .borrow_mut()
-            .execute(self.sender.clone(), CosmosMsg::Wasm(msg))?;
-
-        let resp = AppResponse {
-            events: app.events,
-            data: app.data,
-        };
-        Ok(resp)
+        self.metered_tx(|app| app.execute(self.sender.clone(), CosmosMsg::Wasm(msg)))
     }
 
     fn instantiate2<I: Serialize + Debug>(
@@ -225,17 +280,7 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
             funds: coins.to_vec(),
             salt,
         };
-
-        let app = self
-            .app
-            .borrow_mut()
-            .execute(self.sender.clone(), CosmosMsg::Wasm(msg))?;
-
-        let resp = AppResponse {
-            events: app.events,
-            data: app.data,
-        };
-        Ok(resp)
+        self.metered_tx(|app| app.execute(self.sender.clone(), CosmosMsg::Wasm(msg)))
     }
 
     fn migrate<M: Serialize + Debug>(
@@ -244,15 +289,14 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
         new_code_id: u64,
         contract_address: &Addr,
     ) -> Result<Self::Response, CwEnvError> {
-        self.app
-            .borrow_mut()
-            .migrate_contract(
+        self.metered_tx(|app| {
+            app.migrate_contract(
                 self.sender.clone(),
                 contract_address.clone(),
                 migrate_msg,
                 new_code_id,
             )
-            .map_err(From::from)
+        })
     }
 
     fn upload_with_access_config<T: Uploadable>(
@@ -269,9 +313,8 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
         receiver: &Addr,
         amount: &[cosmwasm_std::Coin],
     ) -> Result<Self::Response, Self::Error> {
-        self.app
-            .borrow_mut()
-            .execute(
+        self.metered_tx(|app| {
+            app.execute(
                 self.sender.clone(),
                 BankMsg::Send {
                     to_address: receiver.to_string(),
@@ -279,7 +322,7 @@ impl<A: Api, S: StateInterface> TxHandler for MockBase<A, S> {
                 }
                 .into(),
             )
-            .map_err(From::from)
+        })
     }
 }
 
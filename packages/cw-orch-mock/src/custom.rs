@@ -0,0 +1,115 @@
+//! Support of contracts in mock environments with custom messages and queries.
+use std::any::{type_name, Any, TypeId};
+
+use cosmwasm_std::{
+    Binary, Checksum, CustomMsg, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo, Reply,
+    Response,
+};
+use cw_multi_test::{AnyResult, Contract};
+use cw_orch_core::{contract::interface_traits::Uploadable, CwEnvError};
+
+/// Returns the wrapper of the contract for an environment with the given custom types.
+///
+/// The [`Uploadable::custom_wrapper`] is used for contracts with custom types (see [`ContractCustomTypes`](cw_orch_core::contract::interface_traits::ContractCustomTypes)),
+/// otherwise the [`Uploadable::wrapper`] is adapted to the custom types of the environment.
+pub(crate) fn contract_wrapper<T, ExecC, QueryC>(
+) -> Result<Box<dyn Contract<ExecC, QueryC>>, CwEnvError>
+where
+    T: Uploadable,
+    ExecC: CustomMsg + 'static,
+    QueryC: CustomQuery + 'static,
+{
+    // `TxHandler::upload` accepts any contract, the types of the contract are matched with the ones of the environment here
+    if is::<T::ExecC, Empty>() && is::<T::QueryC, Empty>() {
+        let wrapper: Box<dyn Any> = Box::new(T::wrapper());
+        return match wrapper.downcast::<Box<dyn Contract<ExecC, QueryC>>>() {
+            // The environment doesn't use custom types
+            Ok(wrapper) => Ok(*wrapper),
+            Err(wrapper) => {
+                let wrapper = wrapper
+                    .downcast::<Box<dyn Contract<Empty, Empty>>>()
+                    .expect("Uploadable::wrapper returns an Empty contract");
+                Ok(Box::new(EmptyContract(*wrapper)))
+            }
+        };
+    }
+
+    let wrapper: Box<dyn Any> = Box::new(T::custom_wrapper());
+    wrapper
+        .downcast::<Box<dyn Contract<ExecC, QueryC>>>()
+        .map(|wrapper| *wrapper)
+        .map_err(|_| {
+            CwEnvError::StdErr(format!(
+                "The contract uses custom types ({}, {}) that don't match the ones of the environment ({}, {})",
+                type_name::<T::ExecC>(),
+                type_name::<T::QueryC>(),
+                type_name::<ExecC>(),
+                type_name::<QueryC>()
+            ))
+        })
+}
+
+fn is<T: 'static, U: 'static>() -> bool {
+    TypeId::of::<T>() == TypeId::of::<U>()
+}
+
+/// Runs a contract without custom messages and queries in an environment with custom types.
+///
+/// IBC entry points of the wrapped contract are not forwarded.
+struct EmptyContract(Box<dyn Contract<Empty, Empty>>);
+
+fn to_custom<ExecC>(response: Response<Empty>) -> Response<ExecC> {
+    response
+        .change_custom()
+        .expect("Empty responses don't contain custom messages")
+}
+
+impl<ExecC, QueryC> Contract<ExecC, QueryC> for EmptyContract
+where
+    ExecC: CustomMsg,
+    QueryC: CustomQuery,
+{
+    fn execute(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        self.0
+            .execute(deps.into_empty(), env, info, msg)
+            .map(to_custom)
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        self.0
+            .instantiate(deps.into_empty(), env, info, msg)
+            .map(to_custom)
+    }
+
+    fn query(&self, deps: Deps<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Binary> {
+        self.0.query(deps.into_empty(), env, msg)
+    }
+
+    fn sudo(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        self.0.sudo(deps.into_empty(), env, msg).map(to_custom)
+    }
+
+    fn reply(&self, deps: DepsMut<QueryC>, env: Env, msg: Reply) -> AnyResult<Response<ExecC>> {
+        self.0.reply(deps.into_empty(), env, msg).map(to_custom)
+    }
+
+    fn migrate(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        self.0.migrate(deps.into_empty(), env, msg).map(to_custom)
+    }
+
+    fn checksum(&self) -> Option<Checksum> {
+        self.0.checksum()
+    }
+}
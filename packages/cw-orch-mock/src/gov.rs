@@ -0,0 +1,902 @@
+//! Emulation of the governance module for the [`MockBase`] environment.
+//!
+//! Proposals are submitted with [`MockBase::submit_proposal`] and enter the voting period once their deposit reaches
+//! the [`GovParams::min_deposit`]. Votes are cast with [`MockBase::vote`] or by contracts with `CosmosMsg::Gov` messages.
+//! Contracts submit proposals and deposit with the `/cosmos.gov.v1.MsgSubmitProposal` and `/cosmos.gov.v1.MsgDeposit` Stargate messages,
+//! the messages of their proposals are executed with the Stargate handlers of the app.
+//!
+//! Proposals are tallied when the block time reaches the end of their voting period, which can be done with
+//! [`MockBase::end_voting_period`] or by waiting blocks. The voting power of an account is the amount of tokens it delegated.
+//! When none of the voters delegated tokens, every voter has the same voting power.
+//! The messages of passed proposals are executed by the [`MockBase::gov_address`].
+//!
+//! ## Example
+//! ```
+//! use cosmwasm_std::{coins, BankMsg, VoteOption};
+//! use cw_orch_core::environment::{BankSetter, ProposalStatus};
+//! use cw_orch_mock::Mock;
+//!
+//! let mut mock = Mock::new("sender");
+//! let gov = mock.gov_address();
+//! mock.set_balance(&gov, coins(100, "ujuno")).unwrap();
+//!
+//! let recipient = mock.addr_make("recipient");
+//! let proposal_id = mock
+//!     .submit_proposal(
+//!         vec![BankMsg::Send { to_address: recipient.to_string(), amount: coins(100, "ujuno") }.into()],
+//!         "Community spend",
+//!         "Send tokens to the recipient",
+//!         &[],
+//!     )
+//!     .unwrap();
+//! mock.vote(proposal_id, VoteOption::Yes).unwrap();
+//!
+//! let proposal = mock.end_voting_period(proposal_id).unwrap();
+//! assert_eq!(proposal.status, ProposalStatus::Passed);
+//! ```
+use std::str::FromStr;
+
+use cosmos_sdk_proto::cosmos::{
+    base::v1beta1::Coin as ProtoCoin,
+    gov::v1::{MsgDeposit, MsgSubmitProposal, MsgSubmitProposalResponse},
+};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, CanonicalAddr, Coin,
+    CosmosMsg, CustomMsg, CustomQuery, Decimal, Empty, Event, GovMsg, Order, Querier,
+    QuerierWrapper, Storage, Uint128, VoteOption,
+};
+use cw_multi_test::{AnyResult, AppResponse, CosmosRouter, Executor, Gov, Module};
+use cw_orch_core::{
+    environment::{
+        GovDeposit, GovProposal, GovVote, GovVoteOption, ProposalStatus, StateInterface,
+        TallyResult,
+    },
+    CwEnvError,
+};
+use cw_storage_plus::{Item, Map};
+use prost::Message;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    core::CustomModule,
+    stargate::{MockStargate, StargateExecContext},
+    MockBase,
+};
+
+/// Parameters of the governance module
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovParams {
+    /// Deposit required for a proposal to enter the voting period
+    pub min_deposit: Vec<Coin>,
+    /// Duration of the deposit period in seconds
+    pub max_deposit_period: u64,
+    /// Duration of the voting period in seconds
+    pub voting_period: u64,
+    /// Minimum proportion of yes votes (excluding abstain) for a proposal to pass
+    pub threshold: Decimal,
+    /// Minimum proportion of no with veto votes for a proposal to be vetoed
+    pub veto_threshold: Decimal,
+}
+
+impl Default for GovParams {
+    fn default() -> Self {
+        Self {
+            min_deposit: vec![],
+            max_deposit_period: 2 * 24 * 3600,
+            voting_period: 2 * 24 * 3600,
+            threshold: Decimal::percent(50),
+            veto_threshold: Decimal::permille(334),
+        }
+    }
+}
+
+const PARAMS: Item<GovParams> = Item::new("gov_params");
+const NEXT_PROPOSAL_ID: Item<u64> = Item::new("gov_next_proposal_id");
+const PROPOSALS: Map<u64, GovProposal> = Map::new("gov_proposals");
+/// JSON encoded messages of the proposals
+const PROPOSAL_MESSAGES: Map<u64, Binary> = Map::new("gov_proposal_messages");
+const VOTES: Map<(u64, &str), GovVote> = Map::new("gov_votes");
+const DEPOSITS: Map<(u64, &str), Vec<Coin>> = Map::new("gov_deposits");
+
+/// Voting power of every voter when none of them delegated tokens
+const EQUAL_VOTING_POWER: Uint128 = Uint128::new(1_000_000);
+
+const MSG_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+const MSG_DEPOSIT_TYPE_URL: &str = "/cosmos.gov.v1.MsgDeposit";
+
+/// Governance module of the mock app, records the votes sent with `CosmosMsg::Gov` messages
+#[derive(Clone, Copy, Default)]
+pub struct MockGov;
+
+impl Module for MockGov {
+    type ExecT = GovMsg;
+    type QueryT = Empty;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: GovMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let (proposal_id, options) = match msg {
+            GovMsg::Vote {
+                proposal_id,
+                option,
+            } => (
+                proposal_id,
+                vec![GovVoteOption {
+                    option,
+                    weight: Decimal::one(),
+                }],
+            ),
+            GovMsg::VoteWeighted {
+                proposal_id,
+                options,
+            } => (
+                proposal_id,
+                options
+                    .into_iter()
+                    .map(|o| GovVoteOption {
+                        option: o.option,
+                        weight: o.weight,
+                    })
+                    .collect(),
+            ),
+        };
+
+        let total_weight = options
+            .iter()
+            .map(|o| o.weight)
+            .fold(Decimal::zero(), |a, b| a + b);
+        if total_weight != Decimal::one() {
+            anyhow::bail!("the weights of the vote options must sum up to 1, got {total_weight}");
+        }
+
+        let proposal = load_proposal(storage, proposal_id)?;
+        let voting_open = proposal.status == ProposalStatus::VotingPeriod
+            && proposal
+                .voting_end_time
+                .is_some_and(|end_time| block.time < end_time);
+        if !voting_open {
+            anyhow::bail!("proposal {proposal_id} is not in its voting period");
+        }
+
+        VOTES.save(
+            storage,
+            (proposal_id, sender.as_str()),
+            &GovVote {
+                proposal_id,
+                voter: sender.to_string(),
+                options,
+            },
+        )?;
+
+        Ok(AppResponse {
+            events: vec![Event::new("proposal_vote")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("voter", sender)],
+            data: None,
+        })
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        _request: Empty,
+    ) -> AnyResult<Binary> {
+        anyhow::bail!("gov queries are not supported, use the GovQuerier of the environment")
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        anyhow::bail!("sudo is not supported by the gov module")
+    }
+}
+
+impl Gov for MockGov {}
+
+impl MockGov {
+    /// Registers the handlers of the `MsgSubmitProposal` and `MsgDeposit` messages sent by contracts
+    pub(crate) fn register_stargate_handlers(stargate: &MockStargate) {
+        stargate.register_exec(MSG_SUBMIT_PROPOSAL_TYPE_URL, |ctx, sender, value| {
+            submit_proposal_msg(ctx, sender, MsgSubmitProposal::decode(value.as_slice())?)
+        });
+        stargate.register_exec(MSG_DEPOSIT_TYPE_URL, |ctx, sender, value| {
+            let msg = MsgDeposit::decode(value.as_slice())?;
+            if msg.depositor != sender.as_str() {
+                anyhow::bail!("the depositor {} is not the sender {sender}", msg.depositor);
+            }
+            deposit_msg(ctx, sender, msg.proposal_id, &parse_coins(msg.amount)?)
+        });
+    }
+}
+
+fn submit_proposal_msg(
+    ctx: &mut StargateExecContext,
+    sender: Addr,
+    msg: MsgSubmitProposal,
+) -> AnyResult<AppResponse> {
+    if msg.proposer != sender.as_str() {
+        anyhow::bail!("the proposer {} is not the sender {sender}", msg.proposer);
+    }
+    // The messages of the proposal are executed with the Stargate handlers of the app
+    #[allow(deprecated)]
+    let messages: Vec<CosmosMsg> = msg
+        .messages
+        .into_iter()
+        .map(|any| CosmosMsg::Stargate {
+            type_url: any.type_url,
+            value: any.value.into(),
+        })
+        .collect();
+    let proposal_id = create_proposal(
+        ctx.storage,
+        ctx.block,
+        &sender,
+        msg.title,
+        msg.summary,
+        to_json_binary(&messages)?,
+    )?;
+
+    let mut response = deposit_msg(ctx, sender, proposal_id, &parse_coins(msg.initial_deposit)?)?;
+    response.events.insert(
+        0,
+        Event::new("submit_proposal").add_attribute("proposal_id", proposal_id.to_string()),
+    );
+    response.data = Some(
+        MsgSubmitProposalResponse { proposal_id }
+            .encode_to_vec()
+            .into(),
+    );
+    Ok(response)
+}
+
+/// Records a deposit and sends the deposited tokens to the gov module account
+fn deposit_msg(
+    ctx: &mut StargateExecContext,
+    depositor: Addr,
+    proposal_id: u64,
+    amount: &[Coin],
+) -> AnyResult<AppResponse> {
+    add_deposit(ctx.storage, ctx.block, proposal_id, &depositor, amount)?;
+
+    let mut response = if amount.is_empty() {
+        AppResponse::default()
+    } else {
+        ctx.bank(
+            depositor.clone(),
+            BankMsg::Send {
+                to_address: gov_address(ctx.api).to_string(),
+                amount: amount.to_vec(),
+            },
+        )?
+    };
+    response.events.push(
+        Event::new("proposal_deposit")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("depositor", depositor),
+    );
+    Ok(response)
+}
+
+/// Stores a new proposal in its deposit period and returns its id.
+/// `messages` are the JSON encoded messages executed if the proposal passes.
+fn create_proposal(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposer: &Addr,
+    title: String,
+    summary: String,
+    messages: Binary,
+) -> AnyResult<u64> {
+    let params = PARAMS.may_load(storage)?.unwrap_or_default();
+    let proposal_id = NEXT_PROPOSAL_ID.may_load(storage)?.unwrap_or(1);
+    NEXT_PROPOSAL_ID.save(storage, &(proposal_id + 1))?;
+    PROPOSALS.save(
+        storage,
+        proposal_id,
+        &GovProposal {
+            id: proposal_id,
+            title,
+            summary,
+            proposer: proposer.to_string(),
+            messages: vec![],
+            status: ProposalStatus::DepositPeriod,
+            total_deposit: vec![],
+            submit_time: block.time,
+            deposit_end_time: block.time.plus_seconds(params.max_deposit_period),
+            voting_start_time: None,
+            voting_end_time: None,
+            final_tally_result: None,
+        },
+    )?;
+    PROPOSAL_MESSAGES.save(storage, proposal_id, &messages)?;
+    Ok(proposal_id)
+}
+
+/// Records a deposit on a proposal, which enters the voting period once its total deposit reaches the minimum deposit.
+/// The deposited tokens are sent to the gov module account by the caller.
+fn add_deposit(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    proposal_id: u64,
+    depositor: &Addr,
+    amount: &[Coin],
+) -> AnyResult<()> {
+    let params = PARAMS.may_load(storage)?.unwrap_or_default();
+    let mut proposal = load_proposal(storage, proposal_id)?;
+    if !matches!(
+        proposal.status,
+        ProposalStatus::DepositPeriod | ProposalStatus::VotingPeriod
+    ) {
+        anyhow::bail!("proposal {proposal_id} doesn't accept deposits anymore");
+    }
+
+    let key = (proposal_id, depositor.as_str());
+    let mut deposit = DEPOSITS.may_load(storage, key)?.unwrap_or_default();
+    add_coins(&mut deposit, amount);
+    DEPOSITS.save(storage, key, &deposit)?;
+
+    add_coins(&mut proposal.total_deposit, amount);
+    if proposal.status == ProposalStatus::DepositPeriod
+        && has_min_deposit(&proposal.total_deposit, &params.min_deposit)
+    {
+        proposal.status = ProposalStatus::VotingPeriod;
+        proposal.voting_start_time = Some(block.time);
+        proposal.voting_end_time = Some(block.time.plus_seconds(params.voting_period));
+    }
+    PROPOSALS.save(storage, proposal_id, &proposal)?;
+    Ok(())
+}
+
+/// Address of the governance module account
+fn gov_address(api: &dyn Api) -> Addr {
+    let hash = Sha256::digest(b"gov");
+    api.addr_humanize(&CanonicalAddr::from(&hash[..20]))
+        .expect("the gov module address is a valid canonical address")
+}
+
+fn parse_coins(coins: Vec<ProtoCoin>) -> AnyResult<Vec<Coin>> {
+    coins
+        .into_iter()
+        .map(|coin| Ok(Coin::new(Uint128::from_str(&coin.amount)?, coin.denom)))
+        .collect()
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> MockBase<A, S, C> {
+    /// Address of the governance module account, executing the messages of passed proposals
+    pub fn gov_address(&self) -> Addr {
+        gov_address(self.app.borrow().api())
+    }
+
+    /// Returns the parameters of the governance module
+    pub fn gov_params(&self) -> Result<GovParams, CwEnvError> {
+        Ok(PARAMS
+            .may_load(self.app.borrow().storage())?
+            .unwrap_or_default())
+    }
+
+    /// Sets the parameters of the governance module, used for the proposals submitted afterwards
+    pub fn set_gov_params(&self, params: &GovParams) -> Result<(), CwEnvError> {
+        PARAMS.save(self.app.borrow_mut().storage_mut(), params)?;
+        Ok(())
+    }
+
+    /// Submits a proposal executing `messages` if it passes and returns its id.
+    /// The `initial_deposit` is sent by the sender to the gov module account.
+    pub fn submit_proposal(
+        &self,
+        messages: Vec<CosmosMsg<C::ExecT>>,
+        title: impl Into<String>,
+        summary: impl Into<String>,
+        initial_deposit: &[Coin],
+    ) -> Result<u64, CwEnvError> {
+        let mut app = self.app.borrow_mut();
+        let block = app.block_info();
+        let proposal_id = create_proposal(
+            app.storage_mut(),
+            &block,
+            &self.sender,
+            title.into(),
+            summary.into(),
+            to_json_binary(&messages)?,
+        )?;
+        drop(app);
+
+        self.deposit(proposal_id, initial_deposit)?;
+        Ok(proposal_id)
+    }
+
+    /// Deposits `amount` from the sender on a proposal.
+    /// The proposal enters the voting period once its total deposit reaches the minimum deposit.
+    pub fn deposit(&self, proposal_id: u64, amount: &[Coin]) -> Result<AppResponse, CwEnvError> {
+        let msg = MsgDeposit {
+            proposal_id,
+            depositor: self.sender.to_string(),
+            amount: amount
+                .iter()
+                .map(|coin| ProtoCoin {
+                    denom: coin.denom.clone(),
+                    amount: coin.amount.to_string(),
+                })
+                .collect(),
+        };
+        #[allow(deprecated)]
+        let msg = CosmosMsg::Stargate {
+            type_url: MSG_DEPOSIT_TYPE_URL.to_string(),
+            value: msg.encode_to_vec().into(),
+        };
+        self.metered_tx(|app| app.execute(self.sender.clone(), msg))
+    }
+
+    /// Votes on a proposal on behalf of the sender
+    pub fn vote(&self, proposal_id: u64, option: VoteOption) -> Result<AppResponse, CwEnvError> {
+        self.metered_tx(|app| {
+            app.execute(
+                self.sender.clone(),
+                CosmosMsg::Gov(GovMsg::Vote {
+                    proposal_id,
+                    option,
+                }),
+            )
+        })
+    }
+
+    /// Advances the block time to the end of the voting period of a proposal and tallies it.
+    /// Returns the proposal with its final status.
+    pub fn end_voting_period(&self, proposal_id: u64) -> Result<GovProposal, CwEnvError> {
+        let proposal = load_proposal(self.app.borrow().storage(), proposal_id)?;
+        let Some(voting_end_time) = proposal.voting_end_time else {
+            return Err(CwEnvError::StdErr(format!(
+                "proposal {proposal_id} didn't enter its voting period"
+            )));
+        };
+
+        self.app.borrow_mut().update_block(|b| {
+            if b.time < voting_end_time {
+                b.height += (voting_end_time.seconds() - b.time.seconds()) / 5;
+                b.time = voting_end_time;
+            }
+        });
+        self.end_gov_block()?;
+
+        Ok(load_proposal(self.app.borrow().storage(), proposal_id)?)
+    }
+
+    /// Tallies the proposals whose voting period ended and removes the proposals whose deposit period ended.
+    /// Called every time blocks are advanced.
+    pub(crate) fn end_gov_block(&self) -> Result<(), CwEnvError> {
+        let (now, proposals) = {
+            let app = self.app.borrow();
+            (app.block_info().time, proposals(app.storage())?)
+        };
+
+        for proposal in proposals {
+            match proposal.status {
+                ProposalStatus::DepositPeriod if proposal.deposit_end_time <= now => {
+                    self.drop_proposal(proposal.id)?
+                }
+                ProposalStatus::VotingPeriod
+                    if proposal.voting_end_time.is_some_and(|end| end <= now) =>
+                {
+                    self.finalize_proposal(proposal)?
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a proposal that didn't reach the minimum deposit, burning its deposits
+    fn drop_proposal(&self, proposal_id: u64) -> Result<(), CwEnvError> {
+        let gov = self.gov_address();
+        let deposits = self.take_deposits(proposal_id)?;
+        let mut app = self.app.borrow_mut();
+        PROPOSALS.remove(app.storage_mut(), proposal_id);
+        PROPOSAL_MESSAGES.remove(app.storage_mut(), proposal_id);
+
+        let amount = deposits.into_iter().flat_map(|d| d.amount).collect();
+        burn(&mut app, gov, amount)
+    }
+
+    fn finalize_proposal(&self, mut proposal: GovProposal) -> Result<(), CwEnvError> {
+        let params = self.gov_params()?;
+        let gov = self.gov_address();
+        let tally = {
+            let app = self.app.borrow();
+            tally(app.storage(), &app.wrap(), proposal.id)?
+        };
+
+        let total = tally.total();
+        let vetoed = !total.is_zero()
+            && Decimal::from_ratio(tally.no_with_veto, total) > params.veto_threshold;
+        let voted = total - tally.abstain;
+        let accepted =
+            !vetoed && !voted.is_zero() && Decimal::from_ratio(tally.yes, voted) > params.threshold;
+
+        proposal.status = if accepted {
+            let messages: Vec<CosmosMsg<C::ExecT>> =
+                from_json(PROPOSAL_MESSAGES.load(self.app.borrow().storage(), proposal.id)?)?;
+            let execution = self.metered_tx(|app| {
+                app.execute_multi(gov.clone(), messages)?;
+                Ok(AppResponse::default())
+            });
+            match execution {
+                Ok(_) => ProposalStatus::Passed,
+                Err(e) => {
+                    log::debug!("execution of proposal {} failed: {e}", proposal.id);
+                    ProposalStatus::Failed
+                }
+            }
+        } else {
+            ProposalStatus::Rejected
+        };
+        proposal.final_tally_result = Some(tally);
+        PROPOSALS.save(self.app.borrow_mut().storage_mut(), proposal.id, &proposal)?;
+
+        // Deposits are burned when the proposal is vetoed and refunded otherwise
+        let deposits = self.take_deposits(proposal.id)?;
+        let mut app = self.app.borrow_mut();
+        if vetoed {
+            let amount = deposits.into_iter().flat_map(|d| d.amount).collect();
+            return burn(&mut app, gov, amount);
+        }
+        for deposit in deposits {
+            if deposit.amount.is_empty() {
+                continue;
+            }
+            app.execute(
+                gov.clone(),
+                BankMsg::Send {
+                    to_address: deposit.depositor,
+                    amount: deposit.amount,
+                }
+                .into(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes and returns the deposits of a proposal
+    fn take_deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, CwEnvError> {
+        let mut app = self.app.borrow_mut();
+        let deposits = deposits(app.storage(), proposal_id)?;
+        for deposit in &deposits {
+            DEPOSITS.remove(app.storage_mut(), (proposal_id, deposit.depositor.as_str()));
+        }
+        Ok(deposits)
+    }
+}
+
+fn burn<A: Api, C: CustomModule>(
+    app: &mut crate::core::MockApp<A, C>,
+    sender: Addr,
+    amount: Vec<Coin>,
+) -> Result<(), CwEnvError> {
+    if !amount.is_empty() {
+        app.execute(sender, BankMsg::Burn { amount }.into())?;
+    }
+    Ok(())
+}
+
+pub(crate) fn load_proposal(
+    storage: &dyn Storage,
+    proposal_id: u64,
+) -> Result<GovProposal, CwEnvError> {
+    PROPOSALS
+        .may_load(storage, proposal_id)?
+        .ok_or_else(|| CwEnvError::StdErr(format!("proposal {proposal_id} doesn't exist")))
+}
+
+pub(crate) fn proposals(storage: &dyn Storage) -> Result<Vec<GovProposal>, CwEnvError> {
+    Ok(PROPOSALS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect::<Result<_, _>>()?)
+}
+
+pub(crate) fn load_vote(
+    storage: &dyn Storage,
+    proposal_id: u64,
+    voter: &Addr,
+) -> Result<GovVote, CwEnvError> {
+    VOTES
+        .may_load(storage, (proposal_id, voter.as_str()))?
+        .ok_or_else(|| CwEnvError::StdErr(format!("{voter} didn't vote on proposal {proposal_id}")))
+}
+
+pub(crate) fn votes(storage: &dyn Storage, proposal_id: u64) -> Result<Vec<GovVote>, CwEnvError> {
+    Ok(VOTES
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, vote)| vote))
+        .collect::<Result<_, _>>()?)
+}
+
+pub(crate) fn deposits(
+    storage: &dyn Storage,
+    proposal_id: u64,
+) -> Result<Vec<GovDeposit>, CwEnvError> {
+    Ok(DEPOSITS
+        .prefix(proposal_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            item.map(|(depositor, amount)| GovDeposit {
+                proposal_id,
+                depositor,
+                amount,
+            })
+        })
+        .collect::<Result<_, _>>()?)
+}
+
+/// Tallies the votes of a proposal, weighted by the tokens delegated by the voters
+pub(crate) fn tally<Q: CustomQuery>(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper<Q>,
+    proposal_id: u64,
+) -> Result<TallyResult, CwEnvError> {
+    let votes = votes(storage, proposal_id)?;
+    let mut powers = votes
+        .iter()
+        .map(|vote| {
+            let delegations = querier.query_all_delegations(&vote.voter)?;
+            Ok(delegations.iter().map(|d| d.amount.amount).sum::<Uint128>())
+        })
+        .collect::<Result<Vec<_>, CwEnvError>>()?;
+    // Without staking, every voter has the same voting power
+    if powers.iter().all(Uint128::is_zero) {
+        powers
+            .iter_mut()
+            .for_each(|power| *power = EQUAL_VOTING_POWER);
+    }
+
+    let mut tally = TallyResult::default();
+    for (vote, power) in votes.iter().zip(powers) {
+        for option in &vote.options {
+            let weighted = power.mul_floor(option.weight);
+            match option.option {
+                VoteOption::Yes => tally.yes += weighted,
+                VoteOption::No => tally.no += weighted,
+                VoteOption::Abstain => tally.abstain += weighted,
+                VoteOption::NoWithVeto => tally.no_with_veto += weighted,
+            }
+        }
+    }
+    Ok(tally)
+}
+
+/// Returns the final tally of a finished proposal or the current tally of a proposal being voted on
+pub(crate) fn proposal_tally<Q: CustomQuery>(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper<Q>,
+    proposal_id: u64,
+) -> Result<TallyResult, CwEnvError> {
+    match load_proposal(storage, proposal_id)?.final_tally_result {
+        Some(tally) => Ok(tally),
+        None => tally(storage, querier, proposal_id),
+    }
+}
+
+fn add_coins(total: &mut Vec<Coin>, amount: &[Coin]) {
+    for coin in amount {
+        match total.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(c) => c.amount += coin.amount,
+            None => total.push(coin.clone()),
+        }
+    }
+}
+
+fn has_min_deposit(deposit: &[Coin], min_deposit: &[Coin]) -> bool {
+    min_deposit.iter().all(|min| {
+        deposit
+            .iter()
+            .any(|c| c.denom == min.denom && c.amount >= min.amount)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{
+        coins, Deps, DepsMut, Env, MessageInfo, Response, StdResult, WeightedVoteOption,
+    };
+    use cw_multi_test::ContractWrapper;
+    use cw_orch_core::environment::{
+        BankSetter, DefaultQueriers, GovQuerier, QueryHandler, TxHandler,
+    };
+
+    use super::*;
+    use crate::Mock;
+
+    fn proposer_instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn proposer_execute(_: DepsMut, env: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        let msg = MsgSubmitProposal {
+            proposer: env.contract.address.to_string(),
+            initial_deposit: vec![ProtoCoin {
+                denom: "ujuno".to_string(),
+                amount: "100".to_string(),
+            }],
+            title: "title".to_string(),
+            summary: "summary".to_string(),
+            ..Default::default()
+        };
+        #[allow(deprecated)]
+        Ok(Response::new().add_message(CosmosMsg::Stargate {
+            type_url: MSG_SUBMIT_PROPOSAL_TYPE_URL.to_string(),
+            value: msg.encode_to_vec().into(),
+        }))
+    }
+
+    fn proposer_query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    #[test]
+    fn deposit_period() -> anyhow::Result<()> {
+        let mut chain = Mock::new("sender");
+        chain.set_gov_params(&GovParams {
+            min_deposit: coins(100, "ujuno"),
+            ..Default::default()
+        })?;
+        chain.set_balance(&chain.sender_addr(), coins(150, "ujuno"))?;
+
+        let proposal_id = chain.submit_proposal(vec![], "title", "summary", &coins(50, "ujuno"))?;
+        assert_eq!(
+            load_proposal(chain.app.borrow().storage(), proposal_id)?.status,
+            ProposalStatus::DepositPeriod
+        );
+        assert!(chain.vote(proposal_id, VoteOption::Yes).is_err());
+
+        chain.deposit(proposal_id, &coins(50, "ujuno"))?;
+        let proposal = load_proposal(chain.app.borrow().storage(), proposal_id)?;
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+        assert_eq!(proposal.total_deposit, coins(100, "ujuno"));
+
+        // Deposits are refunded once the proposal is rejected
+        let proposal = chain.end_voting_period(proposal_id)?;
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        assert_eq!(
+            chain.query_balance(&chain.sender_addr(), "ujuno")?.u128(),
+            150
+        );
+
+        // Proposals without enough deposit are removed at the end of the deposit period
+        let proposal_id = chain.submit_proposal(vec![], "title", "summary", &[])?;
+        chain.wait_seconds(GovParams::default().max_deposit_period)?;
+        assert!(load_proposal(chain.app.borrow().storage(), proposal_id).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn contract_submits_proposal() -> anyhow::Result<()> {
+        let mut chain = Mock::new("sender");
+        chain.set_gov_params(&GovParams {
+            min_deposit: coins(100, "ujuno"),
+            ..Default::default()
+        })?;
+        let code_id = chain
+            .app
+            .borrow_mut()
+            .store_code(Box::new(ContractWrapper::new(
+                proposer_execute,
+                proposer_instantiate,
+                proposer_query,
+            )));
+        let proposer = chain.app.borrow_mut().instantiate_contract(
+            code_id,
+            chain.sender_addr(),
+            &Empty {},
+            &[],
+            "proposer",
+            None,
+        )?;
+        chain.set_balance(&proposer, coins(100, "ujuno"))?;
+
+        chain.app.borrow_mut().execute_contract(
+            chain.sender_addr(),
+            proposer.clone(),
+            &Empty {},
+            &[],
+        )?;
+
+        let proposal = chain.gov_querier().proposal(1)?;
+        assert_eq!(proposal.proposer, proposer.to_string());
+        assert_eq!(proposal.status, ProposalStatus::VotingPeriod);
+        assert_eq!(proposal.total_deposit, coins(100, "ujuno"));
+        assert_eq!(
+            chain.query_balance(&chain.gov_address(), "ujuno")?.u128(),
+            100
+        );
+
+        // Deposits are sent by their depositor
+        let other = chain.addr_make("other");
+        let msg = MsgDeposit {
+            proposal_id: 1,
+            depositor: other.to_string(),
+            amount: vec![],
+        };
+        #[allow(deprecated)]
+        let deposit = chain.app.borrow_mut().execute(
+            chain.sender_addr(),
+            CosmosMsg::Stargate {
+                type_url: MSG_DEPOSIT_TYPE_URL.to_string(),
+                value: msg.encode_to_vec().into(),
+            },
+        );
+        assert!(deposit.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn weighted_votes_and_veto() -> anyhow::Result<()> {
+        let mut chain = Mock::new("sender");
+        let voter = chain.addr_make("voter");
+        chain.set_balance(&chain.sender_addr(), coins(100, "ujuno"))?;
+
+        let proposal_id =
+            chain.submit_proposal(vec![], "title", "summary", &coins(100, "ujuno"))?;
+        chain.app.borrow_mut().execute(
+            voter.clone(),
+            CosmosMsg::Gov(GovMsg::VoteWeighted {
+                proposal_id,
+                options: vec![
+                    WeightedVoteOption {
+                        option: VoteOption::NoWithVeto,
+                        weight: Decimal::percent(50),
+                    },
+                    WeightedVoteOption {
+                        option: VoteOption::Yes,
+                        weight: Decimal::percent(50),
+                    },
+                ],
+            }),
+        )?;
+        chain.vote(proposal_id, VoteOption::NoWithVeto)?;
+        let querier = chain.gov_querier();
+        assert_eq!(querier.votes(proposal_id)?.len(), 2);
+        assert_eq!(
+            querier.vote(proposal_id, &voter)?.options[0].option,
+            VoteOption::NoWithVeto
+        );
+
+        let proposal = chain.end_voting_period(proposal_id)?;
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+        assert_eq!(
+            querier.tally(proposal_id)?.no_with_veto,
+            Uint128::new(1_500_000)
+        );
+        // Deposits of vetoed proposals are burned
+        assert!(chain
+            .query_balance(&chain.sender_addr(), "ujuno")?
+            .is_zero());
+        assert!(chain
+            .query_balance(&chain.gov_address(), "ujuno")?
+            .is_zero());
+        Ok(())
+    }
+}
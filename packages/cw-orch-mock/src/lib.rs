@@ -6,15 +6,26 @@ pub extern crate cw_multi_test;
 
 mod bech32;
 mod core;
+mod custom;
+pub mod gov;
 pub mod queriers;
 mod simple;
+mod staking;
+pub mod stargate;
 mod state;
+mod storage;
+#[cfg(feature = "tokenfactory")]
+pub mod tokenfactory;
+pub mod wasm;
 
-pub use self::core::{Mock, MockBase, MockBech32};
+pub use self::core::{CustomModule, FailingCustom, Mock, MockBase, MockBech32};
 
 pub type MockApp = self::core::MockApp<MockApi>;
 pub type MockAppBech32 = self::core::MockApp<MockApiBech32>;
 
 use cosmwasm_std::testing::MockApi;
 use cw_multi_test::MockApiBech32;
+pub use gov::MockGov;
+pub use stargate::MockStargate;
 pub use state::MockState;
+pub use wasm::WasmExecution;
@@ -8,31 +8,36 @@ use cw_orch_core::{
     CwEnvError,
 };
 
-use crate::{core::MockApp, MockBase};
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    MockBase,
+};
 
-pub struct MockBankQuerier<A> {
-    app: Rc<RefCell<MockApp<A>>>,
+pub struct MockBankQuerier<A: Api, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
 }
 
-impl<A: Api> MockBankQuerier<A> {
-    fn new<S: StateInterface>(mock: &MockBase<A, S>) -> Self {
+impl<A: Api, C: CustomModule> MockBankQuerier<A, C> {
+    fn new<S: StateInterface>(mock: &MockBase<A, S, C>) -> Self {
         Self {
             app: mock.app.clone(),
         }
     }
 }
 
-impl<A: Api, S: StateInterface> QuerierGetter<MockBankQuerier<A>> for MockBase<A, S> {
-    fn querier(&self) -> MockBankQuerier<A> {
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockBankQuerier<A, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockBankQuerier<A, C> {
         MockBankQuerier::new(self)
     }
 }
 
-impl<A: Api> Querier for MockBankQuerier<A> {
+impl<A: Api, C: CustomModule> Querier for MockBankQuerier<A, C> {
     type Error = CwEnvError;
 }
 
-impl<A: Api> BankQuerier for MockBankQuerier<A> {
+impl<A: Api, C: CustomModule> BankQuerier for MockBankQuerier<A, C> {
     fn balance(
         &self,
         address: &Addr,
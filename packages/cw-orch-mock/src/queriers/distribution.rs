@@ -0,0 +1,66 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Api, Coin};
+use cw_orch_core::{
+    environment::{DistributionQuerier, Querier, QuerierGetter, StateInterface},
+    CwEnvError,
+};
+use cw_utils::NativeBalance;
+
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    MockBase,
+};
+
+pub struct MockDistributionQuerier<A: Api, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
+}
+
+impl<A: Api, C: CustomModule> MockDistributionQuerier<A, C> {
+    fn new<S: StateInterface>(mock: &MockBase<A, S, C>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockDistributionQuerier<A, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockDistributionQuerier<A, C> {
+        MockDistributionQuerier::new(self)
+    }
+}
+
+impl<A: Api, C: CustomModule> Querier for MockDistributionQuerier<A, C> {
+    type Error = CwEnvError;
+}
+
+impl<A: Api, C: CustomModule> DistributionQuerier for MockDistributionQuerier<A, C> {
+    /// Rewards are computed by the staking module of the mock app
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        let delegation = self
+            .app
+            .borrow()
+            .wrap()
+            .query_delegation(delegator, validator)?;
+        Ok(delegation
+            .map(|d| d.accumulated_rewards)
+            .unwrap_or_default())
+    }
+
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+        let delegations = self.app.borrow().wrap().query_all_delegations(delegator)?;
+        let mut rewards = NativeBalance::default();
+        for delegation in delegations {
+            rewards =
+                rewards + NativeBalance(self.delegation_rewards(delegator, &delegation.validator)?);
+        }
+        rewards.normalize();
+        Ok(rewards.into_vec())
+    }
+}
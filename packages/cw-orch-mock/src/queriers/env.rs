@@ -3,9 +3,9 @@ use cw_orch_core::environment::{
     EnvironmentInfo, EnvironmentQuerier, QueryHandler, StateInterface,
 };
 
-use crate::MockBase;
+use crate::{core::CustomModule, MockBase};
 
-impl<A: Api, S: StateInterface> EnvironmentQuerier for MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> EnvironmentQuerier for MockBase<A, S, C> {
     fn env_info(&self) -> EnvironmentInfo {
         let block_info = self.block_info().unwrap();
         let chain_id = block_info.chain_id.clone();
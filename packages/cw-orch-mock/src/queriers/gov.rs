@@ -0,0 +1,103 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Api};
+use cw_orch_core::{
+    environment::{
+        GovDeposit, GovProposal, GovQuerier, GovVote, ProposalStatus, Querier, QuerierGetter,
+        StateInterface, TallyResult,
+    },
+    CwEnvError,
+};
+
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    gov, MockBase,
+};
+
+pub struct MockGovQuerier<A: Api, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
+}
+
+impl<A: Api, C: CustomModule> MockGovQuerier<A, C> {
+    fn new<S: StateInterface>(mock: &MockBase<A, S, C>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockGovQuerier<A, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockGovQuerier<A, C> {
+        MockGovQuerier::new(self)
+    }
+}
+
+impl<A: Api, C: CustomModule> Querier for MockGovQuerier<A, C> {
+    type Error = CwEnvError;
+}
+
+impl<A: Api, C: CustomModule> GovQuerier for MockGovQuerier<A, C> {
+    fn proposal(&self, proposal_id: u64) -> Result<GovProposal, Self::Error> {
+        gov::load_proposal(self.app.borrow().storage(), proposal_id)
+    }
+
+    fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<GovProposal>, Self::Error> {
+        let proposals = gov::proposals(self.app.borrow().storage())?;
+        Ok(proposals
+            .into_iter()
+            .filter(|p| status.map_or(true, |status| p.status == status))
+            .collect())
+    }
+
+    fn vote(&self, proposal_id: u64, voter: &Addr) -> Result<GovVote, Self::Error> {
+        gov::load_vote(self.app.borrow().storage(), proposal_id, voter)
+    }
+
+    fn votes(&self, proposal_id: u64) -> Result<Vec<GovVote>, Self::Error> {
+        gov::votes(self.app.borrow().storage(), proposal_id)
+    }
+
+    fn deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error> {
+        gov::deposits(self.app.borrow().storage(), proposal_id)
+    }
+
+    fn tally(&self, proposal_id: u64) -> Result<TallyResult, Self::Error> {
+        let app = self.app.borrow();
+        gov::proposal_tally(app.storage(), &app.wrap(), proposal_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coins;
+    use cw_orch_core::environment::{DefaultQueriers, GovQuerier, ProposalStatus, TxHandler};
+
+    use crate::Mock;
+
+    #[test]
+    fn gov_querier() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        chain.set_balance(&chain.sender_addr(), coins(100, "ujuno"))?;
+
+        let voting = chain.submit_proposal(vec![], "voting", "summary", &coins(100, "ujuno"))?;
+        let deposit = chain.submit_proposal(vec![], "deposit", "summary", &[])?;
+
+        let querier = chain.gov_querier();
+        let proposal = querier.proposal(voting)?;
+        assert_eq!(proposal.title, "voting");
+        assert_eq!(proposal.proposer, chain.sender_addr().to_string());
+        assert_eq!(querier.proposals(None)?.len(), 2);
+        assert_eq!(
+            querier.proposals(Some(ProposalStatus::DepositPeriod))?[0].id,
+            deposit
+        );
+
+        let deposits = querier.deposits(voting)?;
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].amount, coins(100, "ujuno"));
+        assert!(querier.proposal(deposit + 1).is_err());
+        Ok(())
+    }
+}
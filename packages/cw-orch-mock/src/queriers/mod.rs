@@ -1,4 +1,4 @@
-use crate::MockBase;
+use crate::{core::CustomModule, MockBase};
 
 use cosmwasm_std::Api;
 use cw_multi_test::next_block;
@@ -8,11 +8,14 @@ use cw_orch_core::{
 };
 
 pub mod bank;
+pub mod distribution;
 mod env;
+pub mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
-impl<A: Api, S: StateInterface> QueryHandler for MockBase<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> QueryHandler for MockBase<A, S, C> {
     type Error = CwEnvError;
 
     fn wait_blocks(&self, amount: u64) -> Result<(), CwEnvError> {
@@ -20,7 +23,7 @@ impl<A: Api, S: StateInterface> QueryHandler for MockBase<A, S> {
             b.height += amount;
             b.time = b.time.plus_seconds(5 * amount);
         });
-        Ok(())
+        self.end_gov_block()
     }
 
     fn wait_seconds(&self, secs: u64) -> Result<(), CwEnvError> {
@@ -28,17 +31,20 @@ impl<A: Api, S: StateInterface> QueryHandler for MockBase<A, S> {
             b.time = b.time.plus_seconds(secs);
             b.height += secs / 5;
         });
-        Ok(())
+        self.end_gov_block()
     }
 
     fn next_block(&self) -> Result<(), CwEnvError> {
         self.app.borrow_mut().update_block(next_block);
-        Ok(())
+        self.end_gov_block()
     }
 }
 
-impl<A: Api, S: StateInterface> DefaultQueriers for MockBase<A, S> {
-    type Bank = bank::MockBankQuerier<A>;
-    type Wasm = wasm::MockWasmQuerier<A, S>;
-    type Node = node::MockNodeQuerier<A>;
+impl<A: Api, S: StateInterface, C: CustomModule> DefaultQueriers for MockBase<A, S, C> {
+    type Bank = bank::MockBankQuerier<A, C>;
+    type Wasm = wasm::MockWasmQuerier<A, S, C>;
+    type Node = node::MockNodeQuerier<A, C>;
+    type Staking = staking::MockStakingQuerier<A, C>;
+    type Distribution = distribution::MockDistributionQuerier<A, C>;
+    type Gov = gov::MockGovQuerier<A, C>;
 }
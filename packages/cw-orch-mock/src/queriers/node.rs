@@ -7,31 +7,36 @@ use cw_orch_core::{
     CwEnvError,
 };
 
-use crate::{core::MockApp, MockBase};
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    MockBase,
+};
 
-pub struct MockNodeQuerier<A: Api> {
-    app: Rc<RefCell<MockApp<A>>>,
+pub struct MockNodeQuerier<A: Api, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
 }
 
-impl<A: Api> MockNodeQuerier<A> {
-    fn new<S: StateInterface>(mock: &MockBase<A, S>) -> Self {
+impl<A: Api, C: CustomModule> MockNodeQuerier<A, C> {
+    fn new<S: StateInterface>(mock: &MockBase<A, S, C>) -> Self {
         Self {
             app: mock.app.clone(),
         }
     }
 }
 
-impl<A: Api> Querier for MockNodeQuerier<A> {
+impl<A: Api, C: CustomModule> Querier for MockNodeQuerier<A, C> {
     type Error = CwEnvError;
 }
 
-impl<A: Api, S: StateInterface> QuerierGetter<MockNodeQuerier<A>> for MockBase<A, S> {
-    fn querier(&self) -> MockNodeQuerier<A> {
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockNodeQuerier<A, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockNodeQuerier<A, C> {
         MockNodeQuerier::new(self)
     }
 }
 
-impl<A: Api> NodeQuerier for MockNodeQuerier<A> {
+impl<A: Api, C: CustomModule> NodeQuerier for MockNodeQuerier<A, C> {
     type Response = AppResponse;
 
     fn latest_block(&self) -> Result<cosmwasm_std::BlockInfo, Self::Error> {
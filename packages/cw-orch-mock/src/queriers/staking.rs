@@ -0,0 +1,114 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Api, Delegation, Validator};
+use cw_orch_core::{
+    environment::{Querier, QuerierGetter, StakingQuerier, StateInterface},
+    CwEnvError,
+};
+
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    MockBase,
+};
+
+pub struct MockStakingQuerier<A: Api, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
+}
+
+impl<A: Api, C: CustomModule> MockStakingQuerier<A, C> {
+    fn new<S: StateInterface>(mock: &MockBase<A, S, C>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockStakingQuerier<A, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockStakingQuerier<A, C> {
+        MockStakingQuerier::new(self)
+    }
+}
+
+impl<A: Api, C: CustomModule> Querier for MockStakingQuerier<A, C> {
+    type Error = CwEnvError;
+}
+
+impl<A: Api, C: CustomModule> StakingQuerier for MockStakingQuerier<A, C> {
+    fn bonded_denom(&self) -> Result<String, Self::Error> {
+        Ok(self.app.borrow().wrap().query_bonded_denom()?)
+    }
+
+    fn validator(&self, validator: &str) -> Result<Validator, Self::Error> {
+        self.app
+            .borrow()
+            .wrap()
+            .query_validator(validator)?
+            .ok_or_else(|| CwEnvError::StdErr(format!("validator {validator} doesn't exist")))
+    }
+
+    fn validators(&self) -> Result<Vec<Validator>, Self::Error> {
+        Ok(self.app.borrow().wrap().query_all_validators()?)
+    }
+
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<Delegation>, Self::Error> {
+        let delegation = self
+            .app
+            .borrow()
+            .wrap()
+            .query_delegation(delegator, validator)?;
+        Ok(delegation.map(|d| Delegation::new(d.delegator, d.validator, d.amount)))
+    }
+
+    fn delegator_delegations(&self, delegator: &Addr) -> Result<Vec<Delegation>, Self::Error> {
+        Ok(self.app.borrow().wrap().query_all_delegations(delegator)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coin, coins, Decimal};
+    use cw_orch_core::environment::{
+        DefaultQueriers, StakingParams, StakingQuerier, StakingSetter,
+    };
+
+    use crate::Mock;
+
+    #[test]
+    fn staking_querier() -> anyhow::Result<()> {
+        let mut chain = Mock::new("sender");
+        let delegator = chain.addr_make("delegator");
+        let validator = chain.addr_make("validator").to_string();
+        let other_validator = chain.addr_make("other_validator").to_string();
+
+        chain.set_staking_params(StakingParams {
+            bonded_denom: "ustake".to_string(),
+            unbonding_time: 60,
+            apr: Decimal::percent(10),
+        })?;
+        chain.add_validator(&validator, Decimal::percent(5))?;
+        chain.add_validator(&other_validator, Decimal::zero())?;
+        chain.set_balance(&delegator, coins(1_000, "ustake"))?;
+        chain.delegate_from(&delegator, &validator, coin(600, "ustake"))?;
+
+        let querier = chain.staking_querier();
+        assert_eq!(querier.bonded_denom()?, "ustake");
+        assert_eq!(querier.validators()?.len(), 2);
+        assert_eq!(
+            querier.validator(&validator)?.commission,
+            Decimal::percent(5)
+        );
+        assert!(querier.validator("unknown").is_err());
+
+        let delegation = querier.delegation(&delegator, &validator)?.unwrap();
+        assert_eq!(delegation.amount, coin(600, "ustake"));
+        assert_eq!(querier.delegation(&delegator, &other_validator)?, None);
+        assert_eq!(querier.delegator_delegations(&delegator)?, vec![delegation]);
+        Ok(())
+    }
+}
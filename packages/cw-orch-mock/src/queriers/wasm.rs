@@ -13,15 +13,18 @@ use cw_orch_core::{
 use serde::{de::DeserializeOwned, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::{core::MockApp, MockBase};
+use crate::{
+    core::{CustomModule, FailingCustom, MockApp},
+    MockBase,
+};
 
-pub struct MockWasmQuerier<A: Api, S: StateInterface> {
-    app: Rc<RefCell<MockApp<A>>>,
+pub struct MockWasmQuerier<A: Api, S: StateInterface, C: CustomModule = FailingCustom> {
+    app: Rc<RefCell<MockApp<A, C>>>,
     _state: PhantomData<S>,
 }
 
-impl<A: Api, S: StateInterface> MockWasmQuerier<A, S> {
-    fn new(mock: &MockBase<A, S>) -> Self {
+impl<A: Api, S: StateInterface, C: CustomModule> MockWasmQuerier<A, S, C> {
+    fn new(mock: &MockBase<A, S, C>) -> Self {
         Self {
             app: mock.app.clone(),
             _state: PhantomData,
@@ -29,33 +32,39 @@ impl<A: Api, S: StateInterface> MockWasmQuerier<A, S> {
     }
 }
 
-impl<A: Api, S: StateInterface> Querier for MockWasmQuerier<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> Querier for MockWasmQuerier<A, S, C> {
     type Error = CwEnvError;
 }
 
-impl<A: Api, S: StateInterface> QuerierGetter<MockWasmQuerier<A, S>> for MockBase<A, S> {
-    fn querier(&self) -> MockWasmQuerier<A, S> {
+impl<A: Api, S: StateInterface, C: CustomModule> QuerierGetter<MockWasmQuerier<A, S, C>>
+    for MockBase<A, S, C>
+{
+    fn querier(&self) -> MockWasmQuerier<A, S, C> {
         MockWasmQuerier::new(self)
     }
 }
 
-fn code_id_hash<A: Api, S: StateInterface>(
-    querier: &MockWasmQuerier<A, S>,
+fn code_id_hash<A: Api, S: StateInterface, C: CustomModule>(
+    querier: &MockWasmQuerier<A, S, C>,
     code_id: u64,
 ) -> Result<Checksum, CwEnvError> {
     let code_info = querier.app.borrow().wrap().query_wasm_code_info(code_id)?;
     Ok(code_info.checksum)
 }
 
-fn contract_info<A: Api, S: StateInterface>(
-    querier: &MockWasmQuerier<A, S>,
+fn contract_info<A: Api, S: StateInterface, C: CustomModule>(
+    querier: &MockWasmQuerier<A, S, C>,
     address: &Addr,
 ) -> Result<ContractInfoResponse, CwEnvError> {
-    let info = querier
-        .app
-        .borrow()
-        .wrap()
-        .query_wasm_contract_info(address)?;
+    let app = querier.app.borrow();
+    // The querier only forwards the message of the errors, the contract data tells whether the contract exists
+    if let Err(e) = app.contract_data(address) {
+        return Err(match e.downcast_ref::<StdError>() {
+            Some(StdError::NotFound { .. }) => CwEnvError::AddrNotInStore(address.to_string()),
+            _ => e.into(),
+        });
+    }
+    let info = app.wrap().query_wasm_contract_info(address)?;
     Ok(info)
 }
 
@@ -69,8 +78,8 @@ fn local_hash<Chain: TxHandler + QueryHandler, T: Uploadable + ContractInstance<
 }
 
 /// Copied implementation from [`cosmwasm_std::QuerierWrapper::query`] but without deserialization
-fn raw_query<A: Api, S: StateInterface>(
-    querier: &MockWasmQuerier<A, S>,
+fn raw_query<A: Api, S: StateInterface, C: CustomModule>(
+    querier: &MockWasmQuerier<A, S, C>,
     address: &Addr,
     query_data: Vec<u8>,
 ) -> Result<Vec<u8>, CwEnvError> {
@@ -95,8 +104,8 @@ fn raw_query<A: Api, S: StateInterface>(
     Ok(res?.to_vec())
 }
 
-fn smart_query<A: Api, S: StateInterface, Q, T>(
-    querier: &MockWasmQuerier<A, S>,
+fn smart_query<A: Api, S: StateInterface, C: CustomModule, Q, T>(
+    querier: &MockWasmQuerier<A, S, C>,
     address: &Addr,
     query_data: &Q,
 ) -> Result<T, CwEnvError>
@@ -116,8 +125,8 @@ where
         ))?)
 }
 
-fn code<A: Api, S: StateInterface>(
-    querier: &MockWasmQuerier<A, S>,
+fn code<A: Api, S: StateInterface, C: CustomModule>(
+    querier: &MockWasmQuerier<A, S, C>,
     code_id: u64,
 ) -> Result<cosmwasm_std::CodeInfoResponse, CwEnvError> {
     Ok(querier
@@ -129,8 +138,8 @@ fn code<A: Api, S: StateInterface>(
         ))?)
 }
 
-impl<A: Api, S: StateInterface> WasmQuerier for MockWasmQuerier<A, S> {
-    type Chain = MockBase<A, S>;
+impl<A: Api, S: StateInterface, C: CustomModule> WasmQuerier for MockWasmQuerier<A, S, C> {
+    type Chain = MockBase<A, S, C>;
     /// Returns the hex-encoded checksum of the code.
     fn code_id_hash(&self, code_id: u64) -> Result<Checksum, CwEnvError> {
         code_id_hash(self, code_id)
@@ -183,6 +192,11 @@ impl<A: Api, S: StateInterface> WasmQuerier for MockWasmQuerier<A, S> {
             .addr_humanize(&canonical_addr)?
             .to_string())
     }
+
+    /// Only missing contracts are detected, the app doesn't tell missing codes apart from other errors
+    fn is_not_found(&self, error: &CwEnvError) -> bool {
+        matches!(error, CwEnvError::AddrNotInStore(_))
+    }
 }
 
 #[cfg(test)]
@@ -193,6 +207,18 @@ mod tests {
 
     use crate::{Mock, MockBech32};
 
+    #[test]
+    fn missing_contract_is_not_found() -> anyhow::Result<()> {
+        let mock = Mock::new("sender");
+        let querier = mock.wasm_querier();
+
+        let err = querier
+            .contract_info(&Addr::unchecked("missing"))
+            .unwrap_err();
+        assert!(querier.is_not_found(&err));
+        Ok(())
+    }
+
     #[test]
     fn bech32_instantiate2() -> anyhow::Result<()> {
         let mock = MockBech32::new("mock");
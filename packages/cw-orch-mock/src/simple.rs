@@ -11,10 +11,14 @@ use cw_orch_core::{
 };
 use cw_utils::NativeBalance;
 
+use crate::core::{CustomModule, FailingCustom};
+use crate::gov::MockGov;
 use crate::queriers::bank::MockBankQuerier;
-use crate::{Mock, MockState};
+use crate::stargate::MockStargate;
+use crate::wasm::WasmExecution;
+use crate::{Mock, MockBase, MockState};
 
-impl<S: StateInterface> Mock<S> {
+impl<S: StateInterface, C: CustomModule> MockBase<MockApi, S, C> {
     /// Set the bank balance of an address.
     pub fn set_balance(
         &self,
@@ -104,18 +108,41 @@ impl<S: StateInterface> Mock<S> {
     /// Create a mock environment with a custom mock state.
     /// The state is customizable by implementing the `StateInterface` trait on a custom struct and providing it on the custom constructor.
     pub fn new_custom(sender: impl Into<String>, custom_state: S) -> Self {
+        Self::new_with_custom_module(sender, custom_state, FailingCustom::new())
+    }
+}
+
+impl<S: StateInterface, C: CustomModule> MockBase<MockApi, S, C> {
+    /// Create a mock environment with a custom mock state and a module handling the custom messages and queries of the chain.
+    pub fn new_with_custom_module(
+        sender: impl Into<String>,
+        custom_state: S,
+        custom_module: C,
+    ) -> Self {
         let state = Rc::new(RefCell::new(custom_state));
-        let app = AppBuilder::new_custom().build(|_, _, _| {});
+        let stargate = MockStargate::default();
+        MockGov::register_stargate_handlers(&stargate);
+        let app = AppBuilder::new_custom()
+            .with_custom(custom_module)
+            .with_gov(MockGov)
+            .with_stargate(stargate.clone())
+            .build(|_, _, _| {});
         let sender: String = sender.into();
         let sender = app.api().addr_make(&sender);
         let app = Rc::new(RefCell::new(app));
 
-        Self { sender, state, app }
+        Self {
+            sender,
+            state,
+            app,
+            stargate,
+            wasm: WasmExecution::default(),
+        }
     }
 }
 
-impl<S: StateInterface> BankSetter for Mock<S> {
-    type T = MockBankQuerier<MockApi>;
+impl<S: StateInterface, C: CustomModule> BankSetter for MockBase<MockApi, S, C> {
+    type T = MockBankQuerier<MockApi, C>;
 
     fn set_balance(
         &mut self,
@@ -0,0 +1,123 @@
+use cosmwasm_std::{Addr, Api, Coin, Decimal, StakingMsg, Validator};
+use cw_multi_test::{Executor, StakingInfo, StakingSudo, SudoMsg};
+use cw_orch_core::environment::{StakingParams, StakingSetter, StateInterface, TxHandler};
+
+use crate::{core::CustomModule, MockBase};
+
+/// Delegation rewards accrue according to the [`StakingParams::apr`] as the block time advances.
+impl<A: Api, S: StateInterface, C: CustomModule> StakingSetter for MockBase<A, S, C> {
+    fn set_staking_params(
+        &mut self,
+        params: StakingParams,
+    ) -> Result<(), <Self as TxHandler>::Error> {
+        self.app.borrow_mut().init_modules(|router, _, storage| {
+            router.staking.setup(
+                storage,
+                StakingInfo {
+                    bonded_denom: params.bonded_denom,
+                    unbonding_time: params.unbonding_time,
+                    apr: params.apr,
+                },
+            )
+        })?;
+        Ok(())
+    }
+
+    fn add_validator(
+        &mut self,
+        validator: &str,
+        commission: Decimal,
+    ) -> Result<(), <Self as TxHandler>::Error> {
+        let mut app = self.app.borrow_mut();
+        let block = app.block_info();
+        app.init_modules(|router, api, storage| {
+            router.staking.add_validator(
+                api,
+                storage,
+                &block,
+                Validator::new(
+                    validator.to_string(),
+                    commission,
+                    Decimal::one(),
+                    Decimal::one(),
+                ),
+            )
+        })?;
+        Ok(())
+    }
+
+    fn delegate_from(
+        &mut self,
+        delegator: &Addr,
+        validator: &str,
+        amount: Coin,
+    ) -> Result<(), <Self as TxHandler>::Error> {
+        self.app.borrow_mut().execute(
+            delegator.clone(),
+            StakingMsg::Delegate {
+                validator: validator.to_string(),
+                amount,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+
+    fn slash(
+        &mut self,
+        validator: &str,
+        percentage: Decimal,
+    ) -> Result<(), <Self as TxHandler>::Error> {
+        self.app
+            .borrow_mut()
+            .sudo(SudoMsg::Staking(StakingSudo::Slash {
+                validator: validator.to_string(),
+                percentage,
+            }))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coin, coins, Uint128};
+    use cw_orch_core::environment::{
+        BankSetter, DefaultQueriers, DistributionQuerier, QueryHandler, StakingQuerier,
+    };
+
+    use super::*;
+    use crate::Mock;
+
+    #[test]
+    fn delegation_rewards_and_slashing() -> anyhow::Result<()> {
+        let mut chain = Mock::new("sender");
+        let delegator = chain.addr_make("delegator");
+        let validator = chain.addr_make("validator").to_string();
+
+        chain.set_staking_params(StakingParams {
+            bonded_denom: "ustake".to_string(),
+            unbonding_time: 60,
+            apr: Decimal::percent(10),
+        })?;
+        chain.add_validator(&validator, Decimal::zero())?;
+        chain.set_balance(&delegator, coins(1_000, "ustake"))?;
+        chain.delegate_from(&delegator, &validator, coin(1_000, "ustake"))?;
+
+        // Rewards accrue over a year
+        chain.wait_seconds(365 * 24 * 3600)?;
+        assert_eq!(
+            chain
+                .distribution_querier()
+                .delegation_rewards(&delegator, &validator)?,
+            coins(100, "ustake")
+        );
+
+        chain.slash(&validator, Decimal::percent(50))?;
+        let delegation = chain
+            .staking_querier()
+            .delegation(&delegator, &validator)?
+            .unwrap();
+        assert_eq!(delegation.amount.amount, Uint128::new(500));
+        Ok(())
+    }
+}
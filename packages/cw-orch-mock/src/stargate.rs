@@ -0,0 +1,349 @@
+//! Registry of Stargate/Any message handlers for the [`MockBase`] environment.
+//!
+//! cw-multi-test doesn't know how to handle `CosmosMsg::Stargate`/`CosmosMsg::Any` messages and gRPC queries.
+//! Handlers registered on the [`MockStargate`] module of the app emulate the chain modules that process them.
+//!
+//! ## Example
+//! ```
+//! use cosmwasm_std::Binary;
+//! use cw_multi_test::AppResponse;
+//! use cw_orch_mock::Mock;
+//!
+//! let mock = Mock::new("sender");
+//! mock.register_stargate_exec("/my.module.v1.MsgDoSomething", |_ctx, _sender, _value| {
+//!     Ok(AppResponse::default())
+//! });
+//! mock.register_stargate_query("/my.module.v1.Query/Something", |_ctx, _data| {
+//!     Ok(Binary::default())
+//! });
+//! ```
+use cosmwasm_std::{Addr, Api, Binary, CosmosMsg};
+use cw_multi_test::{AnyResult, AppResponse};
+use cw_orch_core::environment::{StateInterface, TxHandler};
+
+use crate::{core::CustomModule, MockBase};
+
+/// Defines the Stargate module `$name` dispatching messages and queries to registered handlers, along with the handler and context types.
+/// The Mock and CloneTesting environments are backed by different forks of cw-multi-test,
+/// `$multi_test` is the crate of the fork whose `Stargate` trait is implemented.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! stargate_module {
+    ($multi_test:ident, $(#[$meta:meta])* $name:ident) => {
+        /// Handler of Stargate/Any messages of a given type URL.
+        /// Receives the sender of the message and its protobuf encoded value.
+        pub type StargateExecHandler = ::std::rc::Rc<
+            dyn Fn(
+                &mut StargateExecContext,
+                ::cosmwasm_std::Addr,
+                ::cosmwasm_std::Binary,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse>,
+        >;
+
+        /// Handler of Stargate/gRPC queries on a given path.
+        /// Receives the protobuf encoded request and returns the protobuf encoded response.
+        pub type StargateQueryHandler = ::std::rc::Rc<
+            dyn Fn(
+                &StargateQueryContext,
+                ::cosmwasm_std::Binary,
+            ) -> ::$multi_test::AnyResult<::cosmwasm_std::Binary>,
+        >;
+
+        /// Access to the app while executing a Stargate message
+        pub struct StargateExecContext<'a> {
+            pub api: &'a dyn ::cosmwasm_std::Api,
+            pub storage: &'a mut dyn ::cosmwasm_std::Storage,
+            pub block: &'a ::cosmwasm_std::BlockInfo,
+            router: &'a dyn ModuleRouter,
+        }
+
+        impl StargateExecContext<'_> {
+            /// Executes a privileged message on the app modules, e.g. minting tokens with `BankSudo::Mint`
+            pub fn sudo(
+                &mut self,
+                msg: ::$multi_test::SudoMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse> {
+                self.router.sudo(self.api, self.storage, self.block, msg)
+            }
+
+            /// Executes a bank message on behalf of `sender`
+            pub fn bank(
+                &mut self,
+                sender: ::cosmwasm_std::Addr,
+                msg: ::cosmwasm_std::BankMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse> {
+                self.router
+                    .bank(self.api, self.storage, self.block, sender, msg)
+            }
+        }
+
+        /// Access to the app while executing a Stargate query
+        pub struct StargateQueryContext<'a> {
+            pub api: &'a dyn ::cosmwasm_std::Api,
+            pub storage: &'a dyn ::cosmwasm_std::Storage,
+            pub querier: &'a dyn ::cosmwasm_std::Querier,
+            pub block: &'a ::cosmwasm_std::BlockInfo,
+        }
+
+        /// Object-safe subset of the `CosmosRouter` available to the handlers
+        trait ModuleRouter {
+            fn sudo(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &mut dyn ::cosmwasm_std::Storage,
+                block: &::cosmwasm_std::BlockInfo,
+                msg: ::$multi_test::SudoMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse>;
+
+            fn bank(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &mut dyn ::cosmwasm_std::Storage,
+                block: &::cosmwasm_std::BlockInfo,
+                sender: ::cosmwasm_std::Addr,
+                msg: ::cosmwasm_std::BankMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse>;
+        }
+
+        struct RouterRef<'a, ExecC, QueryC>(
+            &'a dyn ::$multi_test::CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        );
+
+        impl<ExecC, QueryC> ModuleRouter for RouterRef<'_, ExecC, QueryC>
+        where
+            ExecC: ::cosmwasm_std::CustomMsg + ::serde::de::DeserializeOwned + 'static,
+            QueryC: ::cosmwasm_std::CustomQuery + ::serde::de::DeserializeOwned + 'static,
+        {
+            fn sudo(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &mut dyn ::cosmwasm_std::Storage,
+                block: &::cosmwasm_std::BlockInfo,
+                msg: ::$multi_test::SudoMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse> {
+                self.0.sudo(api, storage, block, msg)
+            }
+
+            fn bank(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &mut dyn ::cosmwasm_std::Storage,
+                block: &::cosmwasm_std::BlockInfo,
+                sender: ::cosmwasm_std::Addr,
+                msg: ::cosmwasm_std::BankMsg,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse> {
+                self.0
+                    .execute(api, storage, block, sender, ::cosmwasm_std::CosmosMsg::Bank(msg))
+            }
+        }
+
+        $(#[$meta])*
+        #[derive(Clone, Default)]
+        pub struct $name {
+            exec_handlers: ::std::rc::Rc<
+                ::std::cell::RefCell<::std::collections::HashMap<String, StargateExecHandler>>,
+            >,
+            query_handlers: ::std::rc::Rc<
+                ::std::cell::RefCell<::std::collections::HashMap<String, StargateQueryHandler>>,
+            >,
+        }
+
+        impl ::std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("exec_handlers", &self.exec_handlers.borrow().keys())
+                    .field("query_handlers", &self.query_handlers.borrow().keys())
+                    .finish()
+            }
+        }
+
+        impl $name {
+            /// Registers the handler of the messages with the given type URL, replacing the previous one
+            pub fn register_exec(
+                &self,
+                type_url: impl Into<String>,
+                handler: impl Fn(
+                        &mut StargateExecContext,
+                        ::cosmwasm_std::Addr,
+                        ::cosmwasm_std::Binary,
+                    ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse>
+                    + 'static,
+            ) {
+                self.exec_handlers
+                    .borrow_mut()
+                    .insert(type_url.into(), ::std::rc::Rc::new(handler));
+            }
+
+            /// Registers the handler of the queries on the given path, replacing the previous one
+            pub fn register_query(
+                &self,
+                path: impl Into<String>,
+                handler: impl Fn(
+                        &StargateQueryContext,
+                        ::cosmwasm_std::Binary,
+                    ) -> ::$multi_test::AnyResult<::cosmwasm_std::Binary>
+                    + 'static,
+            ) {
+                self.query_handlers
+                    .borrow_mut()
+                    .insert(path.into(), ::std::rc::Rc::new(handler));
+            }
+        }
+
+        impl ::$multi_test::Stargate for $name {
+            fn execute_stargate<ExecC, QueryC>(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &mut dyn ::cosmwasm_std::Storage,
+                router: &dyn ::$multi_test::CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+                block: &::cosmwasm_std::BlockInfo,
+                sender: ::cosmwasm_std::Addr,
+                type_url: String,
+                value: ::cosmwasm_std::Binary,
+            ) -> ::$multi_test::AnyResult<::$multi_test::AppResponse>
+            where
+                ExecC: ::cosmwasm_std::CustomMsg + ::serde::de::DeserializeOwned + 'static,
+                QueryC: ::cosmwasm_std::CustomQuery + ::serde::de::DeserializeOwned + 'static,
+            {
+                // The handler is cloned out of the registry so that it can trigger nested Stargate messages
+                let handler = self.exec_handlers.borrow().get(&type_url).cloned();
+                let Some(handler) = handler else {
+                    ::anyhow::bail!("No Stargate handler registered for message {type_url}");
+                };
+                let mut ctx = StargateExecContext {
+                    api,
+                    storage,
+                    block,
+                    router: &RouterRef(router),
+                };
+                handler(&mut ctx, sender, value)
+            }
+
+            fn query_stargate(
+                &self,
+                api: &dyn ::cosmwasm_std::Api,
+                storage: &dyn ::cosmwasm_std::Storage,
+                querier: &dyn ::cosmwasm_std::Querier,
+                block: &::cosmwasm_std::BlockInfo,
+                path: String,
+                data: ::cosmwasm_std::Binary,
+            ) -> ::$multi_test::AnyResult<::cosmwasm_std::Binary> {
+                let handler = self.query_handlers.borrow().get(&path).cloned();
+                let Some(handler) = handler else {
+                    ::anyhow::bail!("No Stargate handler registered for query {path}");
+                };
+                let ctx = StargateQueryContext {
+                    api,
+                    storage,
+                    querier,
+                    block,
+                };
+                handler(&ctx, data)
+            }
+        }
+    };
+}
+
+stargate_module!(
+    cw_multi_test,
+    /// Stargate module of the [`MockApp`](crate::MockApp), dispatching messages and queries to the registered handlers.
+    /// Messages and queries without handler fail.
+    MockStargate
+);
+
+impl<A: Api, S: StateInterface, C: CustomModule> MockBase<A, S, C> {
+    /// Registers the handler of the Stargate/Any messages with the given type URL
+    pub fn register_stargate_exec(
+        &self,
+        type_url: impl Into<String>,
+        handler: impl Fn(&mut StargateExecContext, Addr, Binary) -> AnyResult<AppResponse> + 'static,
+    ) {
+        self.stargate.register_exec(type_url, handler)
+    }
+
+    /// Registers the handler of the Stargate/gRPC queries on the given path
+    pub fn register_stargate_query(
+        &self,
+        path: impl Into<String>,
+        handler: impl Fn(&StargateQueryContext, Binary) -> AnyResult<Binary> + 'static,
+    ) {
+        self.stargate.register_query(path, handler)
+    }
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> cw_orch_traits::Stargate for MockBase<A, S, C> {
+    /// Executes the messages with the registered Stargate handlers, in a single transaction
+    fn commit_any(
+        &self,
+        msgs: Vec<prost_types::Any>,
+        _memo: Option<&str>,
+    ) -> Result<Self::Response, Self::Error> {
+        #[allow(deprecated)]
+        let msgs: Vec<CosmosMsg<C::ExecT>> = msgs
+            .into_iter()
+            .map(|any| CosmosMsg::Stargate {
+                type_url: any.type_url,
+                value: any.value.into(),
+            })
+            .collect();
+
+        self.metered_tx(|app| {
+            let responses = app.execute_multi(self.sender_addr(), msgs)?;
+            Ok(AppResponse {
+                events: responses
+                    .iter()
+                    .flat_map(|resp| resp.events.clone())
+                    .collect(),
+                data: responses.into_iter().rev().find_map(|resp| resp.data),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::{coins, to_json_binary, Empty, QueryRequest};
+    use cw_multi_test::{BankSudo, SudoMsg};
+    use cw_orch_traits::Stargate;
+
+    use crate::Mock;
+
+    use super::*;
+
+    #[test]
+    fn registered_handlers() -> anyhow::Result<()> {
+        let chain = Mock::new("sender");
+        let sender = chain.sender_addr();
+
+        let any = prost_types::Any {
+            type_url: "/test.MsgMint".to_string(),
+            value: vec![],
+        };
+        assert!(chain.commit_any(vec![any.clone()], None).is_err());
+
+        chain.register_stargate_exec("/test.MsgMint", |ctx, sender, _value| {
+            ctx.sudo(SudoMsg::Bank(BankSudo::Mint {
+                to_address: sender.to_string(),
+                amount: coins(10, "utest"),
+            }))
+        });
+        chain.register_stargate_query("/test.Query/Answer", |_ctx, _data| {
+            Ok(to_json_binary(&42u64)?)
+        });
+
+        chain.commit_any(vec![any], None)?;
+        assert_eq!(chain.query_all_balances(&sender)?, coins(10, "utest"));
+
+        #[allow(deprecated)]
+        let answer: u64 = chain
+            .app
+            .borrow()
+            .wrap()
+            .query(&QueryRequest::<Empty>::Stargate {
+                path: "/test.Query/Answer".to_string(),
+                data: Binary::default(),
+            })?;
+        assert_eq!(answer, 42);
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+//! Storage inspection of the contracts of the [`MockBase`] environment.
+use cosmwasm_std::{Addr, Api};
+use cw_orch_core::{
+    environment::{StateInterface, StorageDumper},
+    CwEnvError,
+};
+
+use crate::{core::CustomModule, MockBase};
+
+/// Storage diffs are available through [`StorageDumper::storage_diff`]:
+///
+/// ```
+/// use cw_orch_core::contract::interface_traits::{CwOrchInstantiate, CwOrchUpload};
+/// use cw_orch_core::environment::StorageDumper;
+/// use cw_orch_mock::Mock;
+/// use mock_contract::{InstantiateMsg, MockContract};
+///
+/// let mock = Mock::new("sender");
+/// let contract = MockContract::new("mock-contract", mock.clone());
+/// contract.upload()?;
+///
+/// let (_, diff) = mock.storage_diff(|| contract.instantiate(&InstantiateMsg {}, None, &[]))?;
+/// // The contract version, `test-item` and `test-map/MAP_TEST_KEY` were added
+/// assert_eq!(diff.contract("mock-contract").len(), 3);
+/// println!("{diff}");
+/// # Ok::<(), cw_orch_core::CwEnvError>(())
+/// ```
+impl<A: Api, S: StateInterface, C: CustomModule> StorageDumper for MockBase<A, S, C> {
+    fn dump_contract_storage(&self, address: &Addr) -> Result<Vec<(Vec<u8>, Vec<u8>)>, CwEnvError> {
+        Ok(self.app.borrow().dump_wasm_raw(address))
+    }
+}
@@ -0,0 +1,263 @@
+//! Emulation of the osmosis tokenfactory module, registered with [`MockBase::with_tokenfactory`].
+//!
+//! Supports the `MsgCreateDenom`, `MsgMint`, `MsgBurn` and `MsgChangeAdmin` messages
+//! and the `DenomAuthorityMetadata` query. Denoms are created as `factory/{creator}/{subdenom}`.
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Api, BankMsg, Coin, Event, Storage, Uint128};
+use cw_multi_test::{AnyResult, AppResponse, BankSudo, SudoMsg};
+use cw_orch_core::environment::StateInterface;
+use cw_storage_plus::Map;
+use osmosis_std::types::{
+    cosmos::base::v1beta1::Coin as ProtoCoin,
+    osmosis::tokenfactory::v1beta1::{
+        DenomAuthorityMetadata, MsgBurn, MsgChangeAdmin, MsgCreateDenom, MsgCreateDenomResponse,
+        MsgMint, QueryDenomAuthorityMetadataRequest, QueryDenomAuthorityMetadataResponse,
+    },
+};
+use prost::Message;
+
+use crate::{core::CustomModule, stargate::StargateExecContext, MockBase};
+
+/// Admin of every denom created through the tokenfactory
+const DENOM_ADMINS: Map<&str, String> = Map::new("tokenfactory_denom_admins");
+
+const DENOM_AUTHORITY_METADATA_PATH: &str =
+    "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata";
+
+impl<A: Api, S: StateInterface, C: CustomModule> MockBase<A, S, C> {
+    /// Registers the emulation of the osmosis tokenfactory module
+    pub fn with_tokenfactory(self) -> Self {
+        self.register_stargate_exec(MsgCreateDenom::TYPE_URL, |ctx, sender, value| {
+            create_denom(ctx, sender, MsgCreateDenom::decode(value.as_slice())?)
+        });
+        self.register_stargate_exec(MsgMint::TYPE_URL, |ctx, sender, value| {
+            mint(ctx, sender, MsgMint::decode(value.as_slice())?)
+        });
+        self.register_stargate_exec(MsgBurn::TYPE_URL, |ctx, sender, value| {
+            burn(ctx, sender, MsgBurn::decode(value.as_slice())?)
+        });
+        self.register_stargate_exec(MsgChangeAdmin::TYPE_URL, |ctx, sender, value| {
+            change_admin(ctx, sender, MsgChangeAdmin::decode(value.as_slice())?)
+        });
+        self.register_stargate_query(DENOM_AUTHORITY_METADATA_PATH, |ctx, data| {
+            let request = QueryDenomAuthorityMetadataRequest::decode(data.as_slice())?;
+            let admin = DENOM_ADMINS.load(ctx.storage, &request.denom)?;
+            Ok(QueryDenomAuthorityMetadataResponse {
+                authority_metadata: Some(DenomAuthorityMetadata { admin }),
+            }
+            .encode_to_vec()
+            .into())
+        });
+        self
+    }
+}
+
+fn create_denom(
+    ctx: &mut StargateExecContext,
+    sender: Addr,
+    msg: MsgCreateDenom,
+) -> AnyResult<AppResponse> {
+    let denom = format!("factory/{}/{}", sender, msg.subdenom);
+    if DENOM_ADMINS.has(ctx.storage, &denom) {
+        anyhow::bail!("denom {denom} already exists");
+    }
+    DENOM_ADMINS.save(ctx.storage, &denom, &sender.to_string())?;
+
+    Ok(AppResponse {
+        events: vec![Event::new("create_denom")
+            .add_attribute("creator", sender)
+            .add_attribute("new_token_denom", &denom)],
+        data: Some(
+            MsgCreateDenomResponse {
+                new_token_denom: denom,
+            }
+            .encode_to_vec()
+            .into(),
+        ),
+    })
+}
+
+fn mint(ctx: &mut StargateExecContext, sender: Addr, msg: MsgMint) -> AnyResult<AppResponse> {
+    let amount = parse_coin(msg.amount)?;
+    assert_admin(ctx.storage, &sender, &amount.denom)?;
+    let recipient = if msg.mint_to_address.is_empty() {
+        sender.to_string()
+    } else {
+        msg.mint_to_address
+    };
+
+    ctx.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: recipient.clone(),
+        amount: vec![amount.clone()],
+    }))?;
+
+    Ok(AppResponse {
+        events: vec![Event::new("tf_mint")
+            .add_attribute("mint_to_address", recipient)
+            .add_attribute("amount", amount.to_string())],
+        data: None,
+    })
+}
+
+fn burn(ctx: &mut StargateExecContext, sender: Addr, msg: MsgBurn) -> AnyResult<AppResponse> {
+    let amount = parse_coin(msg.amount)?;
+    assert_admin(ctx.storage, &sender, &amount.denom)?;
+    let burn_from = if msg.burn_from_address.is_empty() {
+        sender
+    } else {
+        ctx.api.addr_validate(&msg.burn_from_address)?
+    };
+
+    ctx.bank(
+        burn_from.clone(),
+        BankMsg::Burn {
+            amount: vec![amount.clone()],
+        },
+    )?;
+
+    Ok(AppResponse {
+        events: vec![Event::new("tf_burn")
+            .add_attribute("burn_from_address", burn_from)
+            .add_attribute("amount", amount.to_string())],
+        data: None,
+    })
+}
+
+fn change_admin(
+    ctx: &mut StargateExecContext,
+    sender: Addr,
+    msg: MsgChangeAdmin,
+) -> AnyResult<AppResponse> {
+    assert_admin(ctx.storage, &sender, &msg.denom)?;
+    DENOM_ADMINS.save(ctx.storage, &msg.denom, &msg.new_admin)?;
+
+    Ok(AppResponse {
+        events: vec![Event::new("change_admin")
+            .add_attribute("denom", msg.denom)
+            .add_attribute("new_admin", msg.new_admin)],
+        data: None,
+    })
+}
+
+fn assert_admin(storage: &dyn Storage, sender: &Addr, denom: &str) -> AnyResult<()> {
+    let Some(admin) = DENOM_ADMINS.may_load(storage, denom)? else {
+        anyhow::bail!("denom {denom} was not created with the tokenfactory");
+    };
+    if admin != sender.as_str() {
+        anyhow::bail!("unauthorized: {sender} is not the admin of {denom}");
+    }
+    Ok(())
+}
+
+fn parse_coin(coin: Option<ProtoCoin>) -> AnyResult<Coin> {
+    let Some(coin) = coin else {
+        anyhow::bail!("missing amount");
+    };
+    Ok(Coin {
+        denom: coin.denom,
+        amount: Uint128::from_str(&coin.amount)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use cosmwasm_std::coin;
+    use cw_orch_core::environment::TxHandler;
+    use cw_orch_traits::Stargate;
+
+    use super::*;
+    use crate::Mock;
+
+    #[test]
+    fn tokenfactory_lifecycle() -> anyhow::Result<()> {
+        let chain = Mock::new("sender").with_tokenfactory();
+        let sender = chain.sender_addr();
+        let receiver = chain.addr_make("receiver");
+        let denom = format!("factory/{}/token", sender);
+
+        let any = |msg: Vec<u8>, type_url: &str| prost_types::Any {
+            type_url: type_url.to_string(),
+            value: msg,
+        };
+
+        chain.commit_any(
+            vec![
+                any(
+                    MsgCreateDenom {
+                        sender: sender.to_string(),
+                        subdenom: "token".to_string(),
+                    }
+                    .encode_to_vec(),
+                    MsgCreateDenom::TYPE_URL,
+                ),
+                any(
+                    MsgMint {
+                        sender: sender.to_string(),
+                        amount: Some(ProtoCoin {
+                            denom: denom.clone(),
+                            amount: "100".to_string(),
+                        }),
+                        mint_to_address: receiver.to_string(),
+                    }
+                    .encode_to_vec(),
+                    MsgMint::TYPE_URL,
+                ),
+            ],
+            None,
+        )?;
+        assert_eq!(
+            chain.query_all_balances(&receiver)?,
+            vec![coin(100, &denom)]
+        );
+
+        chain.commit_any(
+            vec![any(
+                MsgBurn {
+                    sender: sender.to_string(),
+                    amount: Some(ProtoCoin {
+                        denom: denom.clone(),
+                        amount: "40".to_string(),
+                    }),
+                    burn_from_address: receiver.to_string(),
+                }
+                .encode_to_vec(),
+                MsgBurn::TYPE_URL,
+            )],
+            None,
+        )?;
+        assert_eq!(chain.query_all_balances(&receiver)?, vec![coin(60, &denom)]);
+
+        chain.commit_any(
+            vec![any(
+                MsgChangeAdmin {
+                    sender: sender.to_string(),
+                    denom: denom.clone(),
+                    new_admin: receiver.to_string(),
+                }
+                .encode_to_vec(),
+                MsgChangeAdmin::TYPE_URL,
+            )],
+            None,
+        )?;
+
+        // The sender is not the admin anymore
+        let mint_again = chain.commit_any(
+            vec![any(
+                MsgMint {
+                    sender: sender.to_string(),
+                    amount: Some(ProtoCoin {
+                        denom,
+                        amount: "1".to_string(),
+                    }),
+                    mint_to_address: sender.to_string(),
+                }
+                .encode_to_vec(),
+                MsgMint::TYPE_URL,
+            )],
+            None,
+        );
+        assert!(mint_again.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+//! cosmwasm-vm backend giving wasm contracts access to the storage, api and querier of the app.
+//!
+//! The VM needs a `'static` backend while the app only lends its storage, api and querier for the duration of a call.
+//! The contract runs on its own thread and its backend sends each access to the thread of the app,
+//! which serves them with the lent values until the contract returns (see [`HostReceiver::serve`]).
+use std::sync::mpsc;
+
+use cosmwasm_std::{Api, Binary, ContractResult, Order, Querier, Record, Storage, SystemResult};
+use cosmwasm_vm::{BackendApi, BackendError, BackendResult, GasInfo};
+
+/// Multiplier between the SDK gas and the gas of the VM, as in wasmd
+pub(crate) const GAS_MULTIPLIER: u64 = 140_000;
+/// SDK gas charged for each contract call, as in wasmd
+pub(crate) const INSTANCE_COST: u64 = 60_000;
+
+// SDK gas costs of the storage operations (KVGasConfig of the cosmos-sdk)
+const READ_COST_FLAT: u64 = 1_000;
+const READ_COST_PER_BYTE: u64 = 3;
+const WRITE_COST_FLAT: u64 = 2_000;
+const WRITE_COST_PER_BYTE: u64 = 30;
+const DELETE_COST: u64 = 1_000;
+const ITER_NEXT_COST_FLAT: u64 = 30;
+
+fn sdk_gas(gas: u64) -> GasInfo {
+    GasInfo::with_externally_used(gas * GAS_MULTIPLIER)
+}
+
+/// Storage of the called contract, as lent by the app
+pub(crate) enum HostStorage<'a> {
+    /// Storage of a query, the VM doesn't write to it
+    ReadOnly(&'a dyn Storage),
+    Mutable(&'a mut dyn Storage),
+}
+
+impl HostStorage<'_> {
+    fn get(&self) -> &dyn Storage {
+        match self {
+            HostStorage::ReadOnly(storage) => *storage,
+            HostStorage::Mutable(storage) => &**storage,
+        }
+    }
+}
+
+/// Storage, api and querier lent by the app to a contract during a call
+pub(crate) struct Host<'a> {
+    pub storage: HostStorage<'a>,
+    pub api: &'a dyn Api,
+    pub querier: &'a dyn Querier,
+}
+
+type HostCall = Box<dyn FnOnce(&mut Host<'_>) + Send>;
+
+/// Sends the accesses of the backend to the thread of the app
+#[derive(Clone)]
+pub(crate) struct HostSender(mpsc::Sender<HostCall>);
+
+/// Receives the accesses of the backend on the thread of the app
+pub(crate) struct HostReceiver(mpsc::Receiver<HostCall>);
+
+pub(crate) fn host_channel() -> (HostSender, HostReceiver) {
+    let (sender, receiver) = mpsc::channel();
+    (HostSender(sender), HostReceiver(receiver))
+}
+
+impl HostSender {
+    /// Run `f` on the thread of the app and wait for its result
+    fn call<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Host<'_>) -> T + Send + 'static,
+    ) -> Result<T, BackendError> {
+        let (reply, result) = mpsc::sync_channel(1);
+        self.0
+            .send(Box::new(move |host: &mut Host<'_>| {
+                // The backend waits for the reply, it can't be gone
+                let _ = reply.send(f(host));
+            }))
+            .map_err(|_| BackendError::unknown("the app stopped serving the contract"))?;
+        result
+            .recv()
+            .map_err(|_| BackendError::unknown("the app dropped a call of the contract"))
+    }
+}
+
+impl HostReceiver {
+    /// Serve the accesses of the backend until the instance using it is dropped
+    pub fn serve(self, host: &mut Host<'_>) {
+        for call in self.0 {
+            call(host)
+        }
+    }
+}
+
+/// Storage of a contract during a call.
+///
+/// Reads and writes go to the storage of the contract in the app. Iterators are collected when created.
+pub(crate) struct VmStorage {
+    host: HostSender,
+    iterators: Vec<std::vec::IntoIter<Record>>,
+}
+
+impl VmStorage {
+    pub fn new(host: HostSender) -> Self {
+        Self {
+            host,
+            iterators: vec![],
+        }
+    }
+}
+
+impl cosmwasm_vm::Storage for VmStorage {
+    fn get(&self, key: &[u8]) -> BackendResult<Option<Vec<u8>>> {
+        let key = key.to_vec();
+        let size = key.len();
+        match self.host.call(move |host| host.storage.get().get(&key)) {
+            Ok(value) => {
+                let size = size + value.as_ref().map(Vec::len).unwrap_or_default();
+                (
+                    Ok(value),
+                    sdk_gas(READ_COST_FLAT + READ_COST_PER_BYTE * size as u64),
+                )
+            }
+            Err(e) => (Err(e), GasInfo::free()),
+        }
+    }
+
+    fn scan(
+        &mut self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> BackendResult<u32> {
+        let start = start.map(<[u8]>::to_vec);
+        let end = end.map(<[u8]>::to_vec);
+        let records = self.host.call(move |host| match (&start, &end) {
+            // Empty range, some storages panic on those
+            (Some(start), Some(end)) if start >= end => vec![],
+            _ => host
+                .storage
+                .get()
+                .range(start.as_deref(), end.as_deref(), order)
+                .collect::<Vec<_>>(),
+        });
+        match records {
+            Ok(records) => {
+                self.iterators.push(records.into_iter());
+                (
+                    Ok((self.iterators.len() - 1) as u32),
+                    sdk_gas(READ_COST_FLAT),
+                )
+            }
+            Err(e) => (Err(e), GasInfo::free()),
+        }
+    }
+
+    fn next(&mut self, iterator_id: u32) -> BackendResult<Option<Record>> {
+        let Some(iterator) = self.iterators.get_mut(iterator_id as usize) else {
+            return (
+                Err(BackendError::iterator_does_not_exist(iterator_id)),
+                GasInfo::free(),
+            );
+        };
+        let record = iterator.next();
+        let size = record
+            .as_ref()
+            .map(|(k, v)| k.len() + v.len())
+            .unwrap_or_default();
+        (
+            Ok(record),
+            sdk_gas(ITER_NEXT_COST_FLAT + READ_COST_PER_BYTE * size as u64),
+        )
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) -> BackendResult<()> {
+        let gas = sdk_gas(WRITE_COST_FLAT + WRITE_COST_PER_BYTE * (key.len() + value.len()) as u64);
+        let (key, value) = (key.to_vec(), value.to_vec());
+        let result = self.host.call(move |host| match &mut host.storage {
+            HostStorage::Mutable(storage) => {
+                storage.set(&key, &value);
+                Ok(())
+            }
+            HostStorage::ReadOnly(_) => Err(BackendError::user_err("storage is read-only")),
+        });
+        (result.and_then(|result| result), gas)
+    }
+
+    fn remove(&mut self, key: &[u8]) -> BackendResult<()> {
+        let key = key.to_vec();
+        let result = self.host.call(move |host| match &mut host.storage {
+            HostStorage::Mutable(storage) => {
+                storage.remove(&key);
+                Ok(())
+            }
+            HostStorage::ReadOnly(_) => Err(BackendError::user_err("storage is read-only")),
+        });
+        (result.and_then(|result| result), sdk_gas(DELETE_COST))
+    }
+}
+
+/// Api of the app, used by the VM while the calling [`WasmContract`](super::WasmContract) entry point runs.
+#[derive(Clone)]
+pub(crate) struct VmApi(HostSender);
+
+impl VmApi {
+    pub fn new(host: HostSender) -> Self {
+        Self(host)
+    }
+}
+
+impl BackendApi for VmApi {
+    fn addr_validate(&self, input: &str) -> BackendResult<()> {
+        let input = input.to_string();
+        let result = self.0.call(move |host| {
+            host.api
+                .addr_validate(&input)
+                .map(|_| ())
+                .map_err(|e| BackendError::user_err(e.to_string()))
+        });
+        (result.and_then(|result| result), GasInfo::free())
+    }
+
+    fn addr_canonicalize(&self, human: &str) -> BackendResult<Vec<u8>> {
+        let human = human.to_string();
+        let result = self.0.call(move |host| {
+            host.api
+                .addr_canonicalize(&human)
+                .map(|canonical| canonical.to_vec())
+                .map_err(|e| BackendError::user_err(e.to_string()))
+        });
+        (result.and_then(|result| result), GasInfo::free())
+    }
+
+    fn addr_humanize(&self, canonical: &[u8]) -> BackendResult<String> {
+        let canonical = canonical.to_vec();
+        let result = self.0.call(move |host| {
+            host.api
+                .addr_humanize(&canonical.into())
+                .map(|addr| addr.to_string())
+                .map_err(|e| BackendError::user_err(e.to_string()))
+        });
+        (result.and_then(|result| result), GasInfo::free())
+    }
+}
+
+/// Querier of the app, used by the VM while the calling [`WasmContract`](super::WasmContract) entry point runs.
+///
+/// The gas used by queried wasm contracts is reported separately.
+pub(crate) struct VmQuerier(HostSender);
+
+impl VmQuerier {
+    pub fn new(host: HostSender) -> Self {
+        Self(host)
+    }
+}
+
+impl cosmwasm_vm::Querier for VmQuerier {
+    fn query_raw(
+        &self,
+        request: &[u8],
+        _gas_limit: u64,
+    ) -> BackendResult<SystemResult<ContractResult<Binary>>> {
+        let request = request.to_vec();
+        (
+            self.0.call(move |host| host.querier.raw_query(&request)),
+            GasInfo::free(),
+        )
+    }
+}
@@ -0,0 +1,397 @@
+use std::{cell::OnceCell, sync::OnceLock};
+
+use cosmwasm_std::{
+    from_json, to_json_vec, Addr, Binary, Checksum, ContractResult, CustomMsg, CustomQuery, Deps,
+    DepsMut, Env, MessageInfo, QuerierWrapper, Reply, Response,
+};
+use cosmwasm_vm::{
+    call_execute_raw, call_instantiate_raw, call_migrate_raw, call_query_raw, call_reply_raw,
+    call_sudo_raw, capabilities_from_csv, Backend, Cache, CacheOptions, Instance, InstanceOptions,
+    Size, VmError, VmResult,
+};
+use cw_multi_test::{AnyResult, Contract};
+use cw_orch_core::{contract::WasmPath, environment::GasEntryPoint, CwEnvError};
+use serde::de::DeserializeOwned;
+
+use super::{
+    backend::{
+        host_channel, Host, HostStorage, VmApi, VmQuerier, VmStorage, GAS_MULTIPLIER, INSTANCE_COST,
+    },
+    ContractRef, WasmExecution,
+};
+
+/// Maximum SDK gas a single contract call can use
+const GAS_LIMIT: u64 = 100_000_000;
+/// Maximum size of an uploaded wasm code in bytes, as in wasmd
+const MAX_WASM_SIZE: usize = 800 * 1024;
+/// SDK gas per byte of uploaded code, as in wasmd
+const COMPILE_COST: u64 = 3;
+/// Memory limit of the instances in MiB, as in wasmd
+const MEMORY_LIMIT_MIB: usize = 32;
+/// Size of the in-memory cache of compiled modules in MiB
+const MEMORY_CACHE_MIB: usize = 256;
+/// Capabilities available to the contracts, as on a recent wasmd chain
+const CAPABILITIES: &str = "iterator,staking,stargate,cosmwasm_1_1,cosmwasm_1_2,cosmwasm_1_3,cosmwasm_1_4,cosmwasm_2_0,cosmwasm_2_1";
+
+type VmInstance = Instance<VmApi, VmStorage, VmQuerier>;
+type VmCache = Cache<VmApi, VmStorage, VmQuerier>;
+
+/// Cache of the compiled codes, shared by all the Mock environments of the process
+/// so that each code is compiled once instead of once per call.
+///
+/// The compiled codes are stored in a directory shared by the processes using the same version of this crate,
+/// instead of one directory per process left behind in the temporary directory.
+/// cosmwasm-vm stores them under its module serialization version, so VM upgrades don't reuse stale modules.
+fn cache() -> AnyResult<&'static VmCache> {
+    static CACHE: OnceLock<VmCache> = OnceLock::new();
+    if let Some(cache) = CACHE.get() {
+        return Ok(cache);
+    }
+    let options = CacheOptions::new(
+        std::env::temp_dir().join(format!("cw-orch-mock-wasm-{}", env!("CARGO_PKG_VERSION"))),
+        capabilities_from_csv(CAPABILITIES),
+        Size::mebi(MEMORY_CACHE_MIB),
+        Size::mebi(MEMORY_LIMIT_MIB),
+    );
+    // SAFETY: the cache directory is only written by the caches of this crate, from the codes they compiled
+    let cache = unsafe { Cache::new(options) }.map_err(vm_error)?;
+    Ok(CACHE.get_or_init(|| cache))
+}
+
+/// Contract running its compiled wasm code in a cosmwasm-vm instance, reporting the gas used by its calls.
+pub struct WasmContract {
+    checksum: Checksum,
+    /// Size of the code in bytes
+    code_size: usize,
+    execution: WasmExecution,
+}
+
+impl WasmContract {
+    /// Compile the code of the contract, fails if the code isn't a valid contract
+    pub fn new(code: &[u8], execution: WasmExecution) -> Result<Self, CwEnvError> {
+        let checksum = cache()?.save_wasm(code).map_err(vm_error)?;
+        Ok(Self {
+            checksum,
+            code_size: code.len(),
+            execution,
+        })
+    }
+
+    /// Load the code of the contract from a wasm file.
+    /// Fails if the code exceeds the size limit of wasmd.
+    pub fn from_path(path: &WasmPath, execution: WasmExecution) -> Result<Self, CwEnvError> {
+        let code = std::fs::read(path.path())?;
+        if code.len() > MAX_WASM_SIZE {
+            return Err(CwEnvError::StdErr(format!(
+                "wasm code {} is {} bytes, above the upload limit of {MAX_WASM_SIZE} bytes",
+                path.path().display(),
+                code.len()
+            )));
+        }
+        Self::new(&code, execution)
+    }
+
+    /// Gas used to upload the code, as charged by wasmd
+    pub fn upload_gas(&self) -> u64 {
+        COMPILE_COST * self.code_size as u64
+    }
+
+    /// Run an entry point of the contract and return its raw result.
+    ///
+    /// The instance runs on a scoped thread while this thread serves its accesses to the storage, api and querier.
+    fn run(
+        &self,
+        mut host: Host<'_>,
+        contract: Addr,
+        entry_point: GasEntryPoint,
+        message: String,
+        call: impl FnOnce(&mut VmInstance) -> VmResult<Vec<u8>> + Send,
+    ) -> AnyResult<Vec<u8>> {
+        let cache = cache()?;
+        let checksum = self.checksum;
+        let (sender, receiver) = host_channel();
+        let (result, gas_used) = std::thread::scope(|scope| {
+            let vm = scope.spawn(move || {
+                let backend = Backend {
+                    api: VmApi::new(sender.clone()),
+                    storage: VmStorage::new(sender.clone()),
+                    querier: VmQuerier::new(sender),
+                };
+                let options = InstanceOptions {
+                    gas_limit: GAS_LIMIT * GAS_MULTIPLIER,
+                };
+                let mut instance = cache.get_instance(&checksum, backend, options)?;
+                let result = call(&mut instance);
+                let gas = instance.create_gas_report();
+                Ok::<_, VmError>((result, gas.limit - gas.remaining))
+            });
+            // Returns once the instance and its backend are dropped
+            receiver.serve(&mut host);
+            vm.join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        })
+        .map_err(vm_error)?;
+
+        self.execution.record(
+            ContractRef::Address(contract),
+            entry_point,
+            message,
+            INSTANCE_COST + gas_used / GAS_MULTIPLIER,
+        );
+        Ok(result.map_err(vm_error)?)
+    }
+}
+
+fn vm_error(error: VmError) -> anyhow::Error {
+    anyhow::anyhow!("wasm execution failed: {error}")
+}
+
+/// Parse the raw result of an entry point
+fn contract_result<T: DeserializeOwned>(result: &[u8]) -> AnyResult<T> {
+    from_json::<ContractResult<T>>(result)?
+        .into_result()
+        .map_err(anyhow::Error::msg)
+}
+
+/// Name of the message variant of a JSON message, for instance `transfer` for `{"transfer": {..}}`
+fn message_name(msg: &[u8]) -> String {
+    match serde_json::from_slice::<serde_json::Value>(msg) {
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        Ok(serde_json::Value::String(variant)) => variant,
+        _ => String::new(),
+    }
+}
+
+impl<ExecC, QueryC> Contract<ExecC, QueryC> for WasmContract
+where
+    ExecC: CustomMsg + DeserializeOwned,
+    QueryC: CustomQuery,
+{
+    fn execute(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        let contract = env.contract.address.clone();
+        let (env, info) = (to_json_vec(&env)?, to_json_vec(&info)?);
+        let host = Host {
+            storage: HostStorage::Mutable(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(
+            host,
+            contract,
+            GasEntryPoint::Execute,
+            message_name(&msg),
+            move |i| call_execute_raw(i, &env, &info, &msg),
+        )?;
+        contract_result(&result)
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        let contract = env.contract.address.clone();
+        let (env, info) = (to_json_vec(&env)?, to_json_vec(&info)?);
+        let host = Host {
+            storage: HostStorage::Mutable(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(
+            host,
+            contract,
+            GasEntryPoint::Instantiate,
+            message_name(&msg),
+            move |i| call_instantiate_raw(i, &env, &info, &msg),
+        )?;
+        contract_result(&result)
+    }
+
+    fn query(&self, deps: Deps<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Binary> {
+        let contract = env.contract.address.clone();
+        let env = to_json_vec(&env)?;
+        let host = Host {
+            storage: HostStorage::ReadOnly(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(
+            host,
+            contract,
+            GasEntryPoint::Query,
+            message_name(&msg),
+            move |i| call_query_raw(i, &env, &msg),
+        )?;
+        contract_result(&result)
+    }
+
+    fn sudo(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        let contract = env.contract.address.clone();
+        let env = to_json_vec(&env)?;
+        let host = Host {
+            storage: HostStorage::Mutable(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(
+            host,
+            contract,
+            GasEntryPoint::Sudo,
+            message_name(&msg),
+            move |i| call_sudo_raw(i, &env, &msg),
+        )?;
+        contract_result(&result)
+    }
+
+    fn reply(&self, deps: DepsMut<QueryC>, env: Env, msg: Reply) -> AnyResult<Response<ExecC>> {
+        let (contract, message) = (env.contract.address.clone(), msg.id.to_string());
+        let (env, msg) = (to_json_vec(&env)?, to_json_vec(&msg)?);
+        let host = Host {
+            storage: HostStorage::Mutable(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(host, contract, GasEntryPoint::Reply, message, move |i| {
+            call_reply_raw(i, &env, &msg)
+        })?;
+        contract_result(&result)
+    }
+
+    fn migrate(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        let contract = env.contract.address.clone();
+        let env = to_json_vec(&env)?;
+        let host = Host {
+            storage: HostStorage::Mutable(deps.storage),
+            api: deps.api,
+            querier: &*deps.querier,
+        };
+        let result = self.run(
+            host,
+            contract,
+            GasEntryPoint::Migrate,
+            message_name(&msg),
+            move |i| call_migrate_raw(i, &env, &msg),
+        )?;
+        contract_result(&result)
+    }
+
+    fn checksum(&self) -> Option<Checksum> {
+        Some(self.checksum)
+    }
+}
+
+/// Native contract of an interface, built when first needed
+type ContractWrapperFn<ExecC, QueryC> =
+    fn() -> Result<Box<dyn Contract<ExecC, QueryC>>, CwEnvError>;
+
+/// Code of an interface with contracts selected by [`MockBase::use_wasm`](crate::MockBase::use_wasm).
+///
+/// The code runs from its wasm artifact when called on a selected contract and natively otherwise,
+/// the contract is identified by its code id (see [`WasmExecution::start_tx`]).
+pub(crate) struct SelectedWasmContract<ExecC, QueryC> {
+    wasm: WasmContract,
+    native: OnceCell<Box<dyn Contract<ExecC, QueryC>>>,
+    wrapper: ContractWrapperFn<ExecC, QueryC>,
+}
+
+impl<ExecC, QueryC> SelectedWasmContract<ExecC, QueryC>
+where
+    ExecC: CustomMsg + DeserializeOwned + 'static,
+    QueryC: CustomQuery + 'static,
+{
+    pub fn new(wasm: WasmContract, wrapper: ContractWrapperFn<ExecC, QueryC>) -> Self {
+        Self {
+            wasm,
+            native: OnceCell::new(),
+            wrapper,
+        }
+    }
+
+    /// Contract running the call on `address`
+    fn contract(
+        &self,
+        querier: &QuerierWrapper<QueryC>,
+        address: &Addr,
+    ) -> AnyResult<&dyn Contract<ExecC, QueryC>> {
+        let code_id = querier.query_wasm_contract_info(address)?.code_id;
+        if self.wasm.execution.selects_code(code_id) {
+            return Ok(&self.wasm);
+        }
+        if self.native.get().is_none() {
+            let _ = self.native.set((self.wrapper)()?);
+        }
+        Ok(self.native.get().unwrap().as_ref())
+    }
+}
+
+impl<ExecC, QueryC> Contract<ExecC, QueryC> for SelectedWasmContract<ExecC, QueryC>
+where
+    ExecC: CustomMsg + DeserializeOwned + 'static,
+    QueryC: CustomQuery + 'static,
+{
+    fn execute(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .execute(deps, env, info, msg)
+    }
+
+    fn instantiate(
+        &self,
+        deps: DepsMut<QueryC>,
+        env: Env,
+        info: MessageInfo,
+        msg: Vec<u8>,
+    ) -> AnyResult<Response<ExecC>> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .instantiate(deps, env, info, msg)
+    }
+
+    fn query(&self, deps: Deps<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Binary> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .query(deps, env, msg)
+    }
+
+    fn sudo(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .sudo(deps, env, msg)
+    }
+
+    fn reply(&self, deps: DepsMut<QueryC>, env: Env, msg: Reply) -> AnyResult<Response<ExecC>> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .reply(deps, env, msg)
+    }
+
+    fn migrate(&self, deps: DepsMut<QueryC>, env: Env, msg: Vec<u8>) -> AnyResult<Response<ExecC>> {
+        self.contract(&deps.querier, &env.contract.address)?
+            .migrate(deps, env, msg)
+    }
+
+    fn checksum(&self) -> Option<Checksum> {
+        Some(self.wasm.checksum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::message_name;
+
+    #[test]
+    fn message_names() {
+        assert_eq!(message_name(br#"{"transfer":{"amount":"1"}}"#), "transfer");
+        assert_eq!(message_name(br#""reset""#), "reset");
+        assert_eq!(message_name(b"{}"), "");
+    }
+}
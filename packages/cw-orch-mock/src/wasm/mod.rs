@@ -0,0 +1,295 @@
+//! Execution of the compiled wasm of the contracts in Mock environments, with gas metering.
+//!
+//! Enabled for all the contracts with [`MockBase::with_gas_metering`] or per contract with [`MockBase::use_wasm`] (requires the `wasm` feature).
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    rc::Rc,
+};
+
+use cosmwasm_std::{Addr, Api, Event};
+use cw_multi_test::{AnyResult, AppResponse, Contract};
+#[cfg(feature = "wasm")]
+use cw_orch_core::contract::interface_traits::ContractInstance;
+use cw_orch_core::{
+    contract::interface_traits::Uploadable,
+    environment::{GasEntryPoint, GasReport, GasReportKey, StateInterface, GAS_USED_EVENT},
+    CwEnvError,
+};
+
+use crate::{
+    core::{CustomModule, MockApp},
+    custom::contract_wrapper,
+    MockBase,
+};
+
+#[cfg(feature = "wasm")]
+mod backend;
+#[cfg(feature = "wasm")]
+mod contract;
+
+#[cfg(feature = "wasm")]
+use contract::SelectedWasmContract;
+#[cfg(feature = "wasm")]
+pub use contract::WasmContract;
+
+/// Gas metering state of a [`MockBase`] environment, shared with the contracts it runs.
+#[derive(Clone, Debug, Default)]
+pub struct WasmExecution {
+    inner: Rc<RefCell<WasmExecutionState>>,
+}
+
+#[derive(Debug, Default)]
+struct WasmExecutionState {
+    /// Contracts uploaded while enabled run from their wasm artifact
+    enabled: bool,
+    /// Ids of the contracts running from their wasm artifact when not enabled for all contracts,
+    /// with the type name of their interface
+    contracts: BTreeMap<String, &'static str>,
+    /// Code ids of the selected contracts in the state, as of the start of the current transaction
+    selected_codes: BTreeSet<u64>,
+    report: GasReport,
+    /// Calls waiting for their contract to be saved in the state
+    pending: Vec<MeteredCall>,
+    /// Gas used by the wasm contracts called since the start of the current transaction,
+    /// `None` if none of them ran
+    tx_gas: Option<u64>,
+}
+
+/// Contract a metered call was sent to, resolved to its contract id from the state
+#[derive(Debug, Clone)]
+#[cfg_attr(not(feature = "wasm"), allow(dead_code))]
+pub(crate) enum ContractRef {
+    /// Code uploaded by the call
+    CodeId(u64),
+    Address(Addr),
+}
+
+#[derive(Debug)]
+struct MeteredCall {
+    contract: ContractRef,
+    entry_point: GasEntryPoint,
+    message: String,
+    gas: u64,
+}
+
+impl WasmExecution {
+    /// Whether all contracts are uploaded from their wasm artifact
+    pub fn is_enabled(&self) -> bool {
+        self.inner.borrow().enabled
+    }
+
+    /// Whether the contract with id `contract_id` runs from its wasm artifact
+    pub fn runs_wasm(&self, contract_id: &str) -> bool {
+        let state = self.inner.borrow();
+        state.enabled || state.contracts.contains_key(contract_id)
+    }
+
+    /// Whether the code is used by a selected contract
+    #[cfg(feature = "wasm")]
+    pub(crate) fn selects_code(&self, code_id: u64) -> bool {
+        self.inner.borrow().selected_codes.contains(&code_id)
+    }
+
+    /// Whether a contract with the interface `T` was selected to run from its wasm artifact
+    #[cfg(feature = "wasm")]
+    fn selects<T: Uploadable>(&self) -> bool {
+        self.inner
+            .borrow()
+            .contracts
+            .values()
+            .any(|type_name| *type_name == std::any::type_name::<T>())
+    }
+
+    /// Gas used by the wasm contracts so far, keyed by the contract ids of `state`
+    pub fn gas_report(&self, state: &impl StateInterface) -> GasReport {
+        self.resolve_pending(state);
+        self.inner.borrow().report.clone()
+    }
+
+    /// Clear the gas report
+    pub fn reset_gas_report(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.report.clear();
+        inner.pending.clear();
+    }
+
+    #[cfg(feature = "wasm")]
+    pub(crate) fn enable(&self) {
+        self.inner.borrow_mut().enabled = true
+    }
+
+    #[cfg(feature = "wasm")]
+    pub(crate) fn enable_for<T: Uploadable>(&self, contract_id: String) {
+        self.inner
+            .borrow_mut()
+            .contracts
+            .insert(contract_id, std::any::type_name::<T>());
+    }
+
+    /// Records the gas used by a call, reported once its contract is saved in the state
+    pub(crate) fn record(
+        &self,
+        contract: ContractRef,
+        entry_point: GasEntryPoint,
+        message: String,
+        gas: u64,
+    ) {
+        let mut state = self.inner.borrow_mut();
+        state.pending.push(MeteredCall {
+            contract,
+            entry_point,
+            message,
+            gas,
+        });
+        *state.tx_gas.get_or_insert(0) += gas;
+    }
+
+    /// Add the pending calls to the report, keyed by contract id like the [`GasProfiler`](cw_orch_core::environment::GasProfiler).
+    /// Calls to contracts missing from the state are keyed by address or code id.
+    fn resolve_pending(&self, state: &impl StateInterface) {
+        let mut inner = self.inner.borrow_mut();
+        if inner.pending.is_empty() {
+            return;
+        }
+        let addresses = state.get_all_addresses().unwrap_or_default();
+        let code_ids = state.get_all_code_ids().unwrap_or_default();
+        for call in std::mem::take(&mut inner.pending) {
+            let contract = match &call.contract {
+                ContractRef::Address(address) => addresses
+                    .iter()
+                    .filter(|(_, a)| *a == address)
+                    .map(|(id, _)| id.clone())
+                    .min()
+                    .unwrap_or_else(|| address.to_string()),
+                ContractRef::CodeId(code_id) => code_ids
+                    .iter()
+                    .filter(|(_, id)| *id == code_id)
+                    .map(|(id, _)| id.clone())
+                    .min()
+                    .unwrap_or_else(|| code_id.to_string()),
+            };
+            // The codes of the selected interfaces are also uploaded for contracts running natively
+            if call.entry_point == GasEntryPoint::Upload
+                && !inner.enabled
+                && !inner.contracts.contains_key(&contract)
+            {
+                continue;
+            }
+            inner.report.record(
+                GasReportKey::new(contract, call.entry_point, call.message),
+                call.gas,
+            );
+        }
+    }
+
+    /// Reset the gas of the transaction and look up the code ids of the selected contracts,
+    /// which the contract interfaces save in the state after their upload
+    pub(crate) fn start_tx(&self, state: &impl StateInterface) {
+        self.resolve_pending(state);
+        let mut inner = self.inner.borrow_mut();
+        inner.tx_gas = None;
+        if inner.contracts.is_empty() {
+            return;
+        }
+        let selected_codes = state
+            .get_all_code_ids()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(id, _)| inner.contracts.contains_key(id))
+            .map(|(_, code_id)| code_id)
+            .collect();
+        inner.selected_codes = selected_codes;
+    }
+
+    /// Adds the gas used by the transaction to its events when wasm contracts ran during the transaction
+    pub(crate) fn end_tx(&self, mut response: AppResponse) -> AppResponse {
+        if let Some(gas) = self.inner.borrow().tx_gas {
+            response.events.push(
+                Event::new(GAS_USED_EVENT.0).add_attribute(GAS_USED_EVENT.1, gas.to_string()),
+            );
+        }
+        response
+    }
+}
+
+impl<A: Api, S: StateInterface, C: CustomModule> MockBase<A, S, C> {
+    /// Run the contracts uploaded from now on from their wasm artifact ([`Uploadable::wasm`]) and meter the gas they use.
+    ///
+    /// The gas used per call is aggregated in the [`MockBase::gas_report`]
+    /// and the gas used by each transaction is available with [`IndexResponse::gas_used`](cw_orch_core::environment::IndexResponse::gas_used).
+    ///
+    /// Gas amounts approximate the ones of wasmd (VM execution, storage access and instance cost),
+    /// they are suited to compare versions of a contract but don't predict exact fees.
+    #[cfg(feature = "wasm")]
+    pub fn with_gas_metering(self) -> Self {
+        self.wasm.enable();
+        self
+    }
+
+    /// Run `contract` from its wasm artifact ([`Uploadable::wasm`]) once uploaded,
+    /// while the other contracts, including the other instances of its interface, keep running natively.
+    ///
+    /// This allows a test to run against the optimized artifact of a contract, catching issues specific to wasm
+    /// (floating point operations, code size limits, serialization differences).
+    /// The gas used by the selected contracts is reported as with [`MockBase::with_gas_metering`],
+    /// the gas used by native contracts isn't counted.
+    ///
+    /// ```ignore
+    /// let mock = Mock::new("sender");
+    /// let counter = CounterContract::new(mock.clone());
+    /// mock.use_wasm(&counter);
+    /// counter.upload()?;
+    /// ```
+    #[cfg(feature = "wasm")]
+    pub fn use_wasm<T: Uploadable + ContractInstance<Self>>(&self, contract: &T) {
+        self.wasm.enable_for::<T>(contract.id())
+    }
+
+    /// Gas used by the wasm contracts per contract id, see [`MockBase::with_gas_metering`]
+    pub fn gas_report(&self) -> GasReport {
+        self.wasm.gas_report(&*self.state.borrow())
+    }
+
+    /// Clear the gas report, for instance after the setup of a test
+    pub fn reset_gas_report(&self) {
+        self.wasm.reset_gas_report()
+    }
+
+    /// Runs a transaction, metering the gas used by the wasm contracts it calls
+    pub(crate) fn metered_tx(
+        &self,
+        tx: impl FnOnce(&mut MockApp<A, C>) -> AnyResult<AppResponse>,
+    ) -> Result<AppResponse, CwEnvError> {
+        self.wasm.start_tx(&*self.state.borrow());
+        let response = tx(&mut self.app.borrow_mut())?;
+        Ok(self.wasm.end_tx(response))
+    }
+
+    /// Code of the contract to store in the app, with the gas used by its upload when it runs from its wasm artifact
+    pub(crate) fn contract_code<T: Uploadable>(
+        &self,
+    ) -> Result<(Box<dyn Contract<C::ExecT, C::QueryT>>, Option<u64>), CwEnvError> {
+        #[cfg(feature = "wasm")]
+        if self.wasm.is_enabled() || self.wasm.selects::<T>() {
+            let chain_info = cw_orch_core::environment::ChainInfoOwned {
+                chain_id: self.app.borrow().block_info().chain_id,
+                ..Default::default()
+            };
+            let contract = WasmContract::from_path(&T::wasm(&chain_info), self.wasm.clone())?;
+            let upload_gas = Some(contract.upload_gas());
+            if self.wasm.is_enabled() {
+                return Ok((Box::new(contract), upload_gas));
+            }
+            // The code can be shared with contracts that weren't selected, those run natively
+            return Ok((
+                Box::new(SelectedWasmContract::new(
+                    contract,
+                    contract_wrapper::<T, _, _>,
+                )),
+                upload_gas,
+            ));
+        }
+        Ok((contract_wrapper::<T, _, _>()?, None))
+    }
+}
@@ -3,7 +3,13 @@ mod tests {
         DaemonAsync contract general tests
     */
 
-    use cw_orch_core::{contract::interface_traits::*, environment::TxHandler};
+    use std::collections::HashMap;
+
+    use cw_orch_core::{
+        contract::{interface_traits::*, Deploy, DriftIssue, VerifyDeployment},
+        environment::TxHandler,
+        CwEnvError,
+    };
     use cw_orch_mock::Mock;
     use mock_contract::{InstantiateMsg, MigrateMsg, QueryMsg};
 
@@ -71,6 +77,142 @@ mod tests {
             .is_ok();
     }
 
+    #[test]
+    fn drift_detection() {
+        let chain = Mock::new("sender");
+        let sender = chain.sender_addr();
+
+        let contract = mock_contract::MockContract::new("test:mock_contract", chain.clone());
+
+        let drift = contract.drift(None).unwrap();
+        asserting!("code id is missing")
+            .that(&drift.issues)
+            .contains(DriftIssue::CodeIdMissing);
+
+        contract.upload().unwrap();
+        contract
+            .instantiate(&InstantiateMsg {}, Some(&sender), &[])
+            .unwrap();
+        let code_id = contract.code_id().unwrap();
+
+        let drift = contract.drift(Some(&sender)).unwrap();
+        asserting!("contract is found")
+            .that(&drift.is_missing())
+            .is_false();
+        asserting!("running code id is registered")
+            .that(&drift.running_code_id)
+            .is_equal_to(Some(code_id));
+        asserting!("admin is registered")
+            .that(&drift.admin)
+            .is_equal_to(Some(sender.clone()));
+
+        // Upload a new version without migrating the contract
+        contract.upload().unwrap();
+
+        let drift = contract.drift(Some(&sender)).unwrap();
+        asserting!("contract is not tampered")
+            .that(&drift.is_tampered())
+            .is_false();
+        asserting!("contract is stale")
+            .that(&drift.is_stale())
+            .is_true();
+        asserting!("pending migration is reported")
+            .that(&drift.issues)
+            .contains(DriftIssue::NotMigrated {
+                latest: code_id + 1,
+                running: code_id,
+            });
+
+        let other_admin = chain.addr_make("other");
+        let drift = contract.drift(Some(&other_admin)).unwrap();
+        asserting!("contract is tampered")
+            .that(&drift.is_tampered())
+            .is_true();
+        asserting!("admin mismatch is reported")
+            .that(&drift.issues)
+            .contains(DriftIssue::AdminMismatch {
+                expected: Some(other_admin),
+                actual: Some(sender.clone()),
+            });
+
+        contract
+            .migrate(
+                &MigrateMsg {
+                    t: "success".to_string(),
+                },
+                code_id + 1,
+            )
+            .unwrap();
+        let contracts: Vec<&dyn VerifiableContract<Mock>> = vec![&contract];
+        let drift = contracts[0].check_drift(None, Some(&sender)).unwrap();
+        asserting!("migrated contract is not stale")
+            .that(&drift.is_stale())
+            .is_false();
+        asserting!("migrated contract is not tampered")
+            .that(&drift.is_tampered())
+            .is_false();
+    }
+
+    struct MockDeployment {
+        contract: mock_contract::MockContract<Mock>,
+    }
+
+    impl Deploy<Mock> for MockDeployment {
+        type Error = CwEnvError;
+        type DeployData = ();
+
+        fn store_on(chain: Mock) -> Result<Self, CwEnvError> {
+            let contract = mock_contract::MockContract::new("test:mock_contract", chain);
+            contract.upload()?;
+            Ok(Self { contract })
+        }
+
+        fn get_contracts_mut(&mut self) -> Vec<Box<&mut dyn ContractInstance<Mock>>> {
+            vec![Box::new(&mut self.contract)]
+        }
+
+        fn load_from(chain: Mock) -> Result<Self, CwEnvError> {
+            let contract = mock_contract::MockContract::new("test:mock_contract", chain);
+            Ok(Self { contract })
+        }
+    }
+
+    #[test]
+    fn verify_deployment() {
+        let chain = Mock::new("sender");
+        let sender = chain.sender_addr();
+
+        let mut deployment = MockDeployment::store_on(chain.clone()).unwrap();
+        deployment
+            .contract
+            .instantiate(&InstantiateMsg {}, Some(&sender), &[])
+            .unwrap();
+
+        let report = deployment
+            .verify_deployment(&HashMap::new(), Some(&sender))
+            .unwrap();
+        asserting!("deployment is up to date")
+            .that(&report.is_up_to_date())
+            .is_true();
+
+        deployment.contract.upload().unwrap();
+        let report = deployment
+            .verify_deployment(&HashMap::new(), Some(&sender))
+            .unwrap();
+        asserting!("contract is not migrated")
+            .that(&report.stale().count())
+            .is_equal_to(1);
+
+        let missing = chain.addr_make("missing");
+        deployment.contract.set_address(&missing);
+        let report = deployment
+            .verify_deployment(&HashMap::new(), Some(&sender))
+            .unwrap();
+        asserting!("missing contract is reported")
+            .that(&report.get("test:mock_contract").unwrap().issues)
+            .contains(DriftIssue::ContractNotFound { address: missing });
+    }
+
     #[test]
     fn cw_orch_interface_traits() {
         let chain = Mock::new("sender");
@@ -0,0 +1,83 @@
+use cw_orch_core::{
+    contract::{interface_traits::*, ActionKind, ContractStep, DeploymentPlan},
+    CwEnvError,
+};
+use cw_orch_mock::Mock;
+use mock_contract::{InstantiateMsg, MockContract};
+
+use speculoos::prelude::*;
+
+#[test]
+fn plan_orders_and_resumes() -> anyhow::Result<()> {
+    let chain = Mock::new("sender");
+
+    let first = MockContract::new("test:first", chain.clone());
+    let second = MockContract::new("test:second", chain.clone());
+    let uploaded = MockContract::new("test:uploaded", chain.clone());
+
+    let plan = DeploymentPlan::new(chain.clone())
+        .step(
+            ContractStep::instantiate(&second, |ctx| {
+                ctx.address("test:first")?;
+                Ok(InstantiateMsg {})
+            })
+            .depends_on("test:first"),
+        )
+        .step(ContractStep::upload(&uploaded))
+        .step(ContractStep::instantiate(&first, |_| Ok(InstantiateMsg {})));
+
+    let dry_run = plan.dry_run()?;
+    let order: Vec<_> = dry_run
+        .actions
+        .iter()
+        .map(|a| (a.contract_id.as_str(), a.kind))
+        .collect();
+    asserting!("dependencies are deployed first")
+        .that(&order)
+        .is_equal_to(vec![
+            ("test:uploaded", ActionKind::Upload),
+            ("test:first", ActionKind::Upload),
+            ("test:first", ActionKind::Instantiate),
+            ("test:second", ActionKind::Upload),
+            ("test:second", ActionKind::Instantiate),
+        ]);
+    asserting!("dry run doesn't deploy")
+        .that(&first.code_id())
+        .is_err();
+
+    // Simulate a half-finished deployment
+    first.upload()?;
+
+    let summary = plan.execute()?;
+    asserting!("uploaded contract is skipped")
+        .that(&summary.skipped().count())
+        .is_equal_to(1);
+    asserting!("contracts are deployed")
+        .that(&second.address())
+        .is_ok();
+
+    let summary = plan.execute()?;
+    asserting!("deployed plan is skipped")
+        .that(&summary.executed().count())
+        .is_equal_to(0);
+    Ok(())
+}
+
+#[test]
+fn plan_with_cycle() {
+    let chain = Mock::new("sender");
+
+    let first = MockContract::new("test:first", chain.clone());
+    let second = MockContract::new("test:second", chain.clone());
+
+    let plan = DeploymentPlan::new(chain)
+        .step(ContractStep::upload(&first).depends_on("test:second"))
+        .step(ContractStep::upload(&second).depends_on("test:first"));
+
+    asserting!("cycle is detected")
+        .that(&matches!(
+            plan.dry_run(),
+            Err(CwEnvError::PlanDependencyCycle(_))
+        ))
+        .is_true();
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "wasm")]
+
+use cw_orch_core::{
+    contract::interface_traits::{
+        ContractInstance, CwOrchExecute, CwOrchInstantiate, CwOrchQuery, CwOrchUpload,
+    },
+    environment::{GasEntryPoint, GasProfiler, GasReportKey, IndexResponse},
+};
+use cw_orch_mock::Mock;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract, QueryMsg};
+
+#[test]
+fn reports_gas_of_wasm_contracts() -> anyhow::Result<()> {
+    let chain = Mock::new("sender").with_gas_metering();
+    let contract = MockContract::new("mock-contract", chain.clone());
+
+    contract.upload()?;
+    contract.instantiate(&InstantiateMsg {}, None, &[])?;
+    let response = contract.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    let gas_used = response.gas_used().unwrap();
+    assert!(gas_used > 0);
+
+    let result: String = contract.query(&QueryMsg::<String>::FirstQuery {})?;
+    assert_eq!(result, "first query passed");
+
+    let report = chain.gas_report();
+    assert!(report
+        .usage(&GasReportKey::new(
+            "mock-contract",
+            GasEntryPoint::Upload,
+            ""
+        ))
+        .is_some_and(|upload| upload.total > 0));
+    let execute = report
+        .usage(&GasReportKey::new(
+            "mock-contract",
+            GasEntryPoint::Execute,
+            "first_message",
+        ))
+        .unwrap();
+    assert_eq!(execute.calls, 1);
+    assert_eq!(execute.total, gas_used);
+    assert!(report
+        .usage(&GasReportKey::new(
+            "mock-contract",
+            GasEntryPoint::Query,
+            "first_query"
+        ))
+        .is_some());
+
+    // Failing calls don't change the storage but are reported
+    assert!(contract
+        .execute(
+            &ExecuteMsg::<String>::SecondMessage { t: String::new() },
+            &[]
+        )
+        .is_err());
+    assert!(chain
+        .gas_report()
+        .usage(&GasReportKey::new(
+            "mock-contract",
+            GasEntryPoint::Execute,
+            "second_message",
+        ))
+        .is_some());
+    Ok(())
+}
+
+#[test]
+fn profiles_gas_per_contract() -> anyhow::Result<()> {
+    let chain = GasProfiler::new(Mock::new("sender").with_gas_metering());
+    let first = MockContract::new("first", chain.clone());
+    let second = MockContract::new("second", chain.clone());
+
+    // Both contracts share the same code, the second one is instantiated and executed only
+    first.upload()?;
+    second.set_code_id(first.code_id()?);
+    first.instantiate(&InstantiateMsg {}, None, &[])?;
+    let instantiate = second.instantiate(&InstantiateMsg {}, None, &[])?;
+    second.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    second.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+
+    let report = chain.report();
+    let instantiate_key =
+        |contract| GasReportKey::new(contract, GasEntryPoint::Instantiate, "InstantiateMsg");
+    assert_eq!(report.usage(&instantiate_key("first")).unwrap().calls, 1);
+    let second_instantiate = report.usage(&instantiate_key("second")).unwrap();
+    assert_eq!(second_instantiate.calls, 1);
+    assert_eq!(second_instantiate.total, instantiate.gas_used().unwrap());
+
+    let execute_key =
+        |contract| GasReportKey::new(contract, GasEntryPoint::Execute, "first_message");
+    assert_eq!(report.usage(&execute_key("second")).unwrap().calls, 2);
+    assert!(report.usage(&execute_key("first")).is_none());
+    Ok(())
+}
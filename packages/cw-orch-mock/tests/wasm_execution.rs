@@ -0,0 +1,53 @@
+#![cfg(feature = "wasm")]
+
+use cw_orch_core::{
+    contract::interface_traits::{CwOrchExecute, CwOrchInstantiate, CwOrchQuery, CwOrchUpload},
+    environment::{GasEntryPoint, GasReportKey, IndexResponse},
+};
+use cw_orch_mock::Mock;
+use mock_contract::{ExecuteMsg, InstantiateMsg, MockContract, QueryMsg};
+
+#[test]
+fn runs_selected_contracts_from_wasm() -> anyhow::Result<()> {
+    let chain = Mock::new("sender");
+    let native = MockContract::new("native", chain.clone());
+
+    native.upload()?;
+    native.instantiate(&InstantiateMsg {}, None, &[])?;
+    let response = native.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    assert_eq!(response.gas_used(), None);
+    assert!(chain.gas_report().is_empty());
+
+    let wasm = MockContract::new("wasm", chain.clone());
+    chain.use_wasm(&wasm);
+    wasm.upload()?;
+    assert_ne!(wasm.code_id()?, native.code_id()?);
+    wasm.instantiate(&InstantiateMsg {}, None, &[])?;
+    let response = wasm.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    assert!(response.gas_used().unwrap() > 0);
+
+    let result: String = wasm.query(&QueryMsg::<String>::FirstQuery {})?;
+    assert_eq!(result, "first query passed");
+    assert!(chain
+        .gas_report()
+        .usage(&GasReportKey::new(
+            "wasm",
+            GasEntryPoint::Execute,
+            "first_message",
+        ))
+        .is_some());
+
+    // Other contracts with the same interface keep running natively
+    chain.reset_gas_report();
+    let other = MockContract::new("other", chain.clone());
+    other.upload()?;
+    other.instantiate(&InstantiateMsg {}, None, &[])?;
+    let response = other.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    assert_eq!(response.gas_used(), None);
+    assert!(chain.gas_report().is_empty());
+
+    // The selected contract still runs from its wasm artifact
+    let response = wasm.execute(&ExecuteMsg::<String>::FirstMessage {}, &[])?;
+    assert!(response.gas_used().unwrap() > 0);
+    Ok(())
+}
@@ -4,15 +4,18 @@ use cosmwasm_std::{coin, Addr, BankMsg, Coins};
 
 use cw_orch_core::contract::interface_traits::Uploadable;
 use cw_orch_core::contract::WasmPath;
-use cw_orch_core::environment::{BankQuerier, BankSetter, ChainInfo, DefaultQueriers, NetworkInfo};
+use cw_orch_core::environment::{
+    BankQuerier, BankSetter, ChainInfo, DefaultQueriers, NetworkInfo, GAS_USED_EVENT,
+};
 
-use cosmwasm_std::{Binary, Coin, Uint128};
+use cosmwasm_std::{Binary, Coin, Event, Uint128};
 use cw_orch_core::CwEnvError;
 use cw_orch_mock::cw_multi_test::AppResponse;
 use neutron_test_tube::cosmrs::proto::cosmos::bank::v1beta1::MsgSendResponse;
 use neutron_test_tube::{
     neutron_std::{cosmwasm_to_proto_coins, types::cosmos::bank::v1beta1::MsgSend},
-    Account, Bank, Module, NeutronTestApp, Runner, RunnerError, SigningAccount, Wasm,
+    Account, Bank, ExecuteResponse, Module, NeutronTestApp, Runner, RunnerError, SigningAccount,
+    Wasm,
 };
 use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
@@ -20,7 +23,11 @@ use serde::Serialize;
 
 use cw_orch_core::{
     environment::TxHandler,
-    environment::{ChainState, StateInterface},
+    environment::{ChainState, StateInterface, StorageDumper},
+};
+use neutron_test_tube::neutron_std::types::{
+    cosmos::base::query::v1beta1::PageRequest,
+    cosmwasm::wasm::v1::{QueryAllContractStateRequest, QueryAllContractStateResponse},
 };
 
 use cw_orch_mock::MockState;
@@ -79,6 +86,19 @@ pub(crate) fn map_err(e: RunnerError) -> CwEnvError {
     CwEnvError::StdErr(e.to_string())
 }
 
+/// Converts a test-tube response, adding the gas used by the transaction to its events
+pub(crate) fn to_app_response<R>(response: ExecuteResponse<R>) -> AppResponse {
+    let mut events = response.events;
+    events.push(
+        Event::new(GAS_USED_EVENT.0)
+            .add_attribute(GAS_USED_EVENT.1, response.gas_info.gas_used.to_string()),
+    );
+    AppResponse {
+        data: Some(Binary::new(response.raw_data)),
+        events,
+    }
+}
+
 impl<S: StateInterface> NeutronTestTube<S> {
     /// Creates an account and sets its balance
     pub fn init_account(
@@ -128,10 +148,7 @@ impl<S: StateInterface> NeutronTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(send_response.raw_data)),
-            events: send_response.events,
-        })
+        Ok(to_app_response(send_response))
     }
 
     /// Query the (bank) balance of a native token for and address.
@@ -213,10 +230,7 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             .store_code(&wasm_contents, None, &self.sender)
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(upload_response.raw_data)),
-            events: upload_response.events,
-        })
+        Ok(to_app_response(upload_response))
     }
 
     fn execute<E: Serialize + Debug>(
@@ -229,10 +243,7 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             .execute(contract_address.as_ref(), exec_msg, coins, &self.sender)
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(execute_response.raw_data)),
-            events: execute_response.events,
-        })
+        Ok(to_app_response(execute_response))
     }
 
     fn instantiate<I: Serialize + Debug>(
@@ -254,10 +265,7 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(instantiate_response.raw_data)),
-            events: instantiate_response.events,
-        })
+        Ok(to_app_response(instantiate_response))
     }
 
     fn migrate<M: Serialize + Debug>(
@@ -283,10 +291,7 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(migrate_response.raw_data)),
-            events: migrate_response.events,
-        })
+        Ok(to_app_response(migrate_response))
     }
 
     fn instantiate2<I: Serialize + Debug>(
@@ -319,10 +324,7 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(instantiate_response.raw_data)),
-            events: instantiate_response.events,
-        })
+        Ok(to_app_response(instantiate_response))
     }
 
     fn bank_send(
@@ -341,10 +343,46 @@ impl<S: StateInterface> TxHandler for NeutronTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(send_response.raw_data)),
-            events: send_response.events,
-        })
+        Ok(to_app_response(send_response))
+    }
+}
+
+impl<S: StateInterface> StorageDumper for NeutronTestTube<S> {
+    fn dump_contract_storage(&self, address: &Addr) -> Result<Vec<(Vec<u8>, Vec<u8>)>, CwEnvError> {
+        let mut storage = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryAllContractStateResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmwasm.wasm.v1.Query/AllContractState",
+                    &QueryAllContractStateRequest {
+                        address: address.to_string(),
+                        pagination: Some(PageRequest {
+                            key: next_key,
+                            offset: 0,
+                            limit: 0,
+                            count_total: false,
+                            reverse: false,
+                        }),
+                    },
+                )
+                .map_err(map_err)?;
+            storage.extend(
+                response
+                    .models
+                    .into_iter()
+                    .map(|model| (model.key, model.value)),
+            );
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        Ok(storage)
     }
 }
 
@@ -407,10 +445,7 @@ impl NeutronTestTube {
             .execute_multiple_raw::<R>(msgs, &self.sender)
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(tx_response.raw_data)),
-            events: tx_response.events,
-        })
+        Ok(to_app_response(tx_response))
     }
 }
 
@@ -425,6 +460,7 @@ pub mod tests {
     use super::NeutronTestTube;
     use counter_contract::{msg::InstantiateMsg, CounterContract};
     use cw_orch::prelude::*;
+    use cw_orch_core::environment::{GovQuerier, StakingQuerier};
 
     #[test]
     fn wasm_querier_works() -> cw_orch::anyhow::Result<()> {
@@ -502,4 +538,22 @@ pub mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn staking_and_gov_queriers_work() -> cw_orch::anyhow::Result<()> {
+        let app = NeutronTestTube::new(coins(100_000_000_000_000, GAS_TOKEN));
+        let sender = app.sender_addr();
+
+        let staking = app.staking_querier();
+        staking.bonded_denom()?;
+        assert!(staking.delegator_delegations(&sender)?.is_empty());
+        for validator in staking.validators()? {
+            assert_eq!(staking.delegation(&sender, &validator.address)?, None);
+        }
+
+        let gov = app.gov_querier();
+        assert!(gov.proposals(None)?.is_empty());
+        assert!(gov.proposal(1).is_err());
+        Ok(())
+    }
 }
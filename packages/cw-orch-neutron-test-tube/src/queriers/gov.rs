@@ -0,0 +1,369 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128, VoteOption};
+use cw_orch_core::environment::{
+    GovDeposit, GovProposal, GovQuerier, GovVote, GovVoteOption, ProposalMessage, ProposalStatus,
+    Querier, QuerierGetter, StateInterface, TallyResult,
+};
+use cw_orch_core::CwEnvError;
+use neutron_test_tube::neutron_std::{
+    try_proto_to_cosmwasm_coins,
+    types::cosmos::gov::v1beta1::{
+        QueryDepositsRequest, QueryDepositsResponse, QueryTallyResultRequest,
+        QueryTallyResultResponse, QueryVoteRequest, QueryVoteResponse, QueryVotesRequest,
+        QueryVotesResponse, TallyResult as ProtoTallyResult, Vote,
+    },
+};
+use neutron_test_tube::{NeutronTestApp, Runner};
+
+use super::page_request;
+use crate::{map_err, NeutronTestTube};
+
+pub struct NeutronTestTubeGovQuerier {
+    app: Rc<RefCell<NeutronTestApp>>,
+}
+
+impl NeutronTestTubeGovQuerier {
+    fn new<S: StateInterface>(mock: &NeutronTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for NeutronTestTubeGovQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<NeutronTestTubeGovQuerier> for NeutronTestTube<S> {
+    fn querier(&self) -> NeutronTestTubeGovQuerier {
+        NeutronTestTubeGovQuerier::new(self)
+    }
+}
+
+impl GovQuerier for NeutronTestTubeGovQuerier {
+    fn proposal(&self, proposal_id: u64) -> Result<GovProposal, Self::Error> {
+        let response: v1::QueryProposalResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.gov.v1.Query/Proposal",
+                &v1::QueryProposalRequest { proposal_id },
+            )
+            .map_err(map_err)?;
+        let proposal = response
+            .proposal
+            .ok_or_else(|| CwEnvError::StdErr(format!("proposal {proposal_id} doesn't exist")))?;
+        proto_to_gov_proposal(proposal)
+    }
+
+    fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<GovProposal>, Self::Error> {
+        let proposal_status = match status {
+            None => 0,
+            Some(ProposalStatus::DepositPeriod) => 1,
+            Some(ProposalStatus::VotingPeriod) => 2,
+            Some(ProposalStatus::Passed) => 3,
+            Some(ProposalStatus::Rejected) => 4,
+            Some(ProposalStatus::Failed) => 5,
+        };
+        let mut proposals = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: v1::QueryProposalsResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmos.gov.v1.Query/Proposals",
+                    &v1::QueryProposalsRequest {
+                        proposal_status,
+                        voter: String::new(),
+                        depositor: String::new(),
+                        pagination: Some(page_request(next_key)),
+                    },
+                )
+                .map_err(map_err)?;
+            proposals.extend(response.proposals);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        proposals.into_iter().map(proto_to_gov_proposal).collect()
+    }
+
+    fn vote(&self, proposal_id: u64, voter: &Addr) -> Result<GovVote, Self::Error> {
+        let response: QueryVoteResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.gov.v1beta1.Query/Vote",
+                &QueryVoteRequest {
+                    proposal_id,
+                    voter: voter.to_string(),
+                },
+            )
+            .map_err(map_err)?;
+        let vote = response.vote.ok_or_else(|| {
+            CwEnvError::StdErr(format!("{voter} didn't vote on proposal {proposal_id}"))
+        })?;
+        proto_to_gov_vote(vote)
+    }
+
+    fn votes(&self, proposal_id: u64) -> Result<Vec<GovVote>, Self::Error> {
+        let mut votes = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryVotesResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmos.gov.v1beta1.Query/Votes",
+                    &QueryVotesRequest {
+                        proposal_id,
+                        pagination: Some(page_request(next_key)),
+                    },
+                )
+                .map_err(map_err)?;
+            votes.extend(response.votes);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        votes.into_iter().map(proto_to_gov_vote).collect()
+    }
+
+    fn deposits(&self, proposal_id: u64) -> Result<Vec<GovDeposit>, Self::Error> {
+        let mut deposits = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryDepositsResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmos.gov.v1beta1.Query/Deposits",
+                    &QueryDepositsRequest {
+                        proposal_id,
+                        pagination: Some(page_request(next_key)),
+                    },
+                )
+                .map_err(map_err)?;
+            deposits.extend(response.deposits);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        deposits
+            .into_iter()
+            .map(|deposit| {
+                Ok(GovDeposit {
+                    proposal_id: deposit.proposal_id,
+                    depositor: deposit.depositor,
+                    amount: try_proto_to_cosmwasm_coins(deposit.amount)?,
+                })
+            })
+            .collect()
+    }
+
+    fn tally(&self, proposal_id: u64) -> Result<TallyResult, Self::Error> {
+        let response: QueryTallyResultResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.gov.v1beta1.Query/TallyResult",
+                &QueryTallyResultRequest { proposal_id },
+            )
+            .map_err(map_err)?;
+        proto_to_tally_result(response.tally.unwrap_or_default())
+    }
+}
+
+fn proto_to_gov_proposal(proposal: v1::Proposal) -> Result<GovProposal, CwEnvError> {
+    let status = match proposal.status {
+        1 => ProposalStatus::DepositPeriod,
+        2 => ProposalStatus::VotingPeriod,
+        3 => ProposalStatus::Passed,
+        4 => ProposalStatus::Rejected,
+        5 => ProposalStatus::Failed,
+        status => {
+            return Err(CwEnvError::StdErr(format!(
+                "unknown proposal status {status}"
+            )))
+        }
+    };
+    let final_tally_result = match status {
+        ProposalStatus::DepositPeriod | ProposalStatus::VotingPeriod => None,
+        _ => proposal
+            .final_tally_result
+            .map(|tally| {
+                Ok::<_, CwEnvError>(TallyResult {
+                    yes: tally.yes_count.parse()?,
+                    no: tally.no_count.parse()?,
+                    abstain: tally.abstain_count.parse()?,
+                    no_with_veto: tally.no_with_veto_count.parse()?,
+                })
+            })
+            .transpose()?,
+    };
+
+    Ok(GovProposal {
+        id: proposal.id,
+        title: proposal.title,
+        summary: proposal.summary,
+        proposer: proposal.proposer,
+        messages: proposal
+            .messages
+            .into_iter()
+            .map(|msg| ProposalMessage {
+                type_url: msg.type_url,
+                value: msg.value.into(),
+            })
+            .collect(),
+        status,
+        total_deposit: try_proto_to_cosmwasm_coins(proposal.total_deposit)?,
+        submit_time: proposal
+            .submit_time
+            .map(|t| to_timestamp(t.seconds, t.nanos))
+            .unwrap_or_default(),
+        deposit_end_time: proposal
+            .deposit_end_time
+            .map(|t| to_timestamp(t.seconds, t.nanos))
+            .unwrap_or_default(),
+        voting_start_time: proposal
+            .voting_start_time
+            .map(|t| to_timestamp(t.seconds, t.nanos)),
+        voting_end_time: proposal
+            .voting_end_time
+            .map(|t| to_timestamp(t.seconds, t.nanos)),
+        final_tally_result,
+    })
+}
+
+fn proto_to_gov_vote(vote: Vote) -> Result<GovVote, CwEnvError> {
+    let options = vote
+        .options
+        .into_iter()
+        .map(|option| {
+            let vote_option = match option.option {
+                1 => VoteOption::Yes,
+                2 => VoteOption::Abstain,
+                3 => VoteOption::No,
+                4 => VoteOption::NoWithVeto,
+                option => return Err(CwEnvError::StdErr(format!("unknown vote option {option}"))),
+            };
+            Ok(GovVoteOption {
+                option: vote_option,
+                // Weights are encoded as decimals with 18 fractional digits
+                weight: Decimal::from_atomics(option.weight.parse::<Uint128>()?, 18)
+                    .map_err(|e| CwEnvError::StdErr(e.to_string()))?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(GovVote {
+        proposal_id: vote.proposal_id,
+        voter: vote.voter,
+        options,
+    })
+}
+
+fn proto_to_tally_result(tally: ProtoTallyResult) -> Result<TallyResult, CwEnvError> {
+    Ok(TallyResult {
+        yes: tally.yes.parse()?,
+        no: tally.no.parse()?,
+        abstain: tally.abstain.parse()?,
+        no_with_veto: tally.no_with_veto.parse()?,
+    })
+}
+
+fn to_timestamp(seconds: i64, nanos: i32) -> Timestamp {
+    Timestamp::from_seconds(seconds as u64).plus_nanos(nanos as u64)
+}
+
+/// Messages of the `cosmos.gov.v1` query service used by the querier.
+/// The v1 proposals expose their messages and proposer, unlike the v1beta1 ones.
+mod v1 {
+    use neutron_test_tube::neutron_std::types::cosmos::base::{
+        query::v1beta1::{PageRequest, PageResponse},
+        v1beta1::Coin,
+    };
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QueryProposalRequest {
+        #[prost(uint64, tag = "1")]
+        pub proposal_id: u64,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QueryProposalResponse {
+        #[prost(message, optional, tag = "1")]
+        pub proposal: Option<Proposal>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QueryProposalsRequest {
+        #[prost(int32, tag = "1")]
+        pub proposal_status: i32,
+        #[prost(string, tag = "2")]
+        pub voter: String,
+        #[prost(string, tag = "3")]
+        pub depositor: String,
+        #[prost(message, optional, tag = "4")]
+        pub pagination: Option<PageRequest>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct QueryProposalsResponse {
+        #[prost(message, repeated, tag = "1")]
+        pub proposals: Vec<Proposal>,
+        #[prost(message, optional, tag = "2")]
+        pub pagination: Option<PageResponse>,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Proposal {
+        #[prost(uint64, tag = "1")]
+        pub id: u64,
+        #[prost(message, repeated, tag = "2")]
+        pub messages: Vec<::prost_types::Any>,
+        #[prost(int32, tag = "3")]
+        pub status: i32,
+        #[prost(message, optional, tag = "4")]
+        pub final_tally_result: Option<TallyResult>,
+        #[prost(message, optional, tag = "5")]
+        pub submit_time: Option<::prost_types::Timestamp>,
+        #[prost(message, optional, tag = "6")]
+        pub deposit_end_time: Option<::prost_types::Timestamp>,
+        #[prost(message, repeated, tag = "7")]
+        pub total_deposit: Vec<Coin>,
+        #[prost(message, optional, tag = "8")]
+        pub voting_start_time: Option<::prost_types::Timestamp>,
+        #[prost(message, optional, tag = "9")]
+        pub voting_end_time: Option<::prost_types::Timestamp>,
+        #[prost(string, tag = "11")]
+        pub title: String,
+        #[prost(string, tag = "12")]
+        pub summary: String,
+        #[prost(string, tag = "13")]
+        pub proposer: String,
+    }
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct TallyResult {
+        #[prost(string, tag = "1")]
+        pub yes_count: String,
+        #[prost(string, tag = "2")]
+        pub abstain_count: String,
+        #[prost(string, tag = "3")]
+        pub no_count: String,
+        #[prost(string, tag = "4")]
+        pub no_with_veto_count: String,
+    }
+}
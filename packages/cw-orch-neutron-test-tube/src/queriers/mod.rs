@@ -2,12 +2,16 @@ use cw_orch_core::{
     environment::{DefaultQueriers, QueryHandler, StateInterface},
     CwEnvError,
 };
+use neutron_test_tube::neutron_std::types::cosmos::base::query::v1beta1::PageRequest;
 
 use super::NeutronTestTube;
 
 pub mod bank;
+pub mod distribution;
 mod env;
+pub mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
 impl<S: StateInterface> QueryHandler for NeutronTestTube<S> {
@@ -31,4 +35,18 @@ impl<S: StateInterface> DefaultQueriers for NeutronTestTube<S> {
     type Bank = bank::NeutronTestTubeBankQuerier;
     type Wasm = wasm::NeutronTestTubeWasmQuerier<S>;
     type Node = node::NeutronTestTubeNodeQuerier;
+    type Staking = staking::NeutronTestTubeStakingQuerier;
+    type Distribution = distribution::NeutronTestTubeDistributionQuerier;
+    type Gov = gov::NeutronTestTubeGovQuerier;
+}
+
+/// Request for the page starting at `key`, the first page when `key` is empty
+pub(crate) fn page_request(key: Vec<u8>) -> PageRequest {
+    PageRequest {
+        key,
+        offset: 0,
+        limit: 0,
+        count_total: false,
+        reverse: false,
+    }
 }
@@ -0,0 +1,188 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Decimal, Delegation, Uint128, Validator};
+use cw_orch_core::environment::{Querier, QuerierGetter, StakingQuerier, StateInterface};
+use cw_orch_core::CwEnvError;
+use neutron_test_tube::neutron_std::{
+    try_proto_to_cosmwasm_coins,
+    types::cosmos::staking::v1beta1::{
+        DelegationResponse, QueryDelegationRequest, QueryDelegationResponse,
+        QueryDelegatorDelegationsRequest, QueryDelegatorDelegationsResponse, QueryParamsRequest,
+        QueryParamsResponse, QueryValidatorRequest, QueryValidatorResponse, QueryValidatorsRequest,
+        QueryValidatorsResponse, Validator as ProtoValidator,
+    },
+};
+use neutron_test_tube::{NeutronTestApp, Runner, RunnerError};
+
+use super::page_request;
+use crate::{map_err, NeutronTestTube};
+
+pub struct NeutronTestTubeStakingQuerier {
+    app: Rc<RefCell<NeutronTestApp>>,
+}
+
+impl NeutronTestTubeStakingQuerier {
+    fn new<S: StateInterface>(mock: &NeutronTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for NeutronTestTubeStakingQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<NeutronTestTubeStakingQuerier> for NeutronTestTube<S> {
+    fn querier(&self) -> NeutronTestTubeStakingQuerier {
+        NeutronTestTubeStakingQuerier::new(self)
+    }
+}
+
+impl StakingQuerier for NeutronTestTubeStakingQuerier {
+    fn bonded_denom(&self) -> Result<String, Self::Error> {
+        let params: QueryParamsResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.staking.v1beta1.Query/Params",
+                &QueryParamsRequest {},
+            )
+            .map_err(map_err)?;
+        params
+            .params
+            .map(|params| params.bond_denom)
+            .ok_or_else(|| CwEnvError::StdErr("staking params not found".to_string()))
+    }
+
+    fn validator(&self, validator: &str) -> Result<Validator, Self::Error> {
+        let response: QueryValidatorResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.staking.v1beta1.Query/Validator",
+                &QueryValidatorRequest {
+                    validator_addr: validator.to_string(),
+                },
+            )
+            .map_err(map_err)?;
+        let validator = response
+            .validator
+            .ok_or_else(|| CwEnvError::StdErr(format!("validator {validator} doesn't exist")))?;
+        proto_to_cosmwasm_validator(validator)
+    }
+
+    fn validators(&self) -> Result<Vec<Validator>, Self::Error> {
+        let mut validators = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryValidatorsResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmos.staking.v1beta1.Query/Validators",
+                    &QueryValidatorsRequest {
+                        status: "BOND_STATUS_BONDED".to_string(),
+                        pagination: Some(page_request(next_key)),
+                    },
+                )
+                .map_err(map_err)?;
+            validators.extend(response.validators);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        validators
+            .into_iter()
+            .map(proto_to_cosmwasm_validator)
+            .collect()
+    }
+
+    fn delegation(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Option<Delegation>, Self::Error> {
+        let response: Result<QueryDelegationResponse, _> = self.app.borrow().query(
+            "/cosmos.staking.v1beta1.Query/Delegation",
+            &QueryDelegationRequest {
+                delegator_addr: delegator.to_string(),
+                validator_addr: validator.to_string(),
+            },
+        );
+        match response {
+            Ok(response) => response
+                .delegation_response
+                .map(proto_to_cosmwasm_delegation)
+                .transpose(),
+            // The staking module returns a not found error when there is no delegation
+            Err(RunnerError::QueryError { msg }) if msg.contains("not found") => Ok(None),
+            Err(e) => Err(map_err(e)),
+        }
+    }
+
+    fn delegator_delegations(&self, delegator: &Addr) -> Result<Vec<Delegation>, Self::Error> {
+        let mut delegations = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryDelegatorDelegationsResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+                    &QueryDelegatorDelegationsRequest {
+                        delegator_addr: delegator.to_string(),
+                        pagination: Some(page_request(next_key)),
+                    },
+                )
+                .map_err(map_err)?;
+            delegations.extend(response.delegation_responses);
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        delegations
+            .into_iter()
+            .map(proto_to_cosmwasm_delegation)
+            .collect()
+    }
+}
+
+fn proto_to_cosmwasm_validator(validator: ProtoValidator) -> Result<Validator, CwEnvError> {
+    let rates = validator
+        .commission
+        .and_then(|commission| commission.commission_rates)
+        .unwrap_or_default();
+    Ok(Validator::new(
+        validator.operator_address,
+        proto_to_decimal(&rates.rate)?,
+        proto_to_decimal(&rates.max_rate)?,
+        proto_to_decimal(&rates.max_change_rate)?,
+    ))
+}
+
+fn proto_to_cosmwasm_delegation(response: DelegationResponse) -> Result<Delegation, CwEnvError> {
+    let delegation = response.delegation.unwrap_or_default();
+    let amount = response
+        .balance
+        .map(|c| Ok::<_, CwEnvError>(try_proto_to_cosmwasm_coins(vec![c])?[0].clone()))
+        .transpose()?
+        .unwrap_or_default();
+    Ok(Delegation::new(
+        Addr::unchecked(delegation.delegator_address),
+        delegation.validator_address,
+        amount,
+    ))
+}
+
+/// Decimals are encoded with 18 fractional digits
+fn proto_to_decimal(value: &str) -> Result<Decimal, CwEnvError> {
+    Decimal::from_atomics(value.parse::<Uint128>()?, 18)
+        .map_err(|e| CwEnvError::StdErr(e.to_string()))
+}
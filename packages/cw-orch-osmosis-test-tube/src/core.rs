@@ -4,14 +4,18 @@ use cosmwasm_std::{coin, Addr, BankMsg, Coins};
 
 use cw_orch_core::contract::interface_traits::Uploadable;
 use cw_orch_core::contract::WasmPath;
-use cw_orch_core::environment::{BankQuerier, BankSetter, ChainInfo, DefaultQueriers, NetworkInfo};
+use cw_orch_core::environment::{
+    BankQuerier, BankSetter, ChainInfo, DefaultQueriers, NetworkInfo, GAS_USED_EVENT,
+};
 
-use cosmwasm_std::{Binary, Coin, Uint128};
+use cosmwasm_std::{Binary, Coin, Event, Uint128};
 use cw_orch_core::CwEnvError;
 use cw_orch_mock::cw_multi_test::AppResponse;
 use cw_orch_traits::Stargate;
 use osmosis_test_tube::cosmrs::proto::cosmos::bank::v1beta1::MsgSendResponse;
-use osmosis_test_tube::{Account, Bank, Gamm, Module, Runner, RunnerError, SigningAccount, Wasm};
+use osmosis_test_tube::{
+    Account, Bank, ExecuteResponse, Gamm, Module, Runner, RunnerError, SigningAccount, Wasm,
+};
 
 // This should be the way to import stuff.
 // But apparently osmosis-test-tube doesn't have the same dependencies as the test-tube package
@@ -26,7 +30,11 @@ use serde::Serialize;
 
 use cw_orch_core::{
     environment::TxHandler,
-    environment::{ChainState, StateInterface},
+    environment::{ChainState, StateInterface, StorageDumper},
+};
+use osmosis_test_tube::osmosis_std::types::{
+    cosmos::base::query::v1beta1::PageRequest,
+    cosmwasm::wasm::v1::{QueryAllContractStateRequest, QueryAllContractStateResponse},
 };
 
 use cw_orch_mock::MockState;
@@ -85,6 +93,19 @@ pub(crate) fn map_err(e: RunnerError) -> CwEnvError {
     CwEnvError::StdErr(e.to_string())
 }
 
+/// Converts a test-tube response, adding the gas used by the transaction to its events
+pub(crate) fn to_app_response<R>(response: ExecuteResponse<R>) -> AppResponse {
+    let mut events = response.events;
+    events.push(
+        Event::new(GAS_USED_EVENT.0)
+            .add_attribute(GAS_USED_EVENT.1, response.gas_info.gas_used.to_string()),
+    );
+    AppResponse {
+        data: Some(Binary::new(response.raw_data)),
+        events,
+    }
+}
+
 impl<S: StateInterface> OsmosisTestTube<S> {
     /// Creates an account and sets its balance
     pub fn init_account(
@@ -134,10 +155,7 @@ impl<S: StateInterface> OsmosisTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(send_response.raw_data)),
-            events: send_response.events,
-        })
+        Ok(to_app_response(send_response))
     }
 
     /// Creates an osmosis pool (helper)
@@ -235,10 +253,7 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             .store_code(&wasm_contents, None, &self.sender)
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(upload_response.raw_data)),
-            events: upload_response.events,
-        })
+        Ok(to_app_response(upload_response))
     }
 
     fn execute<E: Serialize + Debug>(
@@ -251,10 +266,7 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             .execute(contract_address.as_ref(), exec_msg, coins, &self.sender)
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(execute_response.raw_data)),
-            events: execute_response.events,
-        })
+        Ok(to_app_response(execute_response))
     }
 
     fn instantiate<I: Serialize + Debug>(
@@ -276,10 +288,7 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(instantiate_response.raw_data)),
-            events: instantiate_response.events,
-        })
+        Ok(to_app_response(instantiate_response))
     }
 
     fn migrate<M: Serialize + Debug>(
@@ -305,10 +314,7 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(migrate_response.raw_data)),
-            events: migrate_response.events,
-        })
+        Ok(to_app_response(migrate_response))
     }
 
     fn instantiate2<I: Serialize + Debug>(
@@ -341,10 +347,7 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(instantiate_response.raw_data)),
-            events: instantiate_response.events,
-        })
+        Ok(to_app_response(instantiate_response))
     }
 
     fn bank_send(
@@ -363,10 +366,46 @@ impl<S: StateInterface> TxHandler for OsmosisTestTube<S> {
             )
             .map_err(map_err)?;
 
-        Ok(AppResponse {
-            data: Some(Binary::new(send_response.raw_data)),
-            events: send_response.events,
-        })
+        Ok(to_app_response(send_response))
+    }
+}
+
+impl<S: StateInterface> StorageDumper for OsmosisTestTube<S> {
+    fn dump_contract_storage(&self, address: &Addr) -> Result<Vec<(Vec<u8>, Vec<u8>)>, CwEnvError> {
+        let mut storage = vec![];
+        let mut next_key = vec![];
+        loop {
+            let response: QueryAllContractStateResponse = self
+                .app
+                .borrow()
+                .query(
+                    "/cosmwasm.wasm.v1.Query/AllContractState",
+                    &QueryAllContractStateRequest {
+                        address: address.to_string(),
+                        pagination: Some(PageRequest {
+                            key: next_key,
+                            offset: 0,
+                            limit: 0,
+                            count_total: false,
+                            reverse: false,
+                        }),
+                    },
+                )
+                .map_err(map_err)?;
+            storage.extend(
+                response
+                    .models
+                    .into_iter()
+                    .map(|model| (model.key, model.value)),
+            );
+            match response.pagination {
+                Some(pagination) if !pagination.next_key.is_empty() => {
+                    next_key = pagination.next_key
+                }
+                _ => break,
+            }
+        }
+        Ok(storage)
     }
 }
 
@@ -452,6 +491,12 @@ pub mod tests {
     use super::OsmosisTestTube;
     use counter_contract::{msg::InstantiateMsg, CounterContract};
     use cw_orch::prelude::*;
+    use cw_orch_core::environment::{GovQuerier, StakingQuerier};
+    use cw_orch_traits::Stargate;
+    use osmosis_test_tube::osmosis_std::types::cosmos::{
+        base::v1beta1::Coin as ProtoCoin, staking::v1beta1::MsgDelegate,
+    };
+    use prost::Message;
 
     #[test]
     fn wasm_querier_works() -> cw_orch::anyhow::Result<()> {
@@ -529,4 +574,47 @@ pub mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn staking_querier_works() -> cw_orch::anyhow::Result<()> {
+        let app = OsmosisTestTube::new(coins(100_000_000_000_000, GAS_TOKEN));
+        let sender = app.sender_addr();
+        let querier = app.staking_querier();
+
+        assert_eq!(querier.bonded_denom()?, GAS_TOKEN);
+        let validator = querier.validators()?[0].address.clone();
+        assert_eq!(querier.validator(&validator)?.address, validator);
+        assert_eq!(querier.delegation(&sender, &validator)?, None);
+
+        app.commit_any(
+            vec![prost_types::Any {
+                type_url: MsgDelegate::TYPE_URL.to_string(),
+                value: MsgDelegate {
+                    delegator_address: sender.to_string(),
+                    validator_address: validator.clone(),
+                    amount: Some(ProtoCoin {
+                        denom: GAS_TOKEN.to_string(),
+                        amount: "1000".to_string(),
+                    }),
+                }
+                .encode_to_vec(),
+            }],
+            None,
+        )?;
+
+        let delegation = querier.delegation(&sender, &validator)?.unwrap();
+        assert_eq!(delegation.amount, coin(1000, GAS_TOKEN));
+        assert_eq!(querier.delegator_delegations(&sender)?, vec![delegation]);
+        Ok(())
+    }
+
+    #[test]
+    fn gov_querier_works() -> cw_orch::anyhow::Result<()> {
+        let app = OsmosisTestTube::new(coins(100_000_000_000_000, GAS_TOKEN));
+        let querier = app.gov_querier();
+
+        assert!(querier.proposals(None)?.is_empty());
+        assert!(querier.proposal(1).is_err());
+        Ok(())
+    }
 }
@@ -0,0 +1,89 @@
+use std::{cell::RefCell, rc::Rc};
+
+use cosmwasm_std::{Addr, Coin, Uint128, Uint256};
+use cw_orch_core::environment::{DistributionQuerier, Querier, QuerierGetter, StateInterface};
+use cw_orch_core::CwEnvError;
+use osmosis_test_tube::osmosis_std::types::cosmos::{
+    base::v1beta1::DecCoin,
+    distribution::v1beta1::{
+        QueryDelegationRewardsRequest, QueryDelegationRewardsResponse,
+        QueryDelegationTotalRewardsRequest, QueryDelegationTotalRewardsResponse,
+    },
+};
+use osmosis_test_tube::{OsmosisTestApp, Runner};
+
+use crate::{map_err, OsmosisTestTube};
+
+pub struct OsmosisTestTubeDistributionQuerier {
+    app: Rc<RefCell<OsmosisTestApp>>,
+}
+
+impl OsmosisTestTubeDistributionQuerier {
+    fn new<S: StateInterface>(mock: &OsmosisTestTube<S>) -> Self {
+        Self {
+            app: mock.app.clone(),
+        }
+    }
+}
+
+impl Querier for OsmosisTestTubeDistributionQuerier {
+    type Error = CwEnvError;
+}
+
+impl<S: StateInterface> QuerierGetter<OsmosisTestTubeDistributionQuerier> for OsmosisTestTube<S> {
+    fn querier(&self) -> OsmosisTestTubeDistributionQuerier {
+        OsmosisTestTubeDistributionQuerier::new(self)
+    }
+}
+
+impl DistributionQuerier for OsmosisTestTubeDistributionQuerier {
+    fn delegation_rewards(
+        &self,
+        delegator: &Addr,
+        validator: &str,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        let response: QueryDelegationRewardsResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.distribution.v1beta1.Query/DelegationRewards",
+                &QueryDelegationRewardsRequest {
+                    delegator_address: delegator.to_string(),
+                    validator_address: validator.to_string(),
+                },
+            )
+            .map_err(map_err)?;
+        proto_to_truncated_coins(response.rewards)
+    }
+
+    fn delegation_total_rewards(&self, delegator: &Addr) -> Result<Vec<Coin>, Self::Error> {
+        let response: QueryDelegationTotalRewardsResponse = self
+            .app
+            .borrow()
+            .query(
+                "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+                &QueryDelegationTotalRewardsRequest {
+                    delegator_address: delegator.to_string(),
+                },
+            )
+            .map_err(map_err)?;
+        proto_to_truncated_coins(response.total)
+    }
+}
+
+/// Truncates decimal coins, encoded with 18 fractional digits, to whole tokens
+fn proto_to_truncated_coins(coins: Vec<DecCoin>) -> Result<Vec<Coin>, CwEnvError> {
+    let precision = Uint256::from(10u128.pow(18));
+    let mut truncated = vec![];
+    for coin in coins {
+        let amount = Uint128::try_from(coin.amount.parse::<Uint256>()? / precision)
+            .map_err(|e| CwEnvError::StdErr(e.to_string()))?;
+        if !amount.is_zero() {
+            truncated.push(Coin {
+                denom: coin.denom,
+                amount,
+            });
+        }
+    }
+    Ok(truncated)
+}
@@ -2,12 +2,16 @@ use cw_orch_core::{
     environment::{DefaultQueriers, QueryHandler, StateInterface},
     CwEnvError,
 };
+use osmosis_test_tube::osmosis_std::types::cosmos::base::query::v1beta1::PageRequest;
 
 use super::OsmosisTestTube;
 
 pub mod bank;
+pub mod distribution;
 mod env;
+pub mod gov;
 pub mod node;
+pub mod staking;
 pub mod wasm;
 
 impl<S: StateInterface> QueryHandler for OsmosisTestTube<S> {
@@ -31,4 +35,18 @@ impl<S: StateInterface> DefaultQueriers for OsmosisTestTube<S> {
     type Bank = bank::OsmosisTestTubeBankQuerier;
     type Wasm = wasm::OsmosisTestTubeWasmQuerier<S>;
     type Node = node::OsmosisTestTubeNodeQuerier;
+    type Staking = staking::OsmosisTestTubeStakingQuerier;
+    type Distribution = distribution::OsmosisTestTubeDistributionQuerier;
+    type Gov = gov::OsmosisTestTubeGovQuerier;
+}
+
+/// Request for the page starting at `key`, the first page when `key` is empty
+pub(crate) fn page_request(key: Vec<u8>) -> PageRequest {
+    PageRequest {
+        key,
+        offset: 0,
+        limit: 0,
+        count_total: false,
+        reverse: false,
+    }
 }
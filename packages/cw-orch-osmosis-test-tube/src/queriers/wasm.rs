@@ -184,4 +184,11 @@ impl<S: StateInterface> WasmQuerier for OsmosisTestTubeWasmQuerier<S> {
     ) -> Result<Checksum, CwEnvError> {
         <T as Uploadable>::wasm(&MOCK_CHAIN_INFO.into()).checksum()
     }
+
+    fn is_not_found(&self, error: &CwEnvError) -> bool {
+        matches!(
+            error,
+            CwEnvError::CodeIdNotInStore(_) | CwEnvError::AddrNotInStore(_)
+        )
+    }
 }
@@ -51,6 +51,15 @@ impl<R> ChannelCreation<R> {
     }
 }
 
+/// This struct contains information about an IBC channel closing handshake, with the same struct type for each step.
+/// [More info about channel closing here](https://github.com/cosmos/ibc/blob/main/spec/core/ics-004-channel-and-packet-semantics/README.md#closing-handshake)
+pub struct ChannelClose<R> {
+    /// First step, channel close-initialization (src_chain)
+    pub init: R,
+    /// Second step, channel close-confirmation (dst_chain)
+    pub confirm: R,
+}
+
 /// Alias to indicate functions await a chain id
 pub type ChainId<'a> = &'a str;
 
@@ -452,6 +461,37 @@ pub trait InterchainEnv<Chain: IbcQueryHandler>: Clone {
         dst_chain: ChainId,
         sequence: Sequence,
     ) -> Result<SinglePacketFlow<Chain>, Self::Error>;
+
+    /// Closes a channel with the closing handshake: close-init on `src_chain` and close-confirm on the counterparty chain.
+    /// The contracts bound to the ports of the channel receive the corresponding `ibc_channel_close` calls.
+    /// Environments that can't close channels return [`InterchainError::ChannelCloseNotSupported`].
+    /// ``` rust
+    /// use cw_orch_interchain::prelude::*;
+    /// let interchain = MockBech32InterchainEnv::new(vec![("osmosis-1","osmo"),("archway-1","arch")]);
+    /// let channel = interchain.create_channel(
+    ///     "osmosis-1",
+    ///     "archway-1",
+    ///     &PortId::transfer(),
+    ///     &PortId::transfer(),
+    ///     "ics20-1",
+    ///     Some(cosmwasm_std::IbcOrder::Ordered),
+    /// ).unwrap();
+    ///
+    /// interchain.close_channel("osmosis-1", &channel.interchain_channel).unwrap();
+    /// ```
+    fn close_channel(
+        &self,
+        _src_chain: ChainId,
+        _ibc_channel: &InterchainChannel<<Chain as IbcQueryHandler>::Handler>,
+    ) -> Result<ChannelClose<<Chain as TxHandler>::Response>, Self::Error>
+    where
+        Self::Error: From<InterchainError>,
+    {
+        Err(
+            InterchainError::ChannelCloseNotSupported(std::any::type_name::<Self>().to_string())
+                .into(),
+        )
+    }
 }
 
 /// format the port for a contract
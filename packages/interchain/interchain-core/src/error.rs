@@ -61,4 +61,7 @@ pub enum InterchainError {
 
     #[error("Callback Failure during acknowledgment : {0:?}")]
     CallbackError(String),
+
+    #[error("Closing channels is not supported by {0}")]
+    ChannelCloseNotSupported(String),
 }
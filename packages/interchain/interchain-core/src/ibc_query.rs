@@ -42,3 +42,13 @@ impl<A: Api> IbcQueryHandler for MockBase<A, MockState> {
         self.block_info().unwrap().chain_id
     }
 }
+
+#[cfg(feature = "clone-testing")]
+impl IbcQueryHandler for cw_orch_clone_testing::CloneTesting {
+    type Handler = ();
+    fn ibc_handler(&self) {}
+
+    fn chain_id(&self) -> NetworkId {
+        self.block_info().unwrap().chain_id
+    }
+}
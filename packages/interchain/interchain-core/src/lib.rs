@@ -17,6 +17,7 @@ pub(crate) mod ibc_query;
 pub(crate) mod packet;
 /// Type definition for interchain structure and return types
 pub mod results;
+pub(crate) mod trace;
 pub(crate) mod tx;
 
 pub use ack_parser::IbcAckParser;
@@ -28,4 +29,5 @@ pub use packet::{
     success::{IbcAppResult, IbcPacketResult, SuccessNestedPacketsFlow, SuccessSinglePacketFlow},
     IbcPacketInfo, IbcPacketOutcome, NestedPacketsFlow, SinglePacketFlow,
 };
+pub use trace::{AckStatus, PacketTrace, StepKind, TimelineStep, TracedOutcome, TracedPacket};
 pub use tx::TxId;
@@ -0,0 +1,571 @@
+//! Visual export of nested packet flows, to diagnose interchain tests.
+//!
+//! A [`PacketTrace`] is built from a [`NestedPacketsFlow`] or a [`SuccessNestedPacketsFlow`] and rendered as a
+//! Mermaid sequence diagram, a Graphviz DOT graph or a JSON timeline:
+//! ```rust,ignore
+//! let flow = interchain.await_packets("juno-1", tx_response)?;
+//! std::fs::write("packets.mmd", flow.trace().to_mermaid())?;
+//! ```
+use std::fmt::Write;
+
+use cosmwasm_std::Binary;
+use cw_orch_core::environment::{CwEnv, IndexResponse};
+use serde::Serialize;
+
+use crate::{
+    packet::success::{IbcAppResult, SuccessNestedPacketsFlow},
+    IbcAckParser, IbcPacketOutcome, InterchainError, NestedPacketsFlow, TxId,
+};
+
+/// Transaction of a packet flow, with the packets it sent
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PacketTrace {
+    /// Chain on which the transaction was broadcasted
+    pub chain_id: String,
+    /// Hash of the transaction, if the environment broadcasts actual transactions
+    pub tx_hash: Option<String>,
+    /// Packets sent during the transaction
+    pub packets: Vec<TracedPacket>,
+}
+
+/// Packet sent during a transaction of a packet flow
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TracedPacket {
+    /// Port on which the packet was sent
+    pub src_port: Option<String>,
+    /// Channel on which the packet was sent
+    pub src_channel: Option<String>,
+    /// Port to which the packet was sent
+    pub dst_port: Option<String>,
+    /// Channel to which the packet was sent
+    pub dst_channel: Option<String>,
+    /// Sequence of the packet on its channel
+    pub sequence: Option<u64>,
+    /// Transactions that followed the packet
+    pub outcome: TracedOutcome,
+}
+
+/// Outcome of a traced packet
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TracedOutcome {
+    /// The packet timed out
+    Timeout {
+        /// Timeout transaction, on the source chain
+        timeout_tx: PacketTrace,
+    },
+    /// The packet was received and acknowledged
+    Acknowledgement {
+        /// Receive transaction, on the destination chain
+        receive_tx: PacketTrace,
+        /// Acknowledgement transaction, on the source chain
+        ack_tx: PacketTrace,
+        /// Outcome of the acknowledgement
+        ack: AckStatus,
+    },
+}
+
+/// Outcome of a packet acknowledgement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AckStatus {
+    /// Success acknowledgement, with the IBC application that produced it or the raw acknowledgement
+    Success(String),
+    /// Error acknowledgement, with the error
+    Error(String),
+}
+
+/// Kind of step of a packet timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepKind {
+    /// The packet is sent on the source chain
+    Send,
+    /// The packet is received on the destination chain
+    Receive,
+    /// The acknowledgement is received on the source chain
+    Acknowledge,
+    /// The timeout is received on the source chain
+    Timeout,
+}
+
+/// Step of a packet timeline, see [`PacketTrace::timeline`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineStep {
+    /// Kind of step
+    pub kind: StepKind,
+    /// Chain on which the step happened
+    pub chain_id: String,
+    /// Hash of the transaction of the step
+    pub tx_hash: Option<String>,
+    /// Port of the packet on the chain of the step
+    pub port: Option<String>,
+    /// Channel of the packet on the chain of the step
+    pub channel: Option<String>,
+    /// Sequence of the packet
+    pub sequence: Option<u64>,
+    /// Nesting level of the packet, 0 for the packets sent by the traced transaction
+    pub depth: usize,
+    /// Outcome of the acknowledgement, for [`StepKind::Acknowledge`] steps
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ack: Option<AckStatus>,
+}
+
+impl PacketTrace {
+    fn new<Chain: CwEnv, CustomResult>(
+        tx_id: &TxId<Chain, CustomResult>,
+        outcomes: Vec<TracedOutcome>,
+    ) -> Self {
+        let response = &tx_id.response;
+        let attribute = |key: &str| response.event_attr_values("send_packet", key);
+        let (src_ports, src_channels, dst_ports, dst_channels, sequences) = (
+            attribute("packet_src_port"),
+            attribute("packet_src_channel"),
+            attribute("packet_dst_port"),
+            attribute("packet_dst_channel"),
+            attribute("packet_sequence"),
+        );
+
+        let packets = outcomes
+            .into_iter()
+            .enumerate()
+            .map(|(i, outcome)| TracedPacket {
+                src_port: src_ports.get(i).cloned(),
+                src_channel: src_channels.get(i).cloned(),
+                dst_port: dst_ports.get(i).cloned(),
+                dst_channel: dst_channels.get(i).cloned(),
+                sequence: sequences.get(i).and_then(|s| s.parse().ok()),
+                outcome,
+            })
+            .collect();
+
+        Self {
+            chain_id: tx_id.chain_id.clone(),
+            tx_hash: response.tx_hash(),
+            packets,
+        }
+    }
+
+    /// Steps of the packet flow in the order they happened: each packet is sent, received,
+    /// followed by the packets sent when receiving it, then acknowledged and followed by the packets sent when acknowledging it.
+    pub fn timeline(&self) -> Vec<TimelineStep> {
+        let mut steps = vec![];
+        self.push_steps(0, &mut steps);
+        steps
+    }
+
+    fn push_steps(&self, depth: usize, steps: &mut Vec<TimelineStep>) {
+        for packet in &self.packets {
+            let step = |kind, tx: &PacketTrace, port: &Option<String>, channel: &Option<String>| {
+                TimelineStep {
+                    kind,
+                    chain_id: tx.chain_id.clone(),
+                    tx_hash: tx.tx_hash.clone(),
+                    port: port.clone(),
+                    channel: channel.clone(),
+                    sequence: packet.sequence,
+                    depth,
+                    ack: None,
+                }
+            };
+            steps.push(step(
+                StepKind::Send,
+                self,
+                &packet.src_port,
+                &packet.src_channel,
+            ));
+            match &packet.outcome {
+                TracedOutcome::Timeout { timeout_tx } => {
+                    steps.push(step(
+                        StepKind::Timeout,
+                        timeout_tx,
+                        &packet.src_port,
+                        &packet.src_channel,
+                    ));
+                    timeout_tx.push_steps(depth + 1, steps);
+                }
+                TracedOutcome::Acknowledgement {
+                    receive_tx,
+                    ack_tx,
+                    ack,
+                } => {
+                    steps.push(step(
+                        StepKind::Receive,
+                        receive_tx,
+                        &packet.dst_port,
+                        &packet.dst_channel,
+                    ));
+                    receive_tx.push_steps(depth + 1, steps);
+                    steps.push(TimelineStep {
+                        ack: Some(ack.clone()),
+                        ..step(
+                            StepKind::Acknowledge,
+                            ack_tx,
+                            &packet.src_port,
+                            &packet.src_channel,
+                        )
+                    });
+                    ack_tx.push_steps(depth + 1, steps);
+                }
+            }
+        }
+    }
+
+    /// Renders the [`PacketTrace::timeline`] as a JSON array
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.timeline())
+            .expect("the timeline only contains strings and numbers")
+    }
+
+    /// Renders the packet flow as a Mermaid sequence diagram, with one participant per chain
+    pub fn to_mermaid(&self) -> String {
+        let mut chains = vec![];
+        let mut lines = vec![];
+        self.push_mermaid_lines(&mut chains, &mut lines);
+
+        let mut diagram = String::from("sequenceDiagram\n");
+        for (i, chain) in chains.iter().enumerate() {
+            writeln!(diagram, "    participant c{i} as {chain}").unwrap();
+        }
+        for line in lines {
+            writeln!(diagram, "    {line}").unwrap();
+        }
+        diagram
+    }
+
+    fn push_mermaid_lines(&self, chains: &mut Vec<String>, lines: &mut Vec<String>) {
+        let src = participant(chains, &self.chain_id);
+        for packet in &self.packets {
+            let label = mermaid_escape(&packet.label());
+            match &packet.outcome {
+                TracedOutcome::Timeout { timeout_tx } => {
+                    lines.push(format!("Note over {src}: {label} timed out"));
+                    timeout_tx.push_mermaid_lines(chains, lines);
+                }
+                TracedOutcome::Acknowledgement {
+                    receive_tx,
+                    ack_tx,
+                    ack,
+                } => {
+                    let dst = participant(chains, &receive_tx.chain_id);
+                    lines.push(format!("{src}->>{dst}: {label}"));
+                    receive_tx.push_mermaid_lines(chains, lines);
+                    let ack_label = mermaid_escape(&ack.label());
+                    match ack {
+                        AckStatus::Success(_) => lines.push(format!("{dst}-->>{src}: {ack_label}")),
+                        AckStatus::Error(_) => lines.push(format!("{dst}--x{src}: {ack_label}")),
+                    }
+                    ack_tx.push_mermaid_lines(chains, lines);
+                }
+            }
+        }
+    }
+
+    /// Renders the packet flow as a Graphviz DOT graph.
+    /// Transactions are the nodes, grouped by chain, and packets and acknowledgements are the edges.
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+        self.push_dot_node(&mut graph);
+
+        let mut dot = String::from("digraph packets {\n    rankdir=LR;\n    node [shape=box];\n");
+        for (i, (chain, nodes)) in graph.clusters.iter().enumerate() {
+            writeln!(dot, "    subgraph cluster_{i} {{").unwrap();
+            writeln!(dot, "        label=\"{}\";", dot_escape(chain)).unwrap();
+            for (id, label) in nodes {
+                writeln!(dot, "        tx{id} [label=\"{}\"];", dot_escape(label)).unwrap();
+            }
+            writeln!(dot, "    }}").unwrap();
+        }
+        for edge in graph.edges {
+            writeln!(dot, "    {edge};").unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Adds the transaction and its packets to the graph, returns the id of the transaction node
+    fn push_dot_node(&self, graph: &mut DotGraph) -> usize {
+        let id = graph.node(&self.chain_id, self.tx_hash.clone());
+        for packet in &self.packets {
+            let label = dot_escape(&packet.label());
+            match &packet.outcome {
+                TracedOutcome::Timeout { timeout_tx } => {
+                    let timeout_id = timeout_tx.push_dot_node(graph);
+                    graph.edges.push(format!(
+                        "tx{id} -> tx{timeout_id} [label=\"{label} timeout\", color=red]"
+                    ));
+                }
+                TracedOutcome::Acknowledgement {
+                    receive_tx,
+                    ack_tx,
+                    ack,
+                } => {
+                    let receive_id = receive_tx.push_dot_node(graph);
+                    graph
+                        .edges
+                        .push(format!("tx{id} -> tx{receive_id} [label=\"{label}\"]"));
+                    let ack_id = ack_tx.push_dot_node(graph);
+                    let color = match ack {
+                        AckStatus::Success(_) => "black",
+                        AckStatus::Error(_) => "red",
+                    };
+                    graph.edges.push(format!(
+                        "tx{receive_id} -> tx{ack_id} [label=\"{}\", style=dashed, color={color}]",
+                        dot_escape(&ack.label())
+                    ));
+                }
+            }
+        }
+        id
+    }
+}
+
+impl TracedPacket {
+    /// Short identification of the packet, e.g. `transfer/channel-0 seq 1`
+    fn label(&self) -> String {
+        let unknown = || "?".to_string();
+        let sequence = self.sequence.map(|s| s.to_string()).unwrap_or_else(unknown);
+        format!(
+            "{}/{} seq {}",
+            self.src_port.clone().unwrap_or_else(unknown),
+            self.src_channel.clone().unwrap_or_else(unknown),
+            sequence
+        )
+    }
+}
+
+impl AckStatus {
+    /// Returns the status of a raw acknowledgement
+    fn from_ack(ack: &Binary) -> Self {
+        for result in [
+            IbcAckParser::ics004_json_ack(ack),
+            IbcAckParser::ics004_ack(ack),
+        ] {
+            if let Err(InterchainError::FailedAckReceived(error)) = result {
+                return AckStatus::Error(error);
+            }
+        }
+        match IbcAckParser::any_standard_app_result(ack) {
+            Ok(result) => AckStatus::Success(app_name(&result).to_string()),
+            Err(_) => AckStatus::Success(String::from_utf8_lossy(ack.as_slice()).to_string()),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            AckStatus::Success(result) => format!("ack {result}"),
+            AckStatus::Error(error) => format!("error ack: {error}"),
+        }
+    }
+}
+
+fn app_name<CustomResult>(result: &IbcAppResult<CustomResult>) -> &'static str {
+    match result {
+        IbcAppResult::Polytone(_) => "polytone",
+        IbcAppResult::Ics20 => "ics20",
+        IbcAppResult::Ics004(_) => "ics004",
+        IbcAppResult::IbcHooks(_) => "ibc-hooks",
+        IbcAppResult::Custom(_) => "custom",
+    }
+}
+
+/// Returns the mermaid participant of a chain, registering it if needed
+fn participant(chains: &mut Vec<String>, chain_id: &str) -> String {
+    let index = chains
+        .iter()
+        .position(|c| c == chain_id)
+        .unwrap_or_else(|| {
+            chains.push(chain_id.to_string());
+            chains.len() - 1
+        });
+    format!("c{index}")
+}
+
+/// `#` and `;` are special characters in mermaid messages
+fn mermaid_escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '#' => "#35;".to_string(),
+            ';' => "#59;".to_string(),
+            '\n' => " ".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', " ")
+}
+
+/// Nodes of a DOT graph grouped by chain, and its edges
+#[derive(Default)]
+struct DotGraph {
+    clusters: Vec<(String, Vec<(usize, String)>)>,
+    node_count: usize,
+    edges: Vec<String>,
+}
+
+impl DotGraph {
+    fn node(&mut self, chain_id: &str, tx_hash: Option<String>) -> usize {
+        let id = self.node_count;
+        self.node_count += 1;
+        let label = tx_hash.unwrap_or_else(|| format!("tx {id}"));
+        match self
+            .clusters
+            .iter_mut()
+            .find(|(chain, _)| chain == chain_id)
+        {
+            Some((_, nodes)) => nodes.push((id, label)),
+            None => self
+                .clusters
+                .push((chain_id.to_string(), vec![(id, label)])),
+        }
+        id
+    }
+}
+
+impl<Chain: CwEnv> From<&NestedPacketsFlow<Chain>> for PacketTrace {
+    fn from(flow: &NestedPacketsFlow<Chain>) -> Self {
+        let outcomes = flow
+            .packets
+            .iter()
+            .map(|packet| match packet {
+                IbcPacketOutcome::Timeout { timeout_tx } => TracedOutcome::Timeout {
+                    timeout_tx: timeout_tx.into(),
+                },
+                IbcPacketOutcome::Success {
+                    receive_tx,
+                    ack_tx,
+                    ack,
+                } => TracedOutcome::Acknowledgement {
+                    receive_tx: receive_tx.into(),
+                    ack_tx: ack_tx.into(),
+                    ack: AckStatus::from_ack(ack),
+                },
+            })
+            .collect();
+        PacketTrace::new(&flow.tx_id, outcomes)
+    }
+}
+
+impl<Chain: CwEnv, CustomResult> From<&SuccessNestedPacketsFlow<Chain, CustomResult>>
+    for PacketTrace
+{
+    fn from(flow: &SuccessNestedPacketsFlow<Chain, CustomResult>) -> Self {
+        let outcomes = flow
+            .packets
+            .iter()
+            .map(|packet| TracedOutcome::Acknowledgement {
+                receive_tx: (&packet.receive_tx).into(),
+                ack_tx: (&packet.ack_tx).into(),
+                ack: AckStatus::Success(app_name(&packet.ibc_app_result).to_string()),
+            })
+            .collect();
+        PacketTrace::new(&flow.tx_id, outcomes)
+    }
+}
+
+impl<Chain: CwEnv> NestedPacketsFlow<Chain> {
+    /// Returns the trace of the packet flow, to render it as a diagram or a timeline
+    pub fn trace(&self) -> PacketTrace {
+        self.into()
+    }
+}
+
+impl<Chain: CwEnv, CustomResult> SuccessNestedPacketsFlow<Chain, CustomResult> {
+    /// Returns the trace of the packet flow, to render it as a diagram or a timeline
+    pub fn trace(&self) -> PacketTrace {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{Event, StdAck};
+    use cw_orch_mock::{cw_multi_test::AppResponse, Mock};
+
+    use super::*;
+
+    fn tx(chain_id: &str, events: Vec<Event>) -> TxId<Mock> {
+        let mut response = AppResponse::default();
+        response.events = events;
+        TxId::new(chain_id.to_string(), response)
+    }
+
+    fn flow(
+        tx_id: TxId<Mock>,
+        packets: Vec<IbcPacketOutcome<NestedPacketsFlow<Mock>>>,
+    ) -> NestedPacketsFlow<Mock> {
+        NestedPacketsFlow { tx_id, packets }
+    }
+
+    #[test]
+    fn exports_packet_flow() -> cw_orch::anyhow::Result<()> {
+        let send_packet = Event::new("send_packet").add_attributes([
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "transfer"),
+            ("packet_dst_channel", "channel-1"),
+            ("packet_sequence", "1"),
+        ]);
+        let packet_flow = flow(
+            tx("juno-1", vec![send_packet]),
+            vec![IbcPacketOutcome::Success {
+                receive_tx: flow(tx("osmosis-1", vec![]), vec![]),
+                ack_tx: flow(tx("juno-1", vec![]), vec![]),
+                ack: StdAck::error("insufficient funds").to_binary(),
+            }],
+        );
+        let trace = packet_flow.trace();
+
+        let kinds: Vec<_> = trace.timeline().iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![StepKind::Send, StepKind::Receive, StepKind::Acknowledge]
+        );
+        assert_eq!(
+            trace.timeline()[2].ack,
+            Some(AckStatus::Error("insufficient funds".to_string()))
+        );
+
+        let mermaid = trace.to_mermaid();
+        assert!(mermaid.contains("participant c1 as osmosis-1"));
+        assert!(mermaid.contains("c0->>c1: transfer/channel-0 seq 1"));
+        assert!(mermaid.contains("c1--xc0: error ack: insufficient funds"));
+
+        let dot = trace.to_dot();
+        assert!(dot.contains("tx0 -> tx1 [label=\"transfer/channel-0 seq 1\"]"));
+        assert!(dot.contains("label=\"osmosis-1\""));
+
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json())?;
+        assert_eq!(json[1]["kind"], "receive");
+        assert_eq!(json[1]["channel"], "channel-1");
+        assert_eq!(json[2]["ack"]["error"], "insufficient funds");
+
+        Ok(())
+    }
+
+    #[test]
+    fn escapes_mermaid_labels() {
+        assert_eq!(mermaid_escape("#1; done"), "#35;1#59; done");
+        assert_eq!(mermaid_escape("multi\nline"), "multi line");
+
+        let send_packet = Event::new("send_packet").add_attributes([
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_sequence", "1"),
+        ]);
+        let packet_flow = flow(
+            tx("juno-1", vec![send_packet]),
+            vec![IbcPacketOutcome::Success {
+                receive_tx: flow(tx("osmosis-1", vec![]), vec![]),
+                ack_tx: flow(tx("juno-1", vec![]), vec![]),
+                ack: StdAck::error("invalid memo #1").to_binary(),
+            }],
+        );
+        let mermaid = packet_flow.trace().to_mermaid();
+        assert!(mermaid.contains("c1--xc0: error ack: invalid memo #35;1"));
+    }
+}
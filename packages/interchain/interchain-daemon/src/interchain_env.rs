@@ -286,6 +286,7 @@ impl<C: ChannelCreator> InterchainEnv<Daemon> for DaemonInterchain<C> {
 
         Ok(ibc_trail)
     }
+
     fn chains<'a>(&'a self) -> impl Iterator<Item = &'a Daemon>
     where
         Daemon: 'a,
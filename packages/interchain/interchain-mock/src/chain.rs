@@ -0,0 +1,402 @@
+//! Chains running an in-process IBC module, between which the [`MockInterchainEnvBase`](crate::interchain::MockInterchainEnvBase) relays packets.
+//!
+//! The Mock and CloneTesting environments are backed by different forks of cw-multi-test, with the same IBC module.
+//! [`IbcChain`] exposes the operations of the module needed by the relayer, so that the interchain environment is shared.
+use cosmwasm_std::{
+    Addr, Binary, BlockInfo, CanonicalAddr, Coin, CosmosMsg, IbcOrder, IbcTimeout, StdResult,
+};
+use cw_orch_core::environment::TxHandler;
+use cw_orch_interchain_core::{env::ChannelCreation, IbcQueryHandler};
+use ibc_relayer_types::core::{
+    ics04_channel::packet::Sequence,
+    ics24_host::identifier::{ChannelId, PortId},
+};
+
+use crate::InterchainMockError;
+
+/// Channel opened between two chains, with the transactions of its opening handshake
+pub struct OpenedChannel<R> {
+    /// Id of the channel on the chain that initiated the handshake
+    pub src_channel: String,
+    /// Id of the channel on the counterparty chain
+    pub dst_channel: String,
+    /// Transactions of the handshake
+    pub txs: ChannelCreation<R>,
+}
+
+/// Outcome of relaying a packet to its destination chain
+pub enum RelayedPacket<R> {
+    /// The packet timed out and the timeout was relayed back to the source chain
+    Timeout {
+        /// Transaction of the timeout on the source chain
+        timeout_tx: R,
+        /// Whether the channel was closed by the timeout, as happens on ordered channels
+        channel_closed: bool,
+    },
+    /// The packet was received and its acknowledgement was relayed back to the source chain
+    Acknowledgement {
+        /// Transaction of the reception on the destination chain
+        receive_tx: R,
+        /// Transaction of the acknowledgement on the source chain
+        ack_tx: R,
+        /// Acknowledgement written by the destination chain
+        ack: Binary,
+    },
+}
+
+/// Packet sent on a channel, as stored by the IBC module of the source chain
+pub struct SentPacket {
+    /// Port the packet was sent from
+    pub src_port: String,
+    /// Channel the packet was sent on
+    pub src_channel: String,
+    /// Port the packet is sent to
+    pub dst_port: String,
+    /// Channel end receiving the packet
+    pub dst_channel: String,
+    /// Data of the packet
+    pub data: Binary,
+    /// Timeout of the packet on the destination chain
+    pub timeout: IbcTimeout,
+}
+
+/// Chain running an in-process IBC module, whose packets can be relayed by the interchain environment
+pub trait IbcChain: IbcQueryHandler<Handler = ()> {
+    /// Ids of the connections to the chain `counterparty_chain_id`
+    fn connections(&self, counterparty_chain_id: &str) -> Result<Vec<String>, InterchainMockError>;
+
+    /// Chain id of the counterparty of the connection `connection_id`
+    fn connected_chain(&self, connection_id: &str) -> Result<String, InterchainMockError>;
+
+    /// Creates a connection to `counterparty` and returns its id on this chain
+    fn create_connection(&self, counterparty: &Self) -> Result<String, InterchainMockError>;
+
+    /// Runs the opening handshake of a channel with `counterparty` on the connection `connection_id`
+    fn create_channel(
+        &self,
+        counterparty: &Self,
+        connection_id: String,
+        src_port: &PortId,
+        dst_port: &PortId,
+        version: &str,
+        order: IbcOrder,
+    ) -> Result<OpenedChannel<<Self as TxHandler>::Response>, InterchainMockError>;
+
+    /// Relays a packet sent on this chain to `counterparty` and relays its acknowledgement or timeout back
+    fn relay_packet(
+        &self,
+        counterparty: &Self,
+        port: &PortId,
+        channel: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<RelayedPacket<<Self as TxHandler>::Response>, InterchainMockError>;
+
+    /// Delivers a packet sent on this chain to `counterparty`, without relaying its acknowledgement back.
+    /// `receiver` replaces the receiver of an ICS-20 transfer packet, for middlewares that receive the tokens themselves.
+    /// Returns the transaction of the reception and the acknowledgement written by `counterparty`
+    fn receive_packet(
+        &self,
+        counterparty: &Self,
+        port: &PortId,
+        channel: &ChannelId,
+        sequence: Sequence,
+        receiver: Option<&str>,
+    ) -> Result<(<Self as TxHandler>::Response, Binary), InterchainMockError>;
+
+    /// Packet sent on this chain with the given sequence
+    fn sent_packet(
+        &self,
+        port: &PortId,
+        channel: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<SentPacket, InterchainMockError>;
+
+    /// Acknowledges a packet sent on this chain with `ack`, without delivering it to its destination
+    fn acknowledge_packet(
+        &self,
+        port: &PortId,
+        channel: &ChannelId,
+        sequence: Sequence,
+        ack: Binary,
+    ) -> Result<<Self as TxHandler>::Response, InterchainMockError>;
+
+    /// Runs a step of the closing handshake on the channel end of this chain,
+    /// calling `ibc_channel_close` on the contract bound to the port
+    fn close_channel_end(
+        &self,
+        port: &PortId,
+        channel: &ChannelId,
+        init: bool,
+    ) -> Result<<Self as TxHandler>::Response, InterchainMockError>;
+
+    /// Updates the block of the chain
+    fn update_block(&self, action: impl FnOnce(&mut BlockInfo));
+
+    /// Executes `msg` on behalf of any address
+    fn execute_as(
+        &self,
+        sender: &Addr,
+        msg: CosmosMsg,
+    ) -> Result<<Self as TxHandler>::Response, InterchainMockError>;
+
+    /// Mints tokens to `address`
+    fn mint(
+        &self,
+        address: &str,
+        amount: Vec<Coin>,
+    ) -> Result<<Self as TxHandler>::Response, InterchainMockError>;
+
+    /// Converts a canonical address to the address format of the chain
+    fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr>;
+
+    /// Adds the events of `other` to `response`, when transactions run during the relaying belong to the same step
+    fn merge_responses(
+        response: &mut <Self as TxHandler>::Response,
+        other: <Self as TxHandler>::Response,
+    );
+}
+
+/// Implements [`IbcChain`] for an environment whose app is built with the `$multi_test` fork of cw-multi-test
+macro_rules! impl_ibc_chain {
+    ($(<$generic:ident: $bound:path>)? $chain:ty, $($multi_test:ident)::+) => {
+        impl$(<$generic: $bound>)? IbcChain for $chain {
+            fn connections(
+                &self,
+                counterparty_chain_id: &str,
+            ) -> Result<Vec<String>, InterchainMockError> {
+                let connections: Vec<(String, $($multi_test)::+::ibc::types::Connection)> =
+                    cosmwasm_std::from_json(self.app.borrow().ibc_query(
+                        $($multi_test)::+::ibc::types::MockIbcQuery::ChainConnections {
+                            chain_id: counterparty_chain_id.to_string(),
+                        },
+                    )?)?;
+                Ok(connections.into_iter().map(|(id, _)| id).collect())
+            }
+
+            fn connected_chain(&self, connection_id: &str) -> Result<String, InterchainMockError> {
+                let connection: $($multi_test)::+::ibc::types::Connection =
+                    cosmwasm_std::from_json(self.app.borrow().ibc_query(
+                        $($multi_test)::+::ibc::types::MockIbcQuery::ConnectedChain {
+                            connection_id: connection_id.to_string(),
+                        },
+                    )?)?;
+                Ok(connection.counterparty_chain_id)
+            }
+
+            fn create_connection(&self, counterparty: &Self) -> Result<String, InterchainMockError> {
+                let (connection_id, _) = $($multi_test)::+::ibc::relayer::create_connection(
+                    &mut self.app.borrow_mut(),
+                    &mut counterparty.app.borrow_mut(),
+                )?;
+                Ok(connection_id)
+            }
+
+            fn create_channel(
+                &self,
+                counterparty: &Self,
+                connection_id: String,
+                src_port: &PortId,
+                dst_port: &PortId,
+                version: &str,
+                order: IbcOrder,
+            ) -> Result<OpenedChannel<<Self as TxHandler>::Response>, InterchainMockError> {
+                let channel = $($multi_test)::+::ibc::relayer::create_channel(
+                    &mut self.app.borrow_mut(),
+                    &mut counterparty.app.borrow_mut(),
+                    connection_id,
+                    src_port.to_string(),
+                    dst_port.to_string(),
+                    version.to_string(),
+                    order,
+                )?;
+                Ok(OpenedChannel {
+                    src_channel: channel.src_channel,
+                    dst_channel: channel.dst_channel,
+                    txs: ChannelCreation {
+                        init: channel.init.into(),
+                        r#try: channel.r#try.into(),
+                        ack: channel.ack.into(),
+                        confirm: channel.confirm.into(),
+                    },
+                })
+            }
+
+            fn relay_packet(
+                &self,
+                counterparty: &Self,
+                port: &PortId,
+                channel: &ChannelId,
+                sequence: Sequence,
+            ) -> Result<RelayedPacket<<Self as TxHandler>::Response>, InterchainMockError> {
+                use $($multi_test)::+::ibc::relayer::{relay_packet, RelayingResult};
+
+                let relayed = relay_packet(
+                    &mut self.app.borrow_mut(),
+                    &mut counterparty.app.borrow_mut(),
+                    port.to_string(),
+                    channel.to_string(),
+                    sequence.into(),
+                )?;
+                Ok(match relayed.result {
+                    RelayingResult::Timeout {
+                        timeout_tx,
+                        close_channel_confirm,
+                    } => RelayedPacket::Timeout {
+                        timeout_tx: timeout_tx.into(),
+                        channel_closed: close_channel_confirm.is_some(),
+                    },
+                    RelayingResult::Acknowledgement { tx, ack } => RelayedPacket::Acknowledgement {
+                        receive_tx: relayed.receive_tx.into(),
+                        ack_tx: tx.into(),
+                        ack,
+                    },
+                })
+            }
+
+            fn receive_packet(
+                &self,
+                counterparty: &Self,
+                port: &PortId,
+                channel: &ChannelId,
+                sequence: Sequence,
+                receiver: Option<&str>,
+            ) -> Result<(<Self as TxHandler>::Response, Binary), InterchainMockError> {
+                let mut packet: $($multi_test)::+::ibc::types::IbcPacketData =
+                    cosmwasm_std::from_json(self.app.borrow().ibc_query(
+                        $($multi_test)::+::ibc::types::MockIbcQuery::SendPacket {
+                            channel_id: channel.to_string(),
+                            port_id: port.to_string(),
+                            sequence: sequence.into(),
+                        },
+                    )?)?;
+                if let Some(receiver) = receiver {
+                    let mut data: serde_json::Value = cosmwasm_std::from_json(&packet.data)?;
+                    data["receiver"] = receiver.into();
+                    packet.data = cosmwasm_std::to_json_binary(&data)?;
+                }
+                let response = counterparty.app.borrow_mut().sudo($($multi_test)::+::SudoMsg::Ibc(
+                    $($multi_test)::+::ibc::types::IbcPacketRelayingMsg::Receive { packet },
+                ))?;
+                let ack = response
+                    .events
+                    .iter()
+                    .filter(|e| e.ty == "write_acknowledgement")
+                    .flat_map(|e| e.attributes.iter())
+                    .find(|a| a.key == "packet_ack_hex")
+                    .ok_or_else(|| InterchainMockError::AcknowledgementMissing(sequence.to_string()))?;
+                let ack = cosmwasm_std::HexBinary::from_hex(&ack.value)?;
+                Ok((response.into(), Binary::from(ack.to_vec())))
+            }
+
+            fn sent_packet(
+                &self,
+                port: &PortId,
+                channel: &ChannelId,
+                sequence: Sequence,
+            ) -> Result<SentPacket, InterchainMockError> {
+                let packet: $($multi_test)::+::ibc::types::IbcPacketData =
+                    cosmwasm_std::from_json(self.app.borrow().ibc_query(
+                        $($multi_test)::+::ibc::types::MockIbcQuery::SendPacket {
+                            channel_id: channel.to_string(),
+                            port_id: port.to_string(),
+                            sequence: sequence.into(),
+                        },
+                    )?)?;
+                Ok(SentPacket {
+                    src_port: packet.src_port_id,
+                    src_channel: packet.src_channel_id,
+                    dst_port: packet.dst_port_id,
+                    dst_channel: packet.dst_channel_id,
+                    data: packet.data,
+                    timeout: packet.timeout,
+                })
+            }
+
+            fn acknowledge_packet(
+                &self,
+                port: &PortId,
+                channel: &ChannelId,
+                sequence: Sequence,
+                ack: Binary,
+            ) -> Result<<Self as TxHandler>::Response, InterchainMockError> {
+                let packet = cosmwasm_std::from_json(self.app.borrow().ibc_query(
+                    $($multi_test)::+::ibc::types::MockIbcQuery::SendPacket {
+                        channel_id: channel.to_string(),
+                        port_id: port.to_string(),
+                        sequence: sequence.into(),
+                    },
+                )?)?;
+                let response = self.app.borrow_mut().sudo($($multi_test)::+::SudoMsg::Ibc(
+                    $($multi_test)::+::ibc::types::IbcPacketRelayingMsg::Acknowledge { packet, ack },
+                ))?;
+                Ok(response.into())
+            }
+
+            fn close_channel_end(
+                &self,
+                port: &PortId,
+                channel: &ChannelId,
+                init: bool,
+            ) -> Result<<Self as TxHandler>::Response, InterchainMockError> {
+                let response = self.app.borrow_mut().sudo($($multi_test)::+::SudoMsg::Ibc(
+                    $($multi_test)::+::ibc::types::IbcPacketRelayingMsg::CloseChannel {
+                        port_id: port.to_string(),
+                        channel_id: channel.to_string(),
+                        init,
+                    },
+                ))?;
+                Ok(response.into())
+            }
+
+            fn update_block(&self, action: impl FnOnce(&mut BlockInfo)) {
+                self.app.borrow_mut().update_block(action)
+            }
+
+            fn execute_as(
+                &self,
+                sender: &Addr,
+                msg: CosmosMsg,
+            ) -> Result<<Self as TxHandler>::Response, InterchainMockError> {
+                use $($multi_test)::+::Executor;
+
+                let response = self.app.borrow_mut().execute(sender.clone(), msg)?;
+                Ok(response.into())
+            }
+
+            fn mint(
+                &self,
+                address: &str,
+                amount: Vec<Coin>,
+            ) -> Result<<Self as TxHandler>::Response, InterchainMockError> {
+                let response = self.app.borrow_mut().sudo($($multi_test)::+::SudoMsg::Bank(
+                    $($multi_test)::+::BankSudo::Mint {
+                        to_address: address.to_string(),
+                        amount,
+                    },
+                ))?;
+                Ok(response.into())
+            }
+
+            fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+                use cosmwasm_std::Api;
+
+                self.app.borrow().api().addr_humanize(canonical)
+            }
+
+            fn merge_responses(
+                response: &mut <Self as TxHandler>::Response,
+                other: <Self as TxHandler>::Response,
+            ) {
+                response.events.extend(other.events);
+            }
+        }
+    };
+}
+
+impl_ibc_chain!(<A: cosmwasm_std::Api> crate::interchain::MockBase<A>, cw_orch_mock::cw_multi_test);
+
+#[cfg(feature = "clone-testing")]
+impl_ibc_chain!(
+    cw_orch_clone_testing::CloneTesting,
+    cw_orch_clone_testing::cw_multi_test
+);
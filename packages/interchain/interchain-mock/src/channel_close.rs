@@ -0,0 +1,49 @@
+//! Closing of IBC channels between the chains of the interchain environment.
+use cw_orch_core::environment::TxHandler;
+use cw_orch_interchain_core::channel::IbcPort;
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
+
+use crate::{chain::IbcChain, interchain::MockInterchainEnvBase, InterchainMockError};
+
+/// End of a channel closed with [`InterchainEnv::close_channel`](cw_orch_interchain_core::InterchainEnv::close_channel)
+/// or because a packet timed out on an ordered channel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClosedChannel {
+    /// Chain of the channel end
+    pub chain_id: String,
+    /// Port of the channel end
+    pub port: PortId,
+    /// Id of the channel end
+    pub channel: ChannelId,
+}
+
+impl<Chain: IbcChain> MockInterchainEnvBase<Chain> {
+    /// Channel ends closed in the environment, in the order they were closed.
+    /// For ordered channels closed by a packet timeout, only the end on the chain that sent the packet is listed.
+    pub fn closed_channels(&self) -> Vec<ClosedChannel> {
+        self.closed.borrow().clone()
+    }
+
+    pub(crate) fn record_closed_channel(&self, chain_id: &str, port: &PortId, channel: &ChannelId) {
+        log::info!("IBC channel {port}:{channel} closed on {chain_id}");
+        self.closed.borrow_mut().push(ClosedChannel {
+            chain_id: chain_id.to_string(),
+            port: port.clone(),
+            channel: channel.clone(),
+        });
+    }
+}
+
+/// Runs a step of the closing handshake on one end of a channel, calling `ibc_channel_close` on the contract bound to the port
+pub(crate) fn close_channel_end<Chain: IbcChain>(
+    chain: &Chain,
+    port: &IbcPort<()>,
+    init: bool,
+) -> Result<(<Chain as TxHandler>::Response, ChannelId), InterchainMockError> {
+    let channel = port
+        .channel
+        .clone()
+        .ok_or_else(|| InterchainMockError::ChannelIdMissing(port.chain_id.clone()))?;
+    let response = chain.close_channel_end(&port.port, &channel, init)?;
+    Ok((response, channel))
+}
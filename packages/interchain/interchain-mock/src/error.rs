@@ -1,4 +1,5 @@
 use cosmwasm_std::StdError;
+use cw_orch_core::CwEnvError;
 use cw_orch_interchain_core::InterchainError;
 use thiserror::Error;
 
@@ -13,6 +14,9 @@ pub enum InterchainMockError {
     #[error(transparent)]
     StdError(#[from] StdError),
 
+    #[error(transparent)]
+    CwEnvError(#[from] CwEnvError),
+
     #[error("Error validating IBC structures {0}")]
     ValidationError(#[from] ibc_relayer_types::core::ics24_host::error::ValidationError),
 
@@ -24,6 +28,18 @@ pub enum InterchainMockError {
 
     #[error("mock for chain {0} not found")]
     MockNotFound(String),
+
+    #[error("packet {0} is not pending, it was already relayed or dropped")]
+    PacketNotPending(String),
+
+    #[error("packet {0} has no timeout")]
+    NoPacketTimeout(String),
+
+    #[error("no acknowledgement was written for packet {0}")]
+    AcknowledgementMissing(String),
+
+    #[error("channel id of the channel end on chain {0} is not known")]
+    ChannelIdMissing(String),
 }
 
 impl From<InterchainMockError> for InterchainError {
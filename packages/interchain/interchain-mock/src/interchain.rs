@@ -1,54 +1,67 @@
 #![warn(missing_docs)]
 
-use cosmwasm_std::{from_json, testing::MockApi, Api, Event, IbcOrder};
-use cw_orch_core::environment::QueryHandler;
+use cosmwasm_std::{Event, IbcOrder};
+use cw_orch_core::environment::{IndexResponse, QueryHandler, TxHandler};
 use cw_orch_interchain_core::{
     channel::InterchainChannel,
-    env::{ChainId, ChannelCreation},
+    env::{ChainId, ChannelClose},
     results::{ChannelCreationTransactionsResult, InternalChannelCreationResult},
     IbcPacketInfo, IbcPacketOutcome, InterchainEnv, NestedPacketsFlow, SinglePacketFlow, TxId,
 };
-use cw_orch_mock::{
-    cw_multi_test::{
-        ibc::{
-            relayer::{self, ChannelCreationResult},
-            types::{Connection, MockIbcQuery},
-        },
-        AppResponse, MockApiBech32,
-    },
-    Mock, MockBech32, MockState,
-};
+use cw_orch_mock::{Mock, MockBech32, MockState};
 use ibc_relayer_types::core::{
     ics04_channel::packet::Sequence,
     ics24_host::identifier::{ChannelId, PortId},
 };
 use std::{
+    cell::RefCell,
     collections::{HashMap, HashSet},
+    rc::Rc,
     str::FromStr,
 };
 
-use crate::InterchainMockError;
+use crate::{
+    chain::{IbcChain, OpenedChannel, RelayedPacket},
+    channel_close::close_channel_end,
+    ClosedChannel, InterchainMockError, PendingPacket,
+};
 
 pub type MockBase<A> = cw_orch_mock::MockBase<A, MockState>;
 
-/// Interchain environment for cw_multi_test Mock environment
-/// This leverages Abstract's fork of cw_multi_test enabling IBC interactions
-pub struct MockInterchainEnvBase<A: Api> {
+/// Identifies a packet by its source chain, port, channel and sequence
+pub(crate) type PacketId = (String, PortId, ChannelId, Sequence);
+
+type RelayedPackets<Chain> = HashMap<PacketId, IbcPacketOutcome<NestedPacketsFlow<Chain>>>;
+
+/// Interchain environment relaying IBC packets in-process between chains backed by a cw-multi-test app.
+/// This leverages Abstract's fork of cw_multi_test enabling IBC interactions,
+/// it's used with Mock chains and, with the `clone-testing` feature, with chains forked by CloneTesting.
+pub struct MockInterchainEnvBase<Chain: IbcChain> {
     /// Mock chains registered within the structure
-    pub mocks: HashMap<String, MockBase<A>>,
+    pub mocks: HashMap<String, Chain>,
+    /// Packets held with [`MockInterchainEnvBase::hold_packets`]
+    pub(crate) pending: Rc<RefCell<Vec<PendingPacket>>>,
+    /// Channels closed in the environment, see [`MockInterchainEnvBase::closed_channels`]
+    pub(crate) closed: Rc<RefCell<Vec<ClosedChannel>>>,
+    /// Flows of the packets relayed before the transaction sending them is followed,
+    /// as the packets forwarded by the packet-forward-middleware
+    pub(crate) relayed: Rc<RefCell<RelayedPackets<Chain>>>,
 }
 
-impl<A: Api> Clone for MockInterchainEnvBase<A> {
+impl<Chain: IbcChain> Clone for MockInterchainEnvBase<Chain> {
     fn clone(&self) -> Self {
         Self {
             mocks: self.mocks.clone(),
+            pending: self.pending.clone(),
+            closed: self.closed.clone(),
+            relayed: self.relayed.clone(),
         }
     }
 }
 
-impl<A: Api> MockInterchainEnvBase<A> {
+impl<Chain: IbcChain> MockInterchainEnvBase<Chain> {
     /// Create an interchain structure from mocks
-    pub fn from_mocks(chains: Vec<MockBase<A>>) -> Self {
+    pub fn from_mocks(chains: Vec<Chain>) -> Self {
         Self {
             mocks: chains
                 .iter()
@@ -57,11 +70,14 @@ impl<A: Api> MockInterchainEnvBase<A> {
                     (chain_id, d.clone())
                 })
                 .collect(),
+            pending: Default::default(),
+            closed: Default::default(),
+            relayed: Default::default(),
         }
     }
 
     /// Adds additional mocks to the interchain environment
-    pub fn add_mocks(&mut self, mocks: Vec<MockBase<A>>) {
+    pub fn add_mocks(&mut self, mocks: Vec<Chain>) {
         self.mocks.extend(
             mocks
                 .iter()
@@ -72,7 +88,7 @@ impl<A: Api> MockInterchainEnvBase<A> {
 type Sender<'a> = &'a str;
 type Prefix = &'static str;
 
-impl MockInterchainEnvBase<MockApi> {
+impl MockInterchainEnvBase<Mock> {
     /// Creates a mock environments and associated mock chains from
     /// 1. Chain id
     /// 2. Sender address
@@ -91,11 +107,14 @@ impl MockInterchainEnvBase<MockApi> {
                     (chain_id.to_string(), mock)
                 })
                 .collect(),
+            pending: Default::default(),
+            closed: Default::default(),
+            relayed: Default::default(),
         }
     }
 }
 
-impl MockInterchainEnvBase<MockApiBech32> {
+impl MockInterchainEnvBase<MockBech32> {
     /// Creates a mock environments and associated mock chains from
     /// 1. Chain id
     /// 2. Chain pub address prefix ("cosmos", "juno", etc.)
@@ -108,17 +127,37 @@ impl MockInterchainEnvBase<MockApiBech32> {
                     (chain_id.to_string(), mock)
                 })
                 .collect(),
+            pending: Default::default(),
+            closed: Default::default(),
+            relayed: Default::default(),
         }
     }
 }
 
-impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
-    type ChannelCreationResult = ChannelCreationResult;
+#[cfg(feature = "clone-testing")]
+impl MockInterchainEnvBase<cw_orch_clone_testing::CloneTesting> {
+    /// Forks the given chains with the default state.
+    ///
+    /// The IBC state of the forked chains isn't cloned: connections and channels need to be created locally,
+    /// with [`InterchainEnv::create_channel`], before the contracts can exchange packets.
+    pub fn new(
+        chains: Vec<impl Into<cw_orch_core::environment::ChainInfoOwned>>,
+    ) -> Result<Self, InterchainMockError> {
+        let chains = chains
+            .into_iter()
+            .map(cw_orch_clone_testing::CloneTesting::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_mocks(chains))
+    }
+}
+
+impl<Chain: IbcChain> InterchainEnv<Chain> for MockInterchainEnvBase<Chain> {
+    type ChannelCreationResult = OpenedChannel<<Chain as TxHandler>::Response>;
 
     type Error = InterchainMockError;
 
     /// Get the daemon for a network-id in the interchain.
-    fn get_chain(&self, chain_id: impl ToString) -> Result<MockBase<A>, InterchainMockError> {
+    fn get_chain(&self, chain_id: impl ToString) -> Result<Chain, InterchainMockError> {
         self.mocks
             .get(&chain_id.to_string())
             .ok_or(InterchainMockError::MockNotFound(chain_id.to_string()))
@@ -134,7 +173,7 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         dst_port: &PortId,
         version: &str,
         order: Option<IbcOrder>,
-    ) -> Result<InternalChannelCreationResult<ChannelCreationResult>, Self::Error> {
+    ) -> Result<InternalChannelCreationResult<Self::ChannelCreationResult>, Self::Error> {
         if src_chain.eq(dst_chain) {
             panic!("You can't create an interchain connection between the same chain (because of rust borrow mut rules) {}", src_chain);
         }
@@ -147,30 +186,21 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         // We need to specify the connection id no ?
         // We need to register connections if we want to create channels !
         // We connect the first connection
-        let connections: Vec<(String, Connection)> = from_json(src_mock.app.borrow().ibc_query(
-            MockIbcQuery::ChainConnections {
-                chain_id: dst_chain.to_string(),
-            },
-        )?)?;
+        let connections = src_mock.connections(dst_chain)?;
 
         // We verify there is a connection. If there is none, we create one
-        let connection_id = if let Some((connection_id, _)) = connections.first() {
+        let connection_id = if let Some(connection_id) = connections.first() {
             connection_id.clone()
         } else {
-            let (src_connection_id, _) = relayer::create_connection(
-                &mut src_mock.app.borrow_mut(),
-                &mut dst_mock.app.borrow_mut(),
-            )?;
-            src_connection_id
+            src_mock.create_connection(&dst_mock)?
         };
 
-        let channel_creation = relayer::create_channel(
-            &mut src_mock.app.borrow_mut(),
-            &mut dst_mock.app.borrow_mut(),
+        let channel_creation = src_mock.create_channel(
+            &dst_mock,
             connection_id.clone(),
-            src_port.to_string(),
-            dst_port.to_string(),
-            version.to_string(),
+            src_port,
+            dst_port,
+            version,
             order.unwrap_or(IbcOrder::Unordered),
         )?;
 
@@ -196,34 +226,26 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         &self,
         _src_chain: ChainId,
         _ibc_channel: &mut InterchainChannel<()>,
-        channel_creation_result: ChannelCreationResult,
-    ) -> Result<ChannelCreationTransactionsResult<MockBase<A>>, Self::Error> {
-        let ChannelCreationResult {
+        channel_creation_result: Self::ChannelCreationResult,
+    ) -> Result<ChannelCreationTransactionsResult<Chain>, Self::Error> {
+        let OpenedChannel {
             src_channel,
             dst_channel,
-            init,
-            r#try,
-            ack,
-            confirm,
+            txs,
         } = channel_creation_result;
 
         Ok(ChannelCreationTransactionsResult {
             src_channel_id: ChannelId::from_str(&src_channel)?,
             dst_channel_id: ChannelId::from_str(&dst_channel)?,
-            channel_creation_txs: ChannelCreation {
-                init,
-                r#try,
-                ack,
-                confirm,
-            },
+            channel_creation_txs: txs,
         })
     }
 
     fn await_packets(
         &self,
         chain_id: ChainId,
-        tx_response: AppResponse,
-    ) -> Result<NestedPacketsFlow<MockBase<A>>, Self::Error> {
+        tx_response: <Chain as TxHandler>::Response,
+    ) -> Result<NestedPacketsFlow<Chain>, Self::Error> {
         // We start by analyzing sent packets in the response
         let packets = find_ibc_packets_sent_in_tx(&self.get_chain(chain_id)?, &tx_response)?;
 
@@ -232,6 +254,14 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         let packet_analysis = packets
             .iter()
             .map(|packet| {
+                if let Some(outcome) = self
+                    .relayed
+                    .borrow_mut()
+                    .remove(&packet_id(chain_id, packet))
+                {
+                    return Ok(outcome);
+                }
+
                 let ibc_result = self.await_single_packet(
                     chain_id,
                     packet.src_port.clone(),
@@ -289,27 +319,41 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         src_channel: ChannelId,
         dst_chain: ChainId,
         sequence: Sequence,
-    ) -> Result<SinglePacketFlow<MockBase<A>>, Self::Error> {
+    ) -> Result<SinglePacketFlow<Chain>, Self::Error> {
+        // Transfers handled by a middleware are acknowledged once the middleware ran
+        if let Some(outcome) =
+            self.relay_through_middlewares(src_chain, &src_port, &src_channel, dst_chain, sequence)?
+        {
+            return Ok(SinglePacketFlow {
+                send_tx: None,
+                outcome,
+            });
+        }
+
         let src_mock = self.get_chain(src_chain)?;
         let dst_mock = self.get_chain(dst_chain)?;
 
         // We get the packet data from the chain directly
-        let relay_result = relayer::relay_packet(
-            &mut src_mock.app.borrow_mut(),
-            &mut dst_mock.app.borrow_mut(),
-            src_port.to_string(),
-            src_channel.to_string(),
-            sequence.into(),
-        )?;
+        let relay_result = src_mock.relay_packet(&dst_mock, &src_port, &src_channel, sequence)?;
 
-        let outcome = match relay_result.result {
-            relayer::RelayingResult::Timeout {
+        let outcome = match relay_result {
+            RelayedPacket::Timeout {
                 timeout_tx,
-                close_channel_confirm: _,
-            } => IbcPacketOutcome::Timeout {
-                timeout_tx: TxId::new(src_chain.to_string(), timeout_tx),
-            },
-            relayer::RelayingResult::Acknowledgement { tx, ack } => {
+                channel_closed,
+            } => {
+                // Ordered channels are closed when one of their packets times out
+                if channel_closed {
+                    self.record_closed_channel(src_chain, &src_port, &src_channel);
+                }
+                IbcPacketOutcome::Timeout {
+                    timeout_tx: TxId::new(src_chain.to_string(), timeout_tx),
+                }
+            }
+            RelayedPacket::Acknowledgement {
+                receive_tx,
+                ack_tx,
+                ack,
+            } => {
                 let ack_string =
                     serde_json::from_slice(ack.as_slice()).unwrap_or(format!("{:x?}", ack));
 
@@ -321,8 +365,8 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
                     ack_string,
                 );
                 IbcPacketOutcome::Success {
-                    receive_tx: TxId::new(dst_chain.to_string(), relay_result.receive_tx),
-                    ack_tx: TxId::new(src_chain.to_string(), tx),
+                    receive_tx: TxId::new(dst_chain.to_string(), receive_tx),
+                    ack_tx: TxId::new(src_chain.to_string(), ack_tx),
                     ack,
                 }
             }
@@ -336,22 +380,35 @@ impl<A: Api> InterchainEnv<MockBase<A>> for MockInterchainEnvBase<A> {
         Ok(analysis_result)
     }
 
-    fn chains<'a>(&'a self) -> impl Iterator<Item = &'a MockBase<A>>
+    fn close_channel(
+        &self,
+        src_chain: ChainId,
+        ibc_channel: &InterchainChannel<()>,
+    ) -> Result<ChannelClose<<Chain as TxHandler>::Response>, Self::Error> {
+        let (src_port, dst_port) = ibc_channel.get_ordered_ports_from(src_chain)?;
+
+        let (init, src_channel) = close_channel_end(&self.get_chain(src_chain)?, &src_port, true)?;
+        self.record_closed_channel(src_chain, &src_port.port, &src_channel);
+
+        let (confirm, dst_channel) =
+            close_channel_end(&self.get_chain(&dst_port.chain_id)?, &dst_port, false)?;
+        self.record_closed_channel(&dst_port.chain_id, &dst_port.port, &dst_channel);
+
+        Ok(ChannelClose { init, confirm })
+    }
+
+    fn chains<'a>(&'a self) -> impl Iterator<Item = &'a Chain>
     where
-        MockBase<A>: 'a,
+        Chain: 'a,
     {
         self.mocks.values()
     }
 }
 
-fn get_events(tx: &AppResponse, event: &str) -> Vec<Event> {
-    tx.events
-        .iter()
-        .filter(|e| e.ty == event)
-        .cloned()
-        .collect()
+pub(crate) fn get_events(tx: &impl IndexResponse, event: &str) -> Vec<Event> {
+    tx.events().into_iter().filter(|e| e.ty == event).collect()
 }
-fn get_all_events_values(events: &[Event], attribute: &str) -> Vec<String> {
+pub(crate) fn get_all_events_values(events: &[Event], attribute: &str) -> Vec<String> {
     events
         .iter()
         .flat_map(|e| {
@@ -363,9 +420,18 @@ fn get_all_events_values(events: &[Event], attribute: &str) -> Vec<String> {
         .collect()
 }
 
-fn find_ibc_packets_sent_in_tx<A: Api>(
-    chain: &MockBase<A>,
-    tx: &AppResponse,
+pub(crate) fn packet_id(chain_id: ChainId, packet: &IbcPacketInfo) -> PacketId {
+    (
+        chain_id.to_string(),
+        packet.src_port.clone(),
+        packet.src_channel.clone(),
+        packet.sequence,
+    )
+}
+
+pub(crate) fn find_ibc_packets_sent_in_tx<Chain: IbcChain>(
+    chain: &Chain,
+    tx: &<Chain as TxHandler>::Response,
 ) -> Result<Vec<IbcPacketInfo>, InterchainMockError> {
     let send_packet_events = get_events(tx, "send_packet");
     if send_packet_events.is_empty() {
@@ -379,13 +445,7 @@ fn find_ibc_packets_sent_in_tx<A: Api>(
     let packet_datas = get_all_events_values(&send_packet_events, "packet_data");
     let chain_ids = connections
         .iter()
-        .map(|c| {
-            let connection: Connection =
-                from_json(chain.app.borrow().ibc_query(MockIbcQuery::ConnectedChain {
-                    connection_id: c.to_string(),
-                })?)?;
-            Ok::<_, InterchainMockError>(connection.counterparty_chain_id)
-        })
+        .map(|c| chain.connected_chain(c))
         .collect::<Result<Vec<_>, _>>()?;
 
     let mut ibc_packets = vec![];
@@ -1,11 +1,23 @@
-//! Implementation of the interchain traits for the [cw_orch::prelude::Mock] environment
+//! Implementation of the interchain traits for the [cw_orch::prelude::Mock] environment,
+//! and for the `CloneTesting` environment with the `clone-testing` feature
 
+mod chain;
+mod channel_close;
 mod error;
 mod interchain;
+mod middleware;
+mod packet_control;
 
-use cosmwasm_std::testing::MockApi;
-use cw_orch_mock::cw_multi_test::MockApiBech32;
+pub use chain::{IbcChain, OpenedChannel, RelayedPacket, SentPacket};
+pub use channel_close::ClosedChannel;
+use cw_orch_mock::{Mock, MockBech32};
 pub use error::InterchainMockError;
+pub use interchain::MockInterchainEnvBase;
+pub use middleware::ibc_denom;
+pub use packet_control::PendingPacket;
 
-pub type MockInterchainEnv = interchain::MockInterchainEnvBase<MockApi>;
-pub type MockBech32InterchainEnv = interchain::MockInterchainEnvBase<MockApiBech32>;
+pub type MockInterchainEnv = MockInterchainEnvBase<Mock>;
+pub type MockBech32InterchainEnv = MockInterchainEnvBase<MockBech32>;
+/// Interchain environment relaying IBC packets between chains forked with `CloneTesting`
+#[cfg(feature = "clone-testing")]
+pub type CloneInterchainEnv = MockInterchainEnvBase<cw_orch_clone_testing::CloneTesting>;
@@ -0,0 +1,535 @@
+//! Emulation of the IBC middlewares stacked on top of the ICS-20 transfer application.
+//!
+//! The IBC module of cw-multi-test only runs the transfer application. The transfer packets relayed by the
+//! [`MockInterchainEnvBase`] are then processed according to their memo, once received and before their acknowledgement
+//! is written back to the source chain:
+//! - `{"forward": {"receiver", "port", "channel", "timeout", "next"}}` is handled as by the
+//!   [packet-forward-middleware](https://github.com/cosmos/ibc-apps/tree/main/middleware/packet-forward-middleware):
+//!   the tokens are received by the intermediate receiver derived from the channel and the original sender, whatever the receiver
+//!   of the packet, and sent to the next chain with `next` as memo. The acknowledgement is asynchronous: the forwarded packet is relayed first and its acknowledgement is
+//!   the one of the packet it comes from.
+//! - `{"wasm": {"contract", "msg"}}` is handled as by [ibc-hooks](https://github.com/cosmos/ibc-apps/tree/main/modules/ibc-hooks):
+//!   the contract is executed with the received tokens, by the intermediate sender derived from the channel and the original sender.
+//!
+//! The transactions of the middlewares are added to the receive transaction of the packet, so that the packets they send are followed by
+//! [`InterchainEnv::await_packets`]. When a middleware fails, the reception of the tokens is reverted and the packet is acknowledged
+//! with an error, so that the transfer application of the source chain refunds the sender.
+use std::time::Duration;
+
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, BankMsg, Binary, BlockInfo, CanonicalAddr, Coin,
+    CosmosMsg, HexBinary, IbcMsg, IbcTimeout, StdAck, StdError, Uint128, WasmMsg,
+};
+use cw_orch_core::environment::{IndexResponse, QueryHandler, TxHandler};
+use cw_orch_interchain_core::{env::ChainId, IbcPacketOutcome, InterchainEnv, TxId};
+use ibc_relayer_types::core::{
+    ics04_channel::packet::Sequence,
+    ics24_host::identifier::{ChannelId, PortId},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    chain::IbcChain,
+    interchain::{find_ibc_packets_sent_in_tx, get_events, packet_id, MockInterchainEnvBase},
+    InterchainMockError,
+};
+
+/// Prefix of the hash deriving the intermediate sender of ibc-hooks
+const IBC_HOOKS_SENDER_PREFIX: &str = "ibc-wasm-hook-intermediary";
+
+/// Prefix of the hash deriving the intermediate receiver of the packet-forward-middleware, spelled as its module name
+const FORWARD_RECEIVER_PREFIX: &str = "packetfowardmiddleware";
+
+/// Timeout of the forwarded packets when the forward memo doesn't specify one, as in the packet-forward-middleware
+const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// ICS-20 packet data
+#[derive(Deserialize)]
+struct FungibleTokenPacketData {
+    denom: String,
+    amount: Uint128,
+    sender: String,
+    receiver: String,
+    #[serde(default)]
+    memo: String,
+}
+
+/// Memo keys handled by the middlewares
+#[derive(Deserialize)]
+struct Memo {
+    forward: Option<ForwardMetadata>,
+    wasm: Option<WasmHook>,
+}
+
+#[derive(Deserialize)]
+struct ForwardMetadata {
+    receiver: String,
+    port: String,
+    channel: String,
+    timeout: Option<serde_json::Value>,
+    next: Option<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct WasmHook {
+    contract: String,
+    msg: serde_json::Value,
+}
+
+/// Acknowledgement of the ibc-hooks, parsed by [`IbcAckParser::ibc_hooks_ack`](cw_orch_interchain_core::IbcAckParser::ibc_hooks_ack)
+#[derive(Serialize)]
+struct IbcHooksAck {
+    contract_result: Option<Binary>,
+    ibc_ack: Binary,
+}
+
+/// Received transfer packet, on the destination chain
+struct ReceivedTransfer {
+    data: FungibleTokenPacketData,
+    /// Denom of the tokens on the destination chain
+    denom: String,
+    dst_channel: String,
+    timeout: IbcTimeout,
+    /// Whether the tokens are unwound back to the destination chain, released from escrow instead of minted
+    unwound: bool,
+}
+
+/// Returns the denom of the vouchers of `trace`, e.g. `transfer/channel-0/uatom`, as defined by ICS-20
+pub fn ibc_denom(trace: &str) -> String {
+    format!(
+        "ibc/{}",
+        HexBinary::from(Sha256::digest(trace.as_bytes()).to_vec())
+            .to_hex()
+            .to_uppercase()
+    )
+}
+
+/// Returns the denom of tokens received on `dst_port/dst_channel` from `src_port/src_channel`, with `denom` being the denom of the packet
+fn received_denom(
+    src_port: &str,
+    src_channel: &str,
+    dst_port: &str,
+    dst_channel: &str,
+    denom: &str,
+) -> String {
+    match denom.strip_prefix(&format!("{src_port}/{src_channel}/")) {
+        // The tokens are coming back to the chain they were sent from
+        Some(unwound) => local_denom(unwound),
+        None => ibc_denom(&format!("{dst_port}/{dst_channel}/{denom}")),
+    }
+}
+
+/// Returns the denom of the bank module for a denom trace
+fn local_denom(trace: &str) -> String {
+    if trace.contains('/') {
+        ibc_denom(trace)
+    } else {
+        trace.to_string()
+    }
+}
+
+impl<Chain: IbcChain> MockInterchainEnvBase<Chain> {
+    /// Returns the address executing the contracts called with ibc-hooks on `chain_id`,
+    /// for a transfer sent by `original_sender` and received on `channel`
+    pub fn ibc_hooks_sender(
+        &self,
+        chain_id: ChainId,
+        channel: &ChannelId,
+        original_sender: &str,
+    ) -> Result<Addr, InterchainMockError> {
+        let hash = derivation_hash(IBC_HOOKS_SENDER_PREFIX, channel, original_sender);
+        let sender = self
+            .get_chain(chain_id)?
+            .addr_humanize(&CanonicalAddr::from(hash))?;
+        Ok(sender)
+    }
+
+    /// Returns the address holding the tokens forwarded by the packet-forward-middleware on `chain_id`,
+    /// for a transfer sent by `original_sender` and received on `channel`
+    pub fn forward_receiver(
+        &self,
+        chain_id: ChainId,
+        channel: &ChannelId,
+        original_sender: &str,
+    ) -> Result<Addr, InterchainMockError> {
+        let hash = derivation_hash(FORWARD_RECEIVER_PREFIX, channel, original_sender);
+        let receiver = self
+            .get_chain(chain_id)?
+            .addr_humanize(&CanonicalAddr::from(&hash[..20]))?;
+        Ok(receiver)
+    }
+
+    /// Relays a transfer packet whose memo is handled by a middleware: the packet is received on `dst_chain`,
+    /// the middleware runs and the resulting acknowledgement is relayed back to `src_chain`.
+    /// Returns `None` for the other packets and the timed out transfers, relayed as usual.
+    pub(crate) fn relay_through_middlewares(
+        &self,
+        src_chain: ChainId,
+        src_port: &PortId,
+        src_channel: &ChannelId,
+        dst_chain: ChainId,
+        sequence: Sequence,
+    ) -> Result<Option<IbcPacketOutcome<TxId<Chain>>>, InterchainMockError> {
+        if *src_port != PortId::transfer() {
+            return Ok(None);
+        }
+        let src_mock = self.get_chain(src_chain)?;
+        let dst_mock = self.get_chain(dst_chain)?;
+        let Some((transfer, memo)) = received_transfer(&src_mock, src_port, src_channel, sequence)?
+        else {
+            return Ok(None);
+        };
+        if (memo.forward.is_none() && memo.wasm.is_none())
+            || has_timed_out(&transfer.timeout, &dst_mock.block_info()?)
+        {
+            return Ok(None);
+        }
+
+        // The packet-forward-middleware receives the tokens on its intermediate receiver
+        let forward_holder = match memo.forward {
+            Some(_) => {
+                let channel = transfer.dst_channel.parse::<ChannelId>()?;
+                Some(self.forward_receiver(dst_chain, &channel, &transfer.data.sender)?)
+            }
+            None => None,
+        };
+        let recipient = forward_holder
+            .as_ref()
+            .map_or(transfer.data.receiver.as_str(), Addr::as_str);
+
+        let (mut receive_tx, ack) = src_mock.receive_packet(
+            &dst_mock,
+            src_port,
+            src_channel,
+            sequence,
+            forward_holder.as_ref().map(Addr::as_str),
+        )?;
+        // The error acknowledgements of the transfer application are relayed as is
+        let (src_ack, ack) = if !is_success_ack(&ack) {
+            (ack.clone(), ack)
+        } else {
+            let escrow = if transfer.unwound {
+                Some(escrow_account(&receive_tx, recipient)?)
+            } else {
+                None
+            };
+            let middleware_result = match (memo.forward, forward_holder.as_ref(), memo.wasm) {
+                (Some(forward), Some(holder), _) => self
+                    .forward_transfer(dst_chain, &transfer, forward, holder, &mut receive_tx)
+                    .map(|forward_ack| (forward_ack.clone(), forward_ack)),
+                (None, _, Some(hook)) => self
+                    .execute_wasm_hook(dst_chain, &transfer, hook, ack.clone(), &mut receive_tx)
+                    .map(|hooks_ack| (ack, hooks_ack)),
+                _ => unreachable!("transfers without middleware memo are relayed as usual"),
+            };
+            match middleware_result {
+                Ok(acks) => acks,
+                Err(MiddlewareError { holder, error }) => {
+                    log::info!(
+                        "IBC middleware failed for packet n°{} on {}, reverting the transfer: {}",
+                        sequence,
+                        dst_chain,
+                        error
+                    );
+                    let revert = revert_transfer(&dst_mock, &holder, &transfer, escrow)?;
+                    Chain::merge_responses(&mut receive_tx, revert);
+                    let error_ack = StdAck::error(error).to_binary();
+                    (error_ack.clone(), error_ack)
+                }
+            }
+        };
+
+        let ack_tx = src_mock.acknowledge_packet(src_port, src_channel, sequence, src_ack)?;
+        log::info!(
+            "IBC packet n°{}, relayed through the middlewares of {} and acknowledged on {}",
+            sequence,
+            dst_chain,
+            src_chain,
+        );
+        Ok(Some(IbcPacketOutcome::Success {
+            receive_tx: TxId::new(dst_chain.to_string(), receive_tx),
+            ack_tx: TxId::new(src_chain.to_string(), ack_tx),
+            ack,
+        }))
+    }
+
+    /// Sends the tokens received by the intermediate receiver `holder` to the next chain,
+    /// and returns the acknowledgement of the forwarded packet once it is relayed
+    fn forward_transfer(
+        &self,
+        dst_chain: ChainId,
+        transfer: &ReceivedTransfer,
+        forward: ForwardMetadata,
+        holder: &Addr,
+        receive_tx: &mut <Chain as TxHandler>::Response,
+    ) -> Result<Binary, MiddlewareError> {
+        let dst_mock = self
+            .get_chain(dst_chain)
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        if forward.port != PortId::transfer().as_str() {
+            return Err(MiddlewareError::new(
+                holder,
+                format!(
+                    "packets can only be forwarded on the transfer port, not {}",
+                    forward.port
+                ),
+            ));
+        }
+
+        let timeout = forward_timeout(forward.timeout.as_ref())
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        let memo = match forward.next {
+            None => None,
+            Some(serde_json::Value::String(next)) => Some(next),
+            Some(next) => Some(next.to_string()),
+        };
+
+        let amount = Coin::new(transfer.data.amount, &transfer.denom);
+        let block = dst_mock
+            .block_info()
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        let forward_tx = dst_mock
+            .execute_as(
+                holder,
+                CosmosMsg::Ibc(IbcMsg::Transfer {
+                    channel_id: forward.channel,
+                    to_address: forward.receiver,
+                    amount,
+                    timeout: IbcTimeout::with_timestamp(
+                        block.time.plus_nanos(timeout.as_nanos() as u64),
+                    ),
+                    memo,
+                }),
+            )
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        Chain::merge_responses(receive_tx, forward_tx.clone());
+
+        // The forwarded packet is relayed before the packet it comes from is acknowledged.
+        // Its flow is kept for when the receive transaction is followed.
+        let forwarded_packets = find_ibc_packets_sent_in_tx(&dst_mock, &forward_tx)
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        let forwarded = self
+            .await_packets(dst_chain, forward_tx)
+            .map_err(|e| MiddlewareError::new(holder, e))?;
+        let outcome = forwarded.packets.first().cloned();
+        self.relayed.borrow_mut().extend(
+            forwarded_packets
+                .iter()
+                .map(|packet| packet_id(dst_chain, packet))
+                .zip(forwarded.packets),
+        );
+
+        // The tokens of a failed forward are refunded to the intermediate receiver by the transfer application
+        match outcome {
+            Some(IbcPacketOutcome::Success { ack, .. }) if is_success_ack(&ack) => Ok(ack),
+            Some(IbcPacketOutcome::Success { ack, .. }) => Err(MiddlewareError::new(
+                holder,
+                format!(
+                    "forwarded packet failed: {}",
+                    String::from_utf8_lossy(ack.as_slice())
+                ),
+            )),
+            Some(IbcPacketOutcome::Timeout { .. }) => {
+                Err(MiddlewareError::new(holder, "forwarded packet timed out"))
+            }
+            None => Err(MiddlewareError::new(holder, "no packet was forwarded")),
+        }
+    }
+
+    /// Executes the contract of the hook with the received tokens, as the intermediate sender,
+    /// and returns the acknowledgement of ibc-hooks
+    fn execute_wasm_hook(
+        &self,
+        dst_chain: ChainId,
+        transfer: &ReceivedTransfer,
+        hook: WasmHook,
+        ibc_ack: Binary,
+        receive_tx: &mut <Chain as TxHandler>::Response,
+    ) -> Result<Binary, MiddlewareError> {
+        let receiver = Addr::unchecked(&transfer.data.receiver);
+        if hook.contract != transfer.data.receiver {
+            return Err(MiddlewareError::new(
+                &receiver,
+                "the receiver of the transfer must be the contract of the wasm hook",
+            ));
+        }
+        let dst_mock = self
+            .get_chain(dst_chain)
+            .map_err(|e| MiddlewareError::new(&receiver, e))?;
+        let sender = transfer
+            .dst_channel
+            .parse::<ChannelId>()
+            .map_err(InterchainMockError::from)
+            .and_then(|channel| self.ibc_hooks_sender(dst_chain, &channel, &transfer.data.sender))
+            .map_err(|e| MiddlewareError::new(&receiver, e))?;
+
+        // The tokens are received by the intermediate sender, that sends them along with the execution
+        let funds = coins(transfer.data.amount.u128(), &transfer.denom);
+        let hold_tx = dst_mock
+            .execute_as(
+                &receiver,
+                CosmosMsg::Bank(BankMsg::Send {
+                    to_address: sender.to_string(),
+                    amount: funds.clone(),
+                }),
+            )
+            .map_err(|e| MiddlewareError::new(&receiver, e))?;
+        Chain::merge_responses(receive_tx, hold_tx);
+
+        let msg = to_json_binary(&hook.msg).map_err(|e| MiddlewareError::new(&sender, e))?;
+        let execute_tx = dst_mock
+            .execute_as(
+                &sender,
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: receiver.to_string(),
+                    msg,
+                    funds,
+                }),
+            )
+            .map_err(|e| MiddlewareError::new(&sender, e))?;
+        let contract_result = execute_tx.data();
+        Chain::merge_responses(receive_tx, execute_tx);
+
+        to_json_binary(&IbcHooksAck {
+            contract_result,
+            ibc_ack,
+        })
+        .map_err(|e| MiddlewareError::new(&receiver, e))
+    }
+}
+
+/// Reads the data of a transfer packet, along with its memo if it's a JSON object
+fn received_transfer<Chain: IbcChain>(
+    src_mock: &Chain,
+    src_port: &PortId,
+    src_channel: &ChannelId,
+    sequence: Sequence,
+) -> Result<Option<(ReceivedTransfer, Memo)>, InterchainMockError> {
+    let packet = src_mock.sent_packet(src_port, src_channel, sequence)?;
+    let data: FungibleTokenPacketData = from_json(&packet.data)?;
+    let Ok(memo) = serde_json::from_str::<Memo>(&data.memo) else {
+        return Ok(None);
+    };
+
+    let unwound = data
+        .denom
+        .starts_with(&format!("{}/{}/", packet.src_port, packet.src_channel));
+    let transfer = ReceivedTransfer {
+        denom: received_denom(
+            &packet.src_port,
+            &packet.src_channel,
+            &packet.dst_port,
+            &packet.dst_channel,
+            &data.denom,
+        ),
+        dst_channel: packet.dst_channel,
+        timeout: packet.timeout,
+        unwound,
+        data,
+    };
+    Ok(Some((transfer, memo)))
+}
+
+/// Returns the escrow account that released the tokens received by `receiver` in `receive_tx`
+fn escrow_account(
+    receive_tx: &impl IndexResponse,
+    receiver: &str,
+) -> Result<Addr, InterchainMockError> {
+    get_events(receive_tx, "transfer")
+        .iter()
+        .find(|e| {
+            e.attributes
+                .iter()
+                .any(|a| a.key == "recipient" && a.value == receiver)
+        })
+        .and_then(|e| e.attributes.iter().find(|a| a.key == "sender"))
+        .map(|a| Addr::unchecked(&a.value))
+        .ok_or_else(|| {
+            StdError::generic_err(format!("no escrow released tokens to {receiver}")).into()
+        })
+}
+
+/// Reverts the reception of the tokens held by `holder`: the minted vouchers are burned
+/// and the unwound tokens are sent back to the escrow account that released them
+fn revert_transfer<Chain: IbcChain>(
+    dst_mock: &Chain,
+    holder: &Addr,
+    transfer: &ReceivedTransfer,
+    escrow: Option<Addr>,
+) -> Result<<Chain as TxHandler>::Response, InterchainMockError> {
+    let amount = coins(transfer.data.amount.u128(), &transfer.denom);
+    let msg = match escrow {
+        None => BankMsg::Burn { amount },
+        Some(escrow) => BankMsg::Send {
+            to_address: escrow.to_string(),
+            amount,
+        },
+    };
+    dst_mock.execute_as(holder, CosmosMsg::Bank(msg))
+}
+
+/// Hash deriving the intermediate accounts of the middlewares, as `address.Hash` of the cosmos-sdk
+fn derivation_hash(prefix: &str, channel: &ChannelId, original_sender: &str) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(Sha256::digest(prefix.as_bytes()))
+        .chain_update(format!("{channel}/{original_sender}").as_bytes())
+        .finalize()
+        .to_vec()
+}
+
+/// Whether a packet with `timeout` can't be received anymore at `block` of the destination chain
+fn has_timed_out(timeout: &IbcTimeout, block: &BlockInfo) -> bool {
+    timeout
+        .block()
+        .is_some_and(|timeout| block.height >= timeout.height)
+        || timeout
+            .timestamp()
+            .is_some_and(|timestamp| block.time >= timestamp)
+}
+
+/// Failure of a middleware, with the account holding the received tokens
+struct MiddlewareError {
+    holder: Addr,
+    error: String,
+}
+
+impl MiddlewareError {
+    fn new(holder: &Addr, error: impl ToString) -> Self {
+        Self {
+            holder: holder.clone(),
+            error: error.to_string(),
+        }
+    }
+}
+
+fn is_success_ack(ack: &Binary) -> bool {
+    matches!(from_json::<StdAck>(ack), Ok(StdAck::Success(_)))
+}
+
+/// Parses the timeout of a forward memo: nanoseconds or a duration such as `10m`
+fn forward_timeout(timeout: Option<&serde_json::Value>) -> Result<Duration, String> {
+    let invalid = || format!("invalid forward timeout {timeout:?}");
+    match timeout {
+        None => Ok(DEFAULT_FORWARD_TIMEOUT),
+        Some(serde_json::Value::Number(nanos)) => {
+            nanos.as_u64().map(Duration::from_nanos).ok_or_else(invalid)
+        }
+        Some(serde_json::Value::String(duration)) => {
+            let split = duration
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(invalid)?;
+            let (value, unit) = duration.split_at(split);
+            let value: u64 = value.parse().map_err(|_| invalid())?;
+            match unit {
+                "ns" => Ok(Duration::from_nanos(value)),
+                "ms" => Ok(Duration::from_millis(value)),
+                "s" => Ok(Duration::from_secs(value)),
+                "m" => Ok(Duration::from_secs(value * 60)),
+                "h" => Ok(Duration::from_secs(value * 3600)),
+                _ => Err(invalid()),
+            }
+        }
+        Some(_) => Err(invalid()),
+    }
+}
@@ -0,0 +1,179 @@
+//! Manual relaying of IBC packets, to exercise the timeout and error acknowledgement paths of contracts.
+//!
+//! Instead of relaying the packets sent by a transaction with [`InterchainEnv::await_packets`],
+//! the packets can be held with [`MockInterchainEnvBase::hold_packets`] and then relayed in any order,
+//! dropped, timed out or acknowledged with an error.
+use cosmwasm_std::{Binary, StdAck, Timestamp};
+use cw_orch_core::environment::TxHandler;
+use cw_orch_interchain_core::{env::ChainId, InterchainEnv, SinglePacketFlow};
+use ibc_relayer_types::core::{
+    ics04_channel::packet::Sequence,
+    ics24_host::identifier::{ChannelId, PortId},
+};
+
+use crate::{
+    chain::IbcChain,
+    interchain::{
+        find_ibc_packets_sent_in_tx, get_all_events_values, get_events, MockInterchainEnvBase,
+    },
+    InterchainMockError,
+};
+
+/// Packet sent by a transaction and held, waiting to be relayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPacket {
+    /// Chain the packet was sent from
+    pub src_chain: String,
+    /// Port the packet was sent from
+    pub src_port: PortId,
+    /// Channel the packet was sent on
+    pub src_channel: ChannelId,
+    /// Chain the packet is sent to
+    pub dst_chain: String,
+    /// Sequence of the packet on its channel
+    pub sequence: Sequence,
+    /// Block height of the destination chain after which the packet times out
+    pub timeout_height: Option<u64>,
+    /// Block time of the destination chain after which the packet times out
+    pub timeout_timestamp: Option<Timestamp>,
+}
+
+impl<Chain: IbcChain> MockInterchainEnvBase<Chain> {
+    /// Holds the packets sent in `tx` instead of relaying them, and returns them.
+    /// They are relayed, dropped or timed out later with the other methods of the environment.
+    pub fn hold_packets(
+        &self,
+        chain_id: ChainId,
+        tx: &<Chain as TxHandler>::Response,
+    ) -> Result<Vec<PendingPacket>, InterchainMockError> {
+        let packets = find_ibc_packets_sent_in_tx(&self.get_chain(chain_id)?, tx)?;
+
+        let send_packet_events = get_events(tx, "send_packet");
+        let timeout_heights = get_all_events_values(&send_packet_events, "packet_timeout_height");
+        let timeout_timestamps =
+            get_all_events_values(&send_packet_events, "packet_timeout_timestamp");
+
+        let held: Vec<_> = packets
+            .into_iter()
+            .enumerate()
+            .map(|(i, packet)| PendingPacket {
+                src_chain: chain_id.to_string(),
+                src_port: packet.src_port,
+                src_channel: packet.src_channel,
+                dst_chain: packet.dst_chain_id,
+                sequence: packet.sequence,
+                timeout_height: timeout_heights.get(i).and_then(|h| parse_timeout_height(h)),
+                timeout_timestamp: timeout_timestamps
+                    .get(i)
+                    .and_then(|t| t.parse::<u64>().ok())
+                    .filter(|t| *t > 0)
+                    .map(Timestamp::from_nanos),
+            })
+            .collect();
+
+        self.pending.borrow_mut().extend(held.clone());
+        Ok(held)
+    }
+
+    /// Packets held and not relayed yet, in the order they were sent
+    pub fn pending_packets(&self) -> Vec<PendingPacket> {
+        self.pending.borrow().clone()
+    }
+
+    /// Relays a held packet to its destination chain and relays its acknowledgement back.
+    /// The packet stays pending if relaying fails.
+    pub fn relay(
+        &self,
+        packet: &PendingPacket,
+    ) -> Result<SinglePacketFlow<Chain>, InterchainMockError> {
+        self.position_pending(packet)?;
+        let flow = self.await_single_packet(
+            &packet.src_chain,
+            packet.src_port.clone(),
+            packet.src_channel.clone(),
+            &packet.dst_chain,
+            packet.sequence,
+        )?;
+        self.take_pending(packet)?;
+        Ok(flow)
+    }
+
+    /// Drops a held packet, as if no relayer ever picked it up
+    pub fn drop_packet(&self, packet: &PendingPacket) -> Result<(), InterchainMockError> {
+        self.take_pending(packet)
+    }
+
+    /// Times out a held packet: the destination chain is advanced past the timeout of the packet
+    /// and the timeout is relayed back to the source chain.
+    pub fn timeout(
+        &self,
+        packet: &PendingPacket,
+    ) -> Result<SinglePacketFlow<Chain>, InterchainMockError> {
+        if packet.timeout_height.is_none() && packet.timeout_timestamp.is_none() {
+            return Err(InterchainMockError::NoPacketTimeout(
+                packet.sequence.to_string(),
+            ));
+        }
+        self.position_pending(packet)?;
+        let dst_mock = self.get_chain(&packet.dst_chain)?;
+        dst_mock.update_block(|block| {
+            if let Some(height) = packet.timeout_height {
+                block.height = block.height.max(height + 1);
+            }
+            if let Some(timestamp) = packet.timeout_timestamp {
+                block.time = block.time.max(timestamp.plus_nanos(1));
+            }
+        });
+        self.relay(packet)
+    }
+
+    /// Acknowledges a held packet with an error on its source chain, without delivering it to the destination chain
+    pub fn error_ack(
+        &self,
+        packet: &PendingPacket,
+        error: impl Into<String>,
+    ) -> Result<<Chain as TxHandler>::Response, InterchainMockError> {
+        self.acknowledge(packet, StdAck::error(error).to_binary())
+    }
+
+    /// Acknowledges a held packet with `ack` on its source chain, without delivering it to the destination chain.
+    /// The packet stays pending if the acknowledgement fails.
+    pub fn acknowledge(
+        &self,
+        packet: &PendingPacket,
+        ack: Binary,
+    ) -> Result<<Chain as TxHandler>::Response, InterchainMockError> {
+        self.position_pending(packet)?;
+        let src_mock = self.get_chain(&packet.src_chain)?;
+        let response = src_mock.acknowledge_packet(
+            &packet.src_port,
+            &packet.src_channel,
+            packet.sequence,
+            ack,
+        )?;
+        self.take_pending(packet)?;
+        Ok(response)
+    }
+
+    /// Returns the position of a packet in the pending packets, errors if it's not pending
+    fn position_pending(&self, packet: &PendingPacket) -> Result<usize, InterchainMockError> {
+        self.pending
+            .borrow()
+            .iter()
+            .position(|p| p == packet)
+            .ok_or_else(|| InterchainMockError::PacketNotPending(packet.sequence.to_string()))
+    }
+
+    /// Removes a packet from the pending packets
+    fn take_pending(&self, packet: &PendingPacket) -> Result<(), InterchainMockError> {
+        let position = self.position_pending(packet)?;
+        self.pending.borrow_mut().remove(position);
+        Ok(())
+    }
+}
+
+/// Parses the `{revision}-{height}` timeout height of a packet, `0-0` meaning no timeout height
+fn parse_timeout_height(height: &str) -> Option<u64> {
+    let (_, height) = height.split_once('-')?;
+    height.parse().ok().filter(|h| *h > 0)
+}
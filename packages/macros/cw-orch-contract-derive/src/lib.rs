@@ -1,7 +1,7 @@
 #![recursion_limit = "128"]
 
-use syn::{Expr, Token};
 use syn::{__private::TokenStream2, parse_macro_input, Fields, GenericArgument, Item, Path};
+use syn::{Expr, Token};
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
@@ -14,14 +14,15 @@ use syn::parse::{Parse, ParseStream};
 
 mod kw {
     syn::custom_keyword!(id);
+    syn::custom_keyword!(custom);
 }
-// This is used to parse the types into a list of types separated by Commas
-// and default contract id if provided by "id = $expr"
+// This is used to parse the types into a list of types separated by Commas,
+// the default contract id if provided by "id = $expr"
+// and the custom message and query types if provided by "custom = ($msg, $query)"
 struct InterfaceInput {
     expressions: Punctuated<Path, Comma>,
-    _kw_id: Option<kw::id>,
-    _eq_token: Option<Token![=]>,
     default_id: Option<Expr>,
+    custom_types: Option<(Path, Path)>,
 }
 
 // Implement the `Parse` trait for your input struct
@@ -29,34 +30,59 @@ impl Parse for InterfaceInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut expressions: Punctuated<Path, Comma> = Punctuated::new();
 
-        while let Ok(path) = input.parse() {
+        // Message types come first, until a named argument is found
+        while !input.peek(kw::id) && !input.peek(kw::custom) {
+            let Ok(path) = input.parse() else {
+                break;
+            };
             expressions.push(path);
             let _: Option<Token![,]> = input.parse().ok();
+        }
 
-            // If we found id = break
+        let mut default_id = None;
+        let mut custom_types = None;
+        while !input.is_empty() {
             if input.peek(kw::id) {
-                break;
+                input.parse::<kw::id>()?;
+                input.parse::<Token![=]>().map_err(|_| {
+                    syn::Error::new(
+                        input.span(),
+                        "The id argument of the macro should be of the format `id=my_contract_id`",
+                    )
+                })?;
+                default_id = input.parse().ok();
+            } else if input.peek(kw::custom) {
+                input.parse::<kw::custom>()?;
+                let custom_types_error = |span| {
+                    syn::Error::new(
+                        span,
+                        "The custom argument of the macro should be of the format `custom=(CustomMsg, CustomQuery)`",
+                    )
+                };
+                input
+                    .parse::<Token![=]>()
+                    .map_err(|_| custom_types_error(input.span()))?;
+                let content;
+                syn::parenthesized!(content in input);
+                let types: Punctuated<Path, Comma> =
+                    content.parse_terminated(Path::parse, Token![,])?;
+                if types.len() != 2 {
+                    return Err(custom_types_error(content.span()));
+                }
+                custom_types = Some((types[0].clone(), types[1].clone()));
+            } else {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "The 5th argument of the macro should be of the format `id=my_contract_id` or `custom=(CustomMsg, CustomQuery)`",
+                ));
             }
+            let _: Option<Token![,]> = input.parse().ok();
         }
-        // Parse if there is any
-        let kw_id: Option<kw::id> = input.parse().map_err(|_| {
-            syn::Error::new(
-                input.span(),
-                "The 5th argument of the macro should be of the format `id=my_contract_id`",
-            )
-        })?;
-        let eq_token: Option<Token![=]> = input.parse().map_err(|_| {
-            syn::Error::new(
-                input.span(),
-                "The 5th argument of the macro should be of the format `id=my_contract_id`",
-            )
-        })?;
-        let default_id: Option<Expr> = input.parse().ok();
+
         Ok(Self {
             expressions,
-            _kw_id: kw_id,
-            _eq_token: eq_token,
             default_id,
+            custom_types,
         })
     }
 }
@@ -139,6 +165,27 @@ impl <Chain> Uploadable for Cw20<Chain> {
         WasmPath::new("path/to/cw20.wasm").unwrap()
     }
 }
+```
+
+Contracts using custom messages and queries (e.g. `NeutronMsg`) declare their custom types with `custom = (CustomMsg, CustomQuery)`
+and provide a custom wrapper instead, used by mock environments created with the same custom module types (`MockBase::new_with_custom_module`).
+
+```ignore
+use cw_orch::prelude::*;
+
+#[interface(InstantiateMsg, ExecuteMsg, QueryMsg, Empty, custom = (NeutronMsg, NeutronQuery))]
+pub struct NeutronContract;
+
+impl <Chain> Uploadable for NeutronContract<Chain> {
+    fn custom_wrapper() -> Box<dyn MockContract<NeutronMsg, NeutronQuery>> {
+        Box::new(ContractWrapper::new(
+            neutron_contract::contract::execute,
+            neutron_contract::contract::instantiate,
+            neutron_contract::contract::query,
+        ))
+    }
+}
+```
 */
 #[proc_macro_attribute]
 pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
@@ -149,6 +196,13 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
 
     let types_in_order = attributes.expressions;
     let default_id = attributes.default_id;
+    let (custom_msg, custom_query) = match attributes.custom_types {
+        Some((msg, query)) => (quote!(#msg), quote!(#query)),
+        None => (
+            quote!(::cw_orch::prelude::Empty),
+            quote!(::cw_orch::prelude::Empty),
+        ),
+    };
 
     if types_in_order.len() != 4 {
         panic!("Expected four endpoint types (InstantiateMsg, ExecuteMsg, QueryMsg, MigrateMsg). Use cosmwasm_std::Empty if not implemented.")
@@ -249,6 +303,12 @@ pub fn interface(attrs: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        impl<Chain, #all_generics> ::cw_orch::core::contract::interface_traits::ContractCustomTypes for #name<Chain, #all_generics> {
+            type ExecC = #custom_msg;
+            type QueryC = #custom_query;
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         impl<Chain, #all_generics> ::cw_orch::core::contract::interface_traits::InstantiableContract for #name<Chain, #all_generics> #all_debug_serialize {
             type InstantiateMsg = #init;